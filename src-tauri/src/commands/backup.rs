@@ -1,7 +1,7 @@
 use std::fs::{self, File};
 use std::io::{Read, Write, Cursor};
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager, Runtime};
+use tauri::{AppHandle, Manager, Runtime, State};
 use walkdir::WalkDir;
 use zip::write::FileOptions;
 use reqwest::Client;
@@ -9,6 +9,11 @@ use chrono::Local;
 use base64::{Engine as _, engine::general_purpose};
 use serde::Serialize; 
 use regex::Regex;
+use sqlx::{Pool, Sqlite};
+
+use crate::state::AppState;
+use crate::commands::dialer::{spawn_bridge, ProxyBridge};
+use crate::commands::proxy::internal_get_proxy;
 
 type CommandResult<T> = Result<T, String>;
 
@@ -71,6 +76,22 @@ fn load_webdav_password<R: Runtime>(app: &AppHandle<R>) -> CommandResult<String>
     decrypt_data(&content)
 }
 
+// 🟢 [新增] WebDAV 客户端：指定代理时 reqwest 经本地桥走拨号层 (HTTP CONNECT / SOCKS5 / SOCKS4a)
+// 返回的 ProxyBridge 需与 Client 同生命周期，Drop 时关闭桥
+async fn webdav_client(pool: &Pool<Sqlite>, proxy_id: Option<String>) -> CommandResult<(Client, Option<ProxyBridge>)> {
+    let Some(pid) = proxy_id.filter(|id| !id.is_empty()) else {
+        return Ok((Client::new(), None));
+    };
+
+    let proxy = internal_get_proxy(pool, &pid).await?;
+    let bridge = spawn_bridge(proxy).await?;
+    let client = Client::builder()
+        .proxy(reqwest::Proxy::all(bridge.proxy_url()).map_err(|e| e.to_string())?)
+        .build()
+        .map_err(|e| e.to_string())?;
+    Ok((client, Some(bridge)))
+}
+
 // =================================================================
 // 🚀 业务命令
 // =================================================================
@@ -79,9 +100,12 @@ fn load_webdav_password<R: Runtime>(app: &AppHandle<R>) -> CommandResult<String>
 #[tauri::command]
 pub async fn check_webdav<R: Runtime>(
     app: AppHandle<R>,
+    state: State<'_, AppState>,
     url: String, 
     username: String, 
-    password: Option<String> 
+    password: Option<String>,
+    // 🟢 [新增] 可选代理 (proxies 表 ID)
+    proxy_id: Option<String>
 ) -> CommandResult<String> {
     
     let actual_password = match password {
@@ -89,7 +113,7 @@ pub async fn check_webdav<R: Runtime>(
         _ => load_webdav_password(&app).map_err(|_| "Password required (not saved locally)".to_string())?
     };
 
-    let client = Client::new();
+    let (client, _bridge) = webdav_client(&state.db, proxy_id).await?;
     let res = client.request(reqwest::Method::from_bytes(b"PROPFIND").unwrap(), &url)
         .basic_auth(username, Some(actual_password))
         .header("Depth", "0")
@@ -108,12 +132,14 @@ pub async fn check_webdav<R: Runtime>(
 #[tauri::command]
 pub async fn create_cloud_backup<R: Runtime>(
     app: AppHandle<R>,
+    state: State<'_, AppState>,
     url: String,
     username: String,
     password: Option<String>,
     // 🟢 [新增] 接收前端传来的设备信息
     device_name: String,
-    device_id: String
+    device_id: String,
+    proxy_id: Option<String>
 ) -> CommandResult<String> {
     
     let actual_password = match password {
@@ -196,7 +222,7 @@ pub async fn create_cloud_backup<R: Runtime>(
     let file_content = fs::read(&zip_path).map_err(|e| e.to_string())?;
     let upload_url = format!("{}/{}", url.trim_end_matches('/'), filename);
 
-    let client = Client::new();
+    let (client, _bridge) = webdav_client(&state.db, proxy_id).await?;
     let res = client.put(&upload_url)
         .basic_auth(username, Some(actual_password))
         .body(file_content)
@@ -225,9 +251,11 @@ pub struct CloudBackupFile {
 #[tauri::command]
 pub async fn get_backup_list<R: Runtime>(
     app: AppHandle<R>,
+    state: State<'_, AppState>,
     url: String, 
     username: String, 
-    password: Option<String>
+    password: Option<String>,
+    proxy_id: Option<String>
 ) -> CommandResult<Vec<CloudBackupFile>> {
     
     let actual_password = match password {
@@ -235,7 +263,7 @@ pub async fn get_backup_list<R: Runtime>(
         _ => load_webdav_password(&app)?
     };
 
-    let client = Client::new();
+    let (client, _bridge) = webdav_client(&state.db, proxy_id).await?;
     let res = client.request(reqwest::Method::from_bytes(b"PROPFIND").unwrap(), &url)
         .basic_auth(username, Some(actual_password))
         .header("Depth", "1")
@@ -292,10 +320,12 @@ pub async fn get_backup_list<R: Runtime>(
 #[tauri::command]
 pub async fn delete_cloud_backup<R: Runtime>(
     app: AppHandle<R>,
+    state: State<'_, AppState>,
     url: String,
     username: String,
     password: Option<String>,
-    filename: String
+    filename: String,
+    proxy_id: Option<String>
 ) -> CommandResult<String> {
     
     let actual_password = match password {
@@ -304,7 +334,7 @@ pub async fn delete_cloud_backup<R: Runtime>(
     };
 
     let delete_url = format!("{}/{}", url.trim_end_matches('/'), filename);
-    let client = Client::new();
+    let (client, _bridge) = webdav_client(&state.db, proxy_id).await?;
     
     let res = client.delete(&delete_url)
         .basic_auth(username, Some(actual_password))
@@ -323,10 +353,12 @@ pub async fn delete_cloud_backup<R: Runtime>(
 #[tauri::command]
 pub async fn restore_cloud_backup<R: Runtime>(
     app: AppHandle<R>,
+    state: State<'_, AppState>,
     url: String,
     username: String,
     password: Option<String>,
-    filename: String
+    filename: String,
+    proxy_id: Option<String>
 ) -> CommandResult<String> {
     
     let actual_password = match password {
@@ -334,7 +366,7 @@ pub async fn restore_cloud_backup<R: Runtime>(
         _ => load_webdav_password(&app)?
     };
 
-    let client = Client::new();
+    let (client, _bridge) = webdav_client(&state.db, proxy_id).await?;
     let download_url = format!("{}/{}", url.trim_end_matches('/'), filename);
 
     let res = client.get(&download_url)
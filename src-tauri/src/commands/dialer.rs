@@ -0,0 +1,877 @@
+// src-tauri/src/commands/dialer.rs
+// 代理拨号层：按 proxies 表配置建立到目标主机的 TCP 隧道
+// 支持 HTTP CONNECT (Basic 认证)、SOCKS5 (用户名/密码)、SOCKS4a
+// 不支持 HTTPS 代理 (到代理本身走 TLS)：按明文 CONNECT 处理会把认证信息明文发出，直接拒绝
+
+use std::net::{IpAddr, SocketAddr};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::models::Proxy;
+
+// 代理响应头上限 (防止恶意代理无限输出)
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+
+/// 建立到 host:port 的 TCP 连接；proxy 为 None 时直连
+pub async fn dial(proxy: Option<&Proxy>, host: &str, port: u16) -> Result<TcpStream, String> {
+    let Some(proxy) = proxy else {
+        let stream = TcpStream::connect((host, port))
+            .await
+            .map_err(|e| format!("Connection failed: {}", e))?;
+        let _ = stream.set_nodelay(true);
+        return Ok(stream);
+    };
+    // 在连接代理之前拒绝，避免任何数据以明文发出
+    if proxy.proxy_type.eq_ignore_ascii_case("https") {
+        return Err(format!(
+            "Proxy {}: HTTPS proxies (TLS to the proxy) are not supported; use an HTTP or SOCKS5 proxy",
+            proxy.name
+        ));
+    }
+
+    let mut stream = TcpStream::connect((proxy.host.as_str(), proxy.port))
+        .await
+        .map_err(|e| format!("Proxy {}:{} unreachable: {}", proxy.host, proxy.port, e))?;
+    let _ = stream.set_nodelay(true);
+
+    let result = match proxy.proxy_type.to_lowercase().as_str() {
+        "http" => http_connect(&mut stream, proxy, host, port).await,
+        "socks5" | "socks5h" => socks5_connect(&mut stream, proxy, host, port).await,
+        "socks4" | "socks4a" => socks4a_connect(&mut stream, proxy, host, port).await,
+        other => Err(format!("Unsupported proxy type: {}", other)),
+    };
+
+    result
+        .map(|_| stream)
+        .map_err(|e| format!("Proxy {} ({}:{}): {}", proxy.name, proxy.host, proxy.port, e))
+}
+
+fn credentials(proxy: &Proxy) -> Option<(&str, &str)> {
+    let user = proxy.username.as_deref().filter(|u| !u.is_empty())?;
+    Some((user, proxy.password.as_deref().unwrap_or("")))
+}
+
+// IPv6 字面量需要加方括号
+fn authority(host: &str, port: u16) -> String {
+    if host.contains(':') && !host.starts_with('[') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+fn io_err(e: std::io::Error) -> String {
+    e.to_string()
+}
+
+// ==============================================================================
+// 🟢 HTTP CONNECT
+// ==============================================================================
+
+async fn http_connect(stream: &mut TcpStream, proxy: &Proxy, host: &str, port: u16) -> Result<(), String> {
+    let target = authority(host, port);
+    let mut request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", target);
+    if let Some((user, pass)) = credentials(proxy) {
+        let token = BASE64.encode(format!("{}:{}", user, pass));
+        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", token));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await.map_err(io_err)?;
+
+    // 逐字节读取响应头，不能多读，否则会吞掉隧道内 SSH 的 banner
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_HEADER_BYTES {
+            return Err("CONNECT response header too large".to_string());
+        }
+        let n = stream.read(&mut byte).await.map_err(io_err)?;
+        if n == 0 {
+            return Err("Proxy closed the connection during CONNECT".to_string());
+        }
+        head.push(byte[0]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let status_line = head.lines().next().unwrap_or_default();
+    let code = status_line.split_whitespace().nth(1).unwrap_or_default();
+    match code {
+        "200" => Ok(()),
+        "407" => Err("Proxy authentication required (407)".to_string()),
+        _ => Err(format!("CONNECT rejected: {}", status_line.trim())),
+    }
+}
+
+// ==============================================================================
+// 🟢 SOCKS5 (RFC 1928 / RFC 1929)
+// ==============================================================================
+
+async fn socks5_connect(stream: &mut TcpStream, proxy: &Proxy, host: &str, port: u16) -> Result<(), String> {
+    let creds = credentials(proxy);
+
+    // 1. 协商认证方式：0x00 无认证，0x02 用户名/密码
+    let greeting: &[u8] = if creds.is_some() { &[0x05, 0x02, 0x00, 0x02] } else { &[0x05, 0x01, 0x00] };
+    stream.write_all(greeting).await.map_err(io_err)?;
+
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).await.map_err(io_err)?;
+    if reply[0] != 0x05 {
+        return Err("Not a SOCKS5 proxy".to_string());
+    }
+
+    match reply[1] {
+        0x00 => {}
+        0x02 => {
+            let (user, pass) = creds.ok_or("SOCKS5 proxy requires username/password")?;
+            if user.len() > 255 || pass.len() > 255 {
+                return Err("SOCKS5 credentials too long".to_string());
+            }
+            let mut auth = vec![0x01, user.len() as u8];
+            auth.extend_from_slice(user.as_bytes());
+            auth.push(pass.len() as u8);
+            auth.extend_from_slice(pass.as_bytes());
+            stream.write_all(&auth).await.map_err(io_err)?;
+
+            let mut status = [0u8; 2];
+            stream.read_exact(&mut status).await.map_err(io_err)?;
+            if status[1] != 0x00 {
+                return Err("SOCKS5 authentication failed".to_string());
+            }
+        }
+        0xFF => return Err("SOCKS5 proxy rejected all authentication methods".to_string()),
+        m => return Err(format!("SOCKS5 proxy selected unsupported method 0x{:02x}", m)),
+    }
+
+    // 2. CONNECT 请求：IP 字面量直接发送，域名交给代理解析 (远端 DNS)
+    let mut request = vec![0x05, 0x01, 0x00];
+    match host.trim_matches(|c| c == '[' || c == ']').parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(0x01);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(0x04);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            if host.len() > 255 {
+                return Err("Target hostname too long for SOCKS5".to_string());
+            }
+            request.push(0x03);
+            request.push(host.len() as u8);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).await.map_err(io_err)?;
+
+    // 3. 应答：VER REP RSV ATYP BND.ADDR BND.PORT
+    let mut head = [0u8; 4];
+    stream.read_exact(&mut head).await.map_err(io_err)?;
+    if head[1] != 0x00 {
+        return Err(socks5_error(head[1]));
+    }
+    let addr_len = match head[3] {
+        0x01 => 4,
+        0x04 => 16,
+        0x03 => {
+            let mut len = [0u8; 1];
+            stream.read_exact(&mut len).await.map_err(io_err)?;
+            len[0] as usize
+        }
+        t => return Err(format!("SOCKS5 reply has invalid address type 0x{:02x}", t)),
+    };
+    let mut bound = vec![0u8; addr_len + 2];
+    stream.read_exact(&mut bound).await.map_err(io_err)?;
+    Ok(())
+}
+
+fn socks5_error(code: u8) -> String {
+    let reason = match code {
+        0x01 => "general SOCKS server failure",
+        0x02 => "connection not allowed by ruleset",
+        0x03 => "network unreachable",
+        0x04 => "host unreachable",
+        0x05 => "connection refused",
+        0x06 => "TTL expired",
+        0x07 => "command not supported",
+        0x08 => "address type not supported",
+        _ => "unknown error",
+    };
+    format!("SOCKS5 connect failed: {} (0x{:02x})", reason, code)
+}
+
+// ==============================================================================
+// 🟢 SOCKS4 / SOCKS4a (域名时使用 0.0.0.x 占位，由代理解析)
+// ==============================================================================
+
+async fn socks4a_connect(stream: &mut TcpStream, proxy: &Proxy, host: &str, port: u16) -> Result<(), String> {
+    let user_id = proxy.username.as_deref().unwrap_or("");
+
+    let mut request = vec![0x04, 0x01];
+    request.extend_from_slice(&port.to_be_bytes());
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.extend_from_slice(&ip.octets());
+            request.extend_from_slice(user_id.as_bytes());
+            request.push(0x00);
+        }
+        Ok(IpAddr::V6(_)) => return Err("SOCKS4 does not support IPv6 targets".to_string()),
+        Err(_) => {
+            request.extend_from_slice(&[0, 0, 0, 1]);
+            request.extend_from_slice(user_id.as_bytes());
+            request.push(0x00);
+            request.extend_from_slice(host.as_bytes());
+            request.push(0x00);
+        }
+    }
+    stream.write_all(&request).await.map_err(io_err)?;
+
+    let mut reply = [0u8; 8];
+    stream.read_exact(&mut reply).await.map_err(io_err)?;
+    match reply[1] {
+        0x5A => Ok(()),
+        0x5B => Err("SOCKS4 request rejected or failed".to_string()),
+        0x5C | 0x5D => Err("SOCKS4 identd authentication failed".to_string()),
+        c => Err(format!("SOCKS4 unexpected reply 0x{:02x}", c)),
+    }
+}
+
+// ==============================================================================
+// 🟢 本地 HTTP 代理桥 (reqwest 无法注入自定义连接器)
+// WebDAV 客户端把 127.0.0.1 上的桥当作 HTTP 代理，桥再经 dial() 连接真实目标
+// ==============================================================================
+
+/// 本地代理桥，Drop 时关闭监听
+pub struct ProxyBridge {
+    pub addr: SocketAddr,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl ProxyBridge {
+    pub fn proxy_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+impl Drop for ProxyBridge {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+pub async fn spawn_bridge(proxy: Proxy) -> Result<ProxyBridge, String> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))
+        .await
+        .map_err(|e| format!("Failed to start proxy bridge: {}", e))?;
+    let addr = listener.local_addr().map_err(io_err)?;
+
+    let task = tauri::async_runtime::spawn(async move {
+        while let Ok((client, _)) = listener.accept().await {
+            let proxy = proxy.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = bridge_connection(client, &proxy).await {
+                    eprintln!("[proxy-bridge] {}", e);
+                }
+            });
+        }
+    });
+
+    Ok(ProxyBridge { addr, task })
+}
+
+async fn bridge_connection(mut client: TcpStream, proxy: &Proxy) -> Result<(), String> {
+    // 读取请求头 (可能连带部分请求体)
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() >= MAX_HEADER_BYTES {
+            return Err("Request header too large".to_string());
+        }
+        let n = client.read(&mut chunk).await.map_err(io_err)?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+    let request_line = head.lines().next().unwrap_or_default().to_string();
+    let mut parts = request_line.split_whitespace();
+    let (method, target, version) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(m), Some(t), Some(v)) => (m, t, v),
+        _ => return Err(format!("Malformed request line: {}", request_line)),
+    };
+
+    if method.eq_ignore_ascii_case("CONNECT") {
+        // HTTPS：建立隧道后双向转发
+        let (host, port) = split_host_port(target, 443)?;
+        let mut upstream = match dial(Some(proxy), &host, port).await {
+            Ok(s) => s,
+            Err(e) => {
+                let _ = client.write_all(b"HTTP/1.1 502 Bad Gateway\r\n\r\n").await;
+                return Err(e);
+            }
+        };
+        client
+            .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+            .await
+            .map_err(io_err)?;
+        upstream.write_all(&buf[head_end..]).await.map_err(io_err)?;
+        tokio::io::copy_bidirectional(&mut client, &mut upstream).await.map_err(io_err)?;
+    } else {
+        // 明文 HTTP：绝对 URI 改写为 origin-form 后转发
+        let rest = target
+            .strip_prefix("http://")
+            .ok_or_else(|| format!("Unsupported proxy request target: {}", target))?;
+        let (authority_part, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        let (host, port) = split_host_port(authority_part, 80)?;
+        let mut upstream = match dial(Some(proxy), &host, port).await {
+            Ok(s) => s,
+            Err(e) => {
+                let _ = client.write_all(b"HTTP/1.1 502 Bad Gateway\r\n\r\n").await;
+                return Err(e);
+            }
+        };
+
+        let rewritten = head.replacen(&request_line, &format!("{} {} {}", method, path, version), 1);
+        upstream.write_all(rewritten.as_bytes()).await.map_err(io_err)?;
+        upstream.write_all(&buf[head_end..]).await.map_err(io_err)?;
+        tokio::io::copy_bidirectional(&mut client, &mut upstream).await.map_err(io_err)?;
+    }
+    Ok(())
+}
+
+fn split_host_port(authority: &str, default_port: u16) -> Result<(String, u16), String> {
+    // [v6]:port
+    if let Some(rest) = authority.strip_prefix('[') {
+        let (host, tail) = rest.split_once(']').ok_or("Invalid IPv6 authority")?;
+        let port = match tail.strip_prefix(':') {
+            Some(p) => p.parse().map_err(|_| format!("Invalid port in {}", authority))?,
+            None => default_port,
+        };
+        return Ok((host.to_string(), port));
+    }
+    match authority.rsplit_once(':') {
+        Some((host, p)) => Ok((host.to_string(), p.parse().map_err(|_| format!("Invalid port in {}", authority))?)),
+        None => Ok((authority.to_string(), default_port)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use tokio::task::JoinHandle;
+
+    fn proxy(kind: &str, port: u16, user: Option<&str>, pass: Option<&str>) -> Proxy {
+        Proxy {
+            id: "p".to_string(),
+            name: "test".to_string(),
+            proxy_type: kind.to_string(),
+            host: "127.0.0.1".to_string(),
+            port,
+            username: user.map(str::to_string),
+            password: pass.map(str::to_string),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    // 单连接的脚本化代理：script 处理握手并返回收到的关键字节，供断言
+    async fn serve<F, Fut>(script: F) -> (u16, JoinHandle<Vec<u8>>)
+    where
+        F: FnOnce(TcpStream) -> Fut + Send + 'static,
+        Fut: Future<Output = Vec<u8>> + Send,
+    {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let task = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            script(stream).await
+        });
+        (port, task)
+    }
+
+    async fn read_head(stream: &mut TcpStream) -> String {
+        let mut head = Vec::new();
+        let mut byte = [0u8; 1];
+        while !head.ends_with(b"\r\n\r\n") {
+            stream.read_exact(&mut byte).await.unwrap();
+            head.push(byte[0]);
+        }
+        String::from_utf8(head).unwrap()
+    }
+
+    async fn read_n(stream: &mut TcpStream, n: usize) -> Vec<u8> {
+        let mut buf = vec![0u8; n];
+        stream.read_exact(&mut buf).await.unwrap();
+        buf
+    }
+
+    // SOCKS5 CONNECT 请求：VER CMD RSV ATYP(域名) LEN HOST PORT
+    async fn read_socks5_request(stream: &mut TcpStream) -> Vec<u8> {
+        let mut request = read_n(stream, 5).await;
+        let rest = read_n(stream, request[4] as usize + 2).await;
+        request.extend(rest);
+        request
+    }
+
+    // 读到 NUL 为止 (SOCKS4 的 USERID / 域名字段)
+    async fn read_cstr(stream: &mut TcpStream) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            stream.read_exact(&mut byte).await.unwrap();
+            if byte[0] == 0 {
+                return out;
+            }
+            out.push(byte[0]);
+        }
+    }
+
+    // ==========================================================================
+    // 🟢 HTTP CONNECT
+    // ==========================================================================
+
+    #[tokio::test]
+    async fn http_connect_sends_basic_auth_and_keeps_tunnel_bytes() {
+        let (port, task) = serve(|mut s| async move {
+            let head = read_head(&mut s).await;
+            // 响应头之后紧跟隧道数据，dial 不能把它吞掉
+            s.write_all(b"HTTP/1.1 200 Connection Established\r\n\r\nSSH-2.0-test\r\n").await.unwrap();
+            head.into_bytes()
+        })
+        .await;
+
+        let p = proxy("http", port, Some("alice"), Some("secret"));
+        let mut stream = dial(Some(&p), "example.com", 22).await.unwrap();
+        let mut banner = [0u8; 14];
+        stream.read_exact(&mut banner).await.unwrap();
+        assert_eq!(&banner, b"SSH-2.0-test\r\n");
+
+        let head = String::from_utf8(task.await.unwrap()).unwrap();
+        assert!(head.starts_with("CONNECT example.com:22 HTTP/1.1\r\n"));
+        assert!(head.contains("Host: example.com:22\r\n"));
+        assert!(head.contains(&format!("Proxy-Authorization: Basic {}\r\n", BASE64.encode("alice:secret"))));
+    }
+
+    #[tokio::test]
+    async fn http_connect_brackets_ipv6_and_omits_auth_without_user() {
+        let (port, task) = serve(|mut s| async move {
+            let head = read_head(&mut s).await;
+            s.write_all(b"HTTP/1.0 200 OK\r\n\r\n").await.unwrap();
+            head.into_bytes()
+        })
+        .await;
+
+        let p = proxy("http", port, Some(""), Some("ignored"));
+        dial(Some(&p), "::1", 2222).await.unwrap();
+
+        let head = String::from_utf8(task.await.unwrap()).unwrap();
+        assert!(head.starts_with("CONNECT [::1]:2222 HTTP/1.1\r\n"));
+        assert!(!head.contains("Proxy-Authorization"));
+    }
+
+    #[tokio::test]
+    async fn https_proxy_is_refused_before_connecting() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let p = proxy("https", port, Some("alice"), Some("secret"));
+        let err = dial(Some(&p), "example.com", 22).await.unwrap_err();
+        assert!(err.contains("HTTPS proxies (TLS to the proxy) are not supported"), "{}", err);
+
+        // 代理端口上不应出现任何连接 (凭证不能以明文发出)
+        let accepted = tokio::time::timeout(std::time::Duration::from_millis(100), listener.accept()).await;
+        assert!(accepted.is_err());
+    }
+
+    #[tokio::test]
+    async fn http_connect_reports_auth_required() {
+        let (port, _task) = serve(|mut s| async move {
+            read_head(&mut s).await;
+            s.write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n").await.unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let err = dial(Some(&proxy("http", port, None, None)), "example.com", 22).await.unwrap_err();
+        assert!(err.contains("Proxy authentication required (407)"), "{}", err);
+        assert!(err.starts_with("Proxy test (127.0.0.1:"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn http_connect_reports_rejection_status_line() {
+        let (port, _task) = serve(|mut s| async move {
+            read_head(&mut s).await;
+            s.write_all(b"HTTP/1.1 403 Forbidden\r\nX-Reason: policy\r\n\r\n").await.unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let err = dial(Some(&proxy("http", port, None, None)), "example.com", 22).await.unwrap_err();
+        assert!(err.ends_with("CONNECT rejected: HTTP/1.1 403 Forbidden"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn http_connect_reports_early_close() {
+        let (port, _task) = serve(|mut s| async move {
+            read_head(&mut s).await;
+            s.write_all(b"HTTP/1.1 200").await.unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let err = dial(Some(&proxy("http", port, None, None)), "example.com", 22).await.unwrap_err();
+        assert!(err.contains("Proxy closed the connection during CONNECT"), "{}", err);
+    }
+
+    // ==========================================================================
+    // 🟢 SOCKS5
+    // ==========================================================================
+
+    #[tokio::test]
+    async fn socks5_no_auth_sends_domain_for_remote_dns() {
+        let (port, task) = serve(|mut s| async move {
+            let greeting = read_n(&mut s, 3).await;
+            assert_eq!(greeting, [0x05, 0x01, 0x00]);
+            s.write_all(&[0x05, 0x00]).await.unwrap();
+
+            let request = read_socks5_request(&mut s).await;
+            // 域名类型的绑定地址，验证按长度读完整个应答
+            s.write_all(&[0x05, 0x00, 0x00, 0x03, 4, b'h', b'o', b's', b't', 0x1F, 0x90]).await.unwrap();
+            s.write_all(b"payload").await.unwrap();
+            request
+        })
+        .await;
+
+        let mut stream = dial(Some(&proxy("socks5", port, None, None)), "example.com", 22).await.unwrap();
+        let mut payload = [0u8; 7];
+        stream.read_exact(&mut payload).await.unwrap();
+        assert_eq!(&payload, b"payload");
+
+        let mut expected = vec![0x05, 0x01, 0x00, 0x03, 11];
+        expected.extend_from_slice(b"example.com");
+        expected.extend_from_slice(&22u16.to_be_bytes());
+        assert_eq!(task.await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn socks5_sends_ip_literals_as_addresses() {
+        let (port, task) = serve(|mut s| async move {
+            read_n(&mut s, 3).await;
+            s.write_all(&[0x05, 0x00]).await.unwrap();
+            let request = read_n(&mut s, 4 + 16 + 2).await;
+            s.write_all(&[0x05, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0]).await.unwrap();
+            request
+        })
+        .await;
+
+        dial(Some(&proxy("socks5h", port, None, None)), "[::1]", 22).await.unwrap();
+
+        let request = task.await.unwrap();
+        assert_eq!(&request[..4], &[0x05, 0x01, 0x00, 0x04]);
+        assert_eq!(&request[4..20], &"::1".parse::<std::net::Ipv6Addr>().unwrap().octets());
+        assert_eq!(&request[20..], &22u16.to_be_bytes());
+    }
+
+    #[tokio::test]
+    async fn socks5_username_password_auth() {
+        let (port, task) = serve(|mut s| async move {
+            let greeting = read_n(&mut s, 4).await;
+            assert_eq!(greeting, [0x05, 0x02, 0x00, 0x02]);
+            s.write_all(&[0x05, 0x02]).await.unwrap();
+
+            let head = read_n(&mut s, 2).await;
+            let user = read_n(&mut s, head[1] as usize).await;
+            let pass_len = read_n(&mut s, 1).await;
+            let pass = read_n(&mut s, pass_len[0] as usize).await;
+            s.write_all(&[0x01, 0x00]).await.unwrap();
+
+            read_socks5_request(&mut s).await;
+            s.write_all(&[0x05, 0x00, 0x00, 0x01, 127, 0, 0, 1, 0, 22]).await.unwrap();
+            [head[..1].to_vec(), user, b":".to_vec(), pass].concat()
+        })
+        .await;
+
+        let p = proxy("socks5", port, Some("alice"), Some("secret"));
+        dial(Some(&p), "example.com", 22).await.unwrap();
+        assert_eq!(task.await.unwrap(), b"\x01alice:secret");
+    }
+
+    #[tokio::test]
+    async fn socks5_reports_bad_credentials() {
+        let (port, _task) = serve(|mut s| async move {
+            read_n(&mut s, 4).await;
+            s.write_all(&[0x05, 0x02]).await.unwrap();
+            let head = read_n(&mut s, 2).await;
+            read_n(&mut s, head[1] as usize).await;
+            let pass_len = read_n(&mut s, 1).await;
+            read_n(&mut s, pass_len[0] as usize).await;
+            s.write_all(&[0x01, 0x01]).await.unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let p = proxy("socks5", port, Some("alice"), Some("wrong"));
+        let err = dial(Some(&p), "example.com", 22).await.unwrap_err();
+        assert!(err.ends_with("SOCKS5 authentication failed"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn socks5_reports_auth_required_without_credentials() {
+        let (port, _task) = serve(|mut s| async move {
+            read_n(&mut s, 3).await;
+            s.write_all(&[0x05, 0x02]).await.unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let err = dial(Some(&proxy("socks5", port, None, None)), "example.com", 22).await.unwrap_err();
+        assert!(err.ends_with("SOCKS5 proxy requires username/password"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn socks5_reports_no_acceptable_method() {
+        let (port, _task) = serve(|mut s| async move {
+            read_n(&mut s, 3).await;
+            s.write_all(&[0x05, 0xFF]).await.unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let err = dial(Some(&proxy("socks5", port, None, None)), "example.com", 22).await.unwrap_err();
+        assert!(err.ends_with("SOCKS5 proxy rejected all authentication methods"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn socks5_rejects_non_socks5_server() {
+        let (port, _task) = serve(|mut s| async move {
+            read_n(&mut s, 3).await;
+            s.write_all(&[0x04, 0x00]).await.unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let err = dial(Some(&proxy("socks5", port, None, None)), "example.com", 22).await.unwrap_err();
+        assert!(err.ends_with("Not a SOCKS5 proxy"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn socks5_reports_connect_reply_code() {
+        let (port, _task) = serve(|mut s| async move {
+            read_n(&mut s, 3).await;
+            s.write_all(&[0x05, 0x00]).await.unwrap();
+            read_socks5_request(&mut s).await;
+            s.write_all(&[0x05, 0x05, 0x00, 0x01, 0, 0, 0, 0, 0, 0]).await.unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let err = dial(Some(&proxy("socks5", port, None, None)), "example.com", 22).await.unwrap_err();
+        assert!(err.ends_with("SOCKS5 connect failed: connection refused (0x05)"), "{}", err);
+    }
+
+    #[test]
+    fn socks5_error_names_reply_codes() {
+        assert!(socks5_error(0x01).contains("general SOCKS server failure"));
+        assert!(socks5_error(0x02).contains("connection not allowed by ruleset"));
+        assert!(socks5_error(0x04).contains("host unreachable"));
+        assert!(socks5_error(0x08).contains("address type not supported"));
+        assert_eq!(socks5_error(0x42), "SOCKS5 connect failed: unknown error (0x42)");
+    }
+
+    // ==========================================================================
+    // 🟢 SOCKS4 / SOCKS4a
+    // ==========================================================================
+
+    #[tokio::test]
+    async fn socks4a_sends_domain_after_placeholder_ip() {
+        let (port, task) = serve(|mut s| async move {
+            let mut request = read_n(&mut s, 8).await;
+            let user = read_cstr(&mut s).await;
+            let host = read_cstr(&mut s).await;
+            s.write_all(&[0x00, 0x5A, 0, 0, 0, 0, 0, 0]).await.unwrap();
+            request.extend(user);
+            request.push(b'|');
+            request.extend(host);
+            request
+        })
+        .await;
+
+        let p = proxy("socks4a", port, Some("bob"), None);
+        dial(Some(&p), "example.com", 22).await.unwrap();
+
+        let mut expected = vec![0x04, 0x01, 0x00, 22, 0, 0, 0, 1];
+        expected.extend_from_slice(b"bob|example.com");
+        assert_eq!(task.await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn socks4_sends_ipv4_targets_directly() {
+        let (port, task) = serve(|mut s| async move {
+            let mut request = read_n(&mut s, 8).await;
+            request.extend(read_cstr(&mut s).await);
+            s.write_all(&[0x00, 0x5A, 0, 0, 0, 0, 0, 0]).await.unwrap();
+            request
+        })
+        .await;
+
+        dial(Some(&proxy("socks4", port, None, None)), "10.0.0.5", 2222).await.unwrap();
+        assert_eq!(task.await.unwrap(), [0x04, 0x01, 0x08, 0xAE, 10, 0, 0, 5]);
+    }
+
+    #[tokio::test]
+    async fn socks4_reports_rejection() {
+        let (port, _task) = serve(|mut s| async move {
+            read_n(&mut s, 8).await;
+            read_cstr(&mut s).await;
+            s.write_all(&[0x00, 0x5B, 0, 0, 0, 0, 0, 0]).await.unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let err = dial(Some(&proxy("socks4", port, None, None)), "10.0.0.5", 22).await.unwrap_err();
+        assert!(err.ends_with("SOCKS4 request rejected or failed"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn socks4_reports_identd_failure() {
+        let (port, _task) = serve(|mut s| async move {
+            read_n(&mut s, 8).await;
+            read_cstr(&mut s).await;
+            s.write_all(&[0x00, 0x5D, 0, 0, 0, 0, 0, 0]).await.unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let err = dial(Some(&proxy("socks4", port, Some("bob"), None)), "10.0.0.5", 22).await.unwrap_err();
+        assert!(err.ends_with("SOCKS4 identd authentication failed"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn socks4_refuses_ipv6_targets() {
+        let (port, _task) = serve(|_s| async move { Vec::new() }).await;
+
+        let err = dial(Some(&proxy("socks4", port, None, None)), "::1", 22).await.unwrap_err();
+        assert!(err.ends_with("SOCKS4 does not support IPv6 targets"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn unsupported_proxy_type_is_reported() {
+        let (port, _task) = serve(|_s| async move { Vec::new() }).await;
+
+        let err = dial(Some(&proxy("ftp", port, None, None)), "example.com", 22).await.unwrap_err();
+        assert!(err.ends_with("Unsupported proxy type: ftp"), "{}", err);
+    }
+
+    // ==========================================================================
+    // 🟢 本地 HTTP 代理桥
+    // ==========================================================================
+
+    // 上游 HTTP 代理：确认 CONNECT 目标后交给 tunnel 处理隧道内的数据
+    async fn upstream<F, Fut>(tunnel: F) -> (u16, JoinHandle<Vec<u8>>)
+    where
+        F: FnOnce(TcpStream) -> Fut + Send + 'static,
+        Fut: Future<Output = Vec<u8>> + Send,
+    {
+        serve(|mut s| async move {
+            let head = read_head(&mut s).await;
+            s.write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n").await.unwrap();
+            let mut seen = head.lines().next().unwrap().as_bytes().to_vec();
+            seen.push(b'\n');
+            seen.extend(tunnel(s).await);
+            seen
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn bridge_tunnels_connect_requests_through_the_proxy() {
+        let (port, task) = upstream(|mut s| async move {
+            let early = read_n(&mut s, 5).await;
+            s.write_all(b"world").await.unwrap();
+            early
+        })
+        .await;
+        let bridge = spawn_bridge(proxy("http", port, None, None)).await.unwrap();
+        assert_eq!(bridge.proxy_url(), format!("http://{}", bridge.addr));
+
+        let mut client = TcpStream::connect(bridge.addr).await.unwrap();
+        // 请求头之后紧跟的数据也要转发进隧道
+        client.write_all(b"CONNECT dav.example.com:443 HTTP/1.1\r\nHost: dav.example.com:443\r\n\r\nhello").await.unwrap();
+        assert_eq!(read_head(&mut client).await, "HTTP/1.1 200 Connection Established\r\n\r\n");
+        assert_eq!(read_n(&mut client, 5).await, b"world");
+
+        assert_eq!(task.await.unwrap(), b"CONNECT dav.example.com:443 HTTP/1.1\nhello");
+    }
+
+    #[tokio::test]
+    async fn bridge_rewrites_plain_http_requests_to_origin_form() {
+        let (port, task) = upstream(|mut s| async move {
+            let head = read_head(&mut s).await;
+            let body = read_n(&mut s, 4).await;
+            s.write_all(b"HTTP/1.1 201 Created\r\nContent-Length: 0\r\n\r\n").await.unwrap();
+            [head.into_bytes(), body].concat()
+        })
+        .await;
+        let bridge = spawn_bridge(proxy("http", port, None, None)).await.unwrap();
+
+        let mut client = TcpStream::connect(bridge.addr).await.unwrap();
+        client
+            .write_all(b"PUT http://dav.example.com/backup/a.zip HTTP/1.1\r\nHost: dav.example.com\r\nContent-Length: 4\r\n\r\ndata")
+            .await
+            .unwrap();
+        assert_eq!(read_head(&mut client).await, "HTTP/1.1 201 Created\r\nContent-Length: 0\r\n\r\n");
+
+        let seen = String::from_utf8(task.await.unwrap()).unwrap();
+        assert_eq!(
+            seen,
+            "CONNECT dav.example.com:80 HTTP/1.1\n\
+             PUT /backup/a.zip HTTP/1.1\r\nHost: dav.example.com\r\nContent-Length: 4\r\n\r\ndata"
+        );
+    }
+
+    #[tokio::test]
+    async fn bridge_answers_502_when_the_proxy_refuses() {
+        let (port, _task) = serve(|mut s| async move {
+            read_head(&mut s).await;
+            s.write_all(b"HTTP/1.1 403 Forbidden\r\n\r\n").await.unwrap();
+            Vec::new()
+        })
+        .await;
+        let bridge = spawn_bridge(proxy("http", port, None, None)).await.unwrap();
+
+        let mut client = TcpStream::connect(bridge.addr).await.unwrap();
+        client.write_all(b"CONNECT dav.example.com:443 HTTP/1.1\r\n\r\n").await.unwrap();
+        assert_eq!(read_head(&mut client).await, "HTTP/1.1 502 Bad Gateway\r\n\r\n");
+    }
+
+    #[tokio::test]
+    async fn bridge_stops_listening_when_dropped() {
+        let bridge = spawn_bridge(proxy("http", 9, None, None)).await.unwrap();
+        let addr = bridge.addr;
+        drop(bridge);
+        tokio::task::yield_now().await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(TcpStream::connect(addr).await.is_err());
+    }
+
+    #[test]
+    fn split_host_port_handles_defaults_and_ipv6() {
+        assert_eq!(split_host_port("example.com", 80).unwrap(), ("example.com".to_string(), 80));
+        assert_eq!(split_host_port("example.com:8443", 443).unwrap(), ("example.com".to_string(), 8443));
+        assert_eq!(split_host_port("[::1]:8080", 80).unwrap(), ("::1".to_string(), 8080));
+        assert_eq!(split_host_port("[::1]", 443).unwrap(), ("::1".to_string(), 443));
+        assert!(split_host_port("example.com:http", 80).is_err());
+    }
+}
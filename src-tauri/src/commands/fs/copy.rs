@@ -0,0 +1,261 @@
+// src-tauri/src/commands/fs/copy.rs
+// 远程复制 / 移动
+//   同一会话：优先在服务器上执行 cp -a / rsync -a (移动用 rename / mv)，数据不经过本机
+//   服务器没有可用 Shell (仅开放 SFTP、Windows cmd 等) 时退回 SFTP 流式复制
+//   跨会话 (服务器 A -> B)：经本机内存中转逐条复制；移动 = 复制全部成功后删除源
+// 传入 op_id 时流式复制推送 fs-batch-progress-{opId} 进度事件
+
+use russh_sftp::client::SftpSession;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+use tokio::io::{AsyncWriteExt, BufReader};
+
+use crate::commands::ssh::core::SshHandle;
+use crate::commands::ssh::{exec, SshState};
+
+use super::recursive::{
+    ensure_remote_dir, remote_dest, remote_symlink, remove_entries, scan_remote, Batch, BatchReport, Kind,
+    SymlinkMode,
+};
+use super::with_timeout;
+
+const CHUNK_SIZE: usize = 256 * 1024;
+// 仅开放 SFTP 的账号 (ForceCommand internal-sftp) 执行任何命令都会挂起，探测必须限时
+const PROBE_TIMEOUT_SECS: u64 = 5;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyMethod {
+    Cp,
+    Rsync,
+    Rename,
+    Mv,
+    Stream,
+}
+
+impl CopyMethod {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CopyMethod::Cp => "cp",
+            CopyMethod::Rsync => "rsync",
+            CopyMethod::Rename => "rename",
+            CopyMethod::Mv => "mv",
+            CopyMethod::Stream => "stream",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyReport {
+    pub method: CopyMethod,
+    /// 仅流式复制逐条统计；服务器端执行只有整体成功 / 失败
+    #[serde(flatten)]
+    pub report: BatchReport,
+}
+
+impl CopyReport {
+    fn server_side(method: CopyMethod) -> Self {
+        Self { method, report: BatchReport::default() }
+    }
+}
+
+// 目录不能复制 / 移动到自身或其子目录下
+fn check_not_inside(from: &str, to: &str) -> Result<(), String> {
+    let from = from.trim_end_matches('/');
+    let to = to.trim_end_matches('/');
+    if to == from || to.starts_with(&format!("{}/", from)) {
+        return Err("Cannot copy or move a path into itself".to_string());
+    }
+    Ok(())
+}
+
+// cp / rsync 在目标目录已存在时会把源放进其中，而不是作为目标本身
+async fn check_dir_target(dst: &SftpSession, to: &str) -> Result<(), String> {
+    if with_timeout(5, dst.symlink_metadata(to.to_string())).await.is_ok() {
+        return Err(format!("Target already exists: {}", to));
+    }
+    Ok(())
+}
+
+// 按顺序返回第一个可用的命令；exec 通道打不开或没有 POSIX Shell 时返回 None
+async fn probe_tool(handle: &SshHandle, candidates: &[CopyMethod]) -> Option<CopyMethod> {
+    let names: Vec<_> = candidates.iter().map(|m| m.as_str()).collect();
+    let script = format!(
+        "for t in {}; do command -v \"$t\" >/dev/null 2>&1 && {{ echo \"$t\"; exit 0; }}; done; exit 127",
+        names.join(" ")
+    );
+    let mut req = exec::ExecRequest::new(script);
+    req.timeout_secs = Some(PROBE_TIMEOUT_SECS);
+
+    let output = exec::run(handle, &req).await.ok()?;
+    if !output.success() {
+        return None;
+    }
+    let found = output.stdout.trim();
+    candidates.iter().copied().find(|m| m.as_str() == found)
+}
+
+// 在服务器上执行复制 / 移动；Ok(None) 表示没有可用的 Shell，应退回流式复制
+async fn run_on_server(
+    ssh_state: &SshState,
+    id: &str,
+    candidates: &[CopyMethod],
+    from: &str,
+    to: &str,
+    is_dir: bool,
+) -> Result<Option<CopyMethod>, String> {
+    let Ok(handle) = ssh_state.get_handle(id) else {
+        return Ok(None);
+    };
+    let Some(method) = probe_tool(&handle, candidates).await else {
+        return Ok(None);
+    };
+
+    let (src, dst) = (exec::shell_quote(from), exec::shell_quote(to));
+    let cmd = match method {
+        // rsync 源路径带 / 时复制目录内容，否则会多出一层
+        CopyMethod::Rsync if is_dir => format!("rsync -a -- {}/ {}", src, dst),
+        CopyMethod::Rsync => format!("rsync -a -- {} {}", src, dst),
+        CopyMethod::Mv => format!("mv -f -- {} {}", src, dst),
+        _ => format!("cp -a -- {} {}", src, dst),
+    };
+    // 大目录可能耗时很久，不设超时
+    let output = exec::run(&handle, &exec::ExecRequest::new(cmd)).await?;
+    if !output.success() {
+        return Err(format!("{} failed: {}", method.as_str(), output.error_message()));
+    }
+    Ok(Some(method))
+}
+
+async fn copy_file(src: &SftpSession, dst: &SftpSession, from: &str, to: &str) -> Result<(), String> {
+    let reader = with_timeout(10, src.open(from.to_string()))
+        .await
+        .map_err(|e| format!("Failed to open source: {}", e))?;
+    let mut writer = with_timeout(10, dst.create(to.to_string()))
+        .await
+        .map_err(|e| format!("Failed to create dest: {}", e))?;
+
+    // 数据流向：SFTP Server A -> Rust Memory Buffer -> SFTP Server B (同一会话时 A = B)
+    let mut reader = BufReader::with_capacity(CHUNK_SIZE, reader);
+    tokio::io::copy_buf(&mut reader, &mut writer)
+        .await
+        .map_err(|e| format!("Copy stream failed: {}", e))?;
+    writer.shutdown().await.map_err(|e| format!("Flush failed: {}", e))
+}
+
+// 逐条流式复制；与 cp -a 一致，链接按原样重建而不进入其目标
+async fn stream_copy(
+    app: &AppHandle,
+    src: &SftpSession,
+    dst: &SftpSession,
+    target_id: &str,
+    from: &str,
+    to: &str,
+    op_id: Option<String>,
+) -> Result<CopyReport, String> {
+    let mut plan = scan_remote(src, from, SymlinkMode::Copy).await?;
+    let mut batch = Batch::new(app, op_id, &mut plan);
+    let ssh_state = app.state::<SshState>();
+
+    for entry in &plan.entries {
+        if batch.under_failed_dir(&entry.rel) {
+            continue;
+        }
+        let dest = remote_dest(to, &entry.rel);
+        let result = match &entry.kind {
+            Kind::Dir => ensure_remote_dir(dst, &dest).await,
+            Kind::File => copy_file(src, dst, &entry.source, &dest).await,
+            Kind::Symlink(target) => remote_symlink(&ssh_state, target_id, target, &dest).await,
+        };
+        batch.record(entry, result);
+    }
+    Ok(CopyReport { method: CopyMethod::Stream, report: batch.report })
+}
+
+// 移动的后半段：复制全部成功后删除源，删除失败的路径并入报告
+async fn remove_source(app: &AppHandle, src: &SftpSession, from: &str, copied: &mut CopyReport) -> Result<(), String> {
+    if !copied.report.failures.is_empty() {
+        return Ok(());
+    }
+    let mut plan = scan_remote(src, from, SymlinkMode::Copy).await?;
+    let mut batch = Batch::new(app, None, &mut plan);
+    remove_entries(src, &plan, &mut batch).await;
+    copied.report.failures.extend(batch.report.failures);
+    Ok(())
+}
+
+// ==============================================================================
+// 🟢 命令
+// ==============================================================================
+
+/// 把 id 会话上的 from_path 复制为 target_id 会话上的 to_path (target_id 缺省为同一会话)
+#[tauri::command]
+pub async fn sftp_copy(
+    app: AppHandle,
+    ssh_state: State<'_, SshState>,
+    id: String,
+    from_path: String,
+    to_path: String,
+    target_id: Option<String>,
+    op_id: Option<String>,
+) -> Result<CopyReport, String> {
+    let target_id = target_id.unwrap_or_else(|| id.clone());
+    let src = ssh_state.get_sftp(&id).await?;
+    let dst = ssh_state.get_sftp(&target_id).await?;
+    let meta = with_timeout(5, src.symlink_metadata(from_path.clone()))
+        .await
+        .map_err(|e| format!("Failed to open source: {}", e))?;
+    if meta.is_dir() {
+        check_dir_target(&dst, &to_path).await?;
+    }
+
+    if target_id == id {
+        check_not_inside(&from_path, &to_path)?;
+        let candidates = [CopyMethod::Cp, CopyMethod::Rsync];
+        if let Some(method) = run_on_server(&ssh_state, &id, &candidates, &from_path, &to_path, meta.is_dir()).await? {
+            return Ok(CopyReport::server_side(method));
+        }
+    }
+    stream_copy(&app, &src, &dst, &target_id, &from_path, &to_path, op_id).await
+}
+
+/// 把 id 会话上的 from_path 移动为 target_id 会话上的 to_path (target_id 缺省为同一会话)
+#[tauri::command]
+pub async fn sftp_move(
+    app: AppHandle,
+    ssh_state: State<'_, SshState>,
+    id: String,
+    from_path: String,
+    to_path: String,
+    target_id: Option<String>,
+    op_id: Option<String>,
+) -> Result<CopyReport, String> {
+    let target_id = target_id.unwrap_or_else(|| id.clone());
+    let src = ssh_state.get_sftp(&id).await?;
+    let meta = with_timeout(5, src.symlink_metadata(from_path.clone()))
+        .await
+        .map_err(|e| format!("Failed to open source: {}", e))?;
+
+    if target_id == id {
+        check_not_inside(&from_path, &to_path)?;
+        // 同一文件系统内 rename 是原子的；跨挂载点或目标文件已存在时失败，再交给 mv
+        if with_timeout(5, src.rename(from_path.clone(), to_path.clone())).await.is_ok() {
+            return Ok(CopyReport::server_side(CopyMethod::Rename));
+        }
+        if meta.is_dir() {
+            check_dir_target(&src, &to_path).await?;
+        }
+        if let Some(method) = run_on_server(&ssh_state, &id, &[CopyMethod::Mv], &from_path, &to_path, meta.is_dir()).await? {
+            return Ok(CopyReport::server_side(method));
+        }
+    }
+
+    let dst = ssh_state.get_sftp(&target_id).await?;
+    if meta.is_dir() && target_id != id {
+        check_dir_target(&dst, &to_path).await?;
+    }
+    let mut report = stream_copy(&app, &src, &dst, &target_id, &from_path, &to_path, op_id).await?;
+    remove_source(&app, &src, &from_path, &mut report).await?;
+    Ok(report)
+}
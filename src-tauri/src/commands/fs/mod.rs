@@ -0,0 +1,416 @@
+use crate::commands::ssh::{exec, SshState};
+use crate::commands::transfer::{self, verify::Verification};
+use russh_sftp::client::SftpSession;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::State;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+// 目录级操作 (预扫描 / 递归上传、下载、删除)
+pub mod recursive;
+pub use recursive::{sftp_delete_tree, sftp_download_tree, sftp_scan_tree, sftp_upload_tree};
+
+// 复制 / 移动 (服务器端执行优先，跨会话经本机中转)
+pub mod copy;
+pub use copy::{sftp_copy, sftp_move};
+
+// 本地 <-> 远程文件夹同步 (预演计划 + 执行，配置按服务器保存)
+pub mod sync;
+pub use sync::{delete_sync_profile, list_sync_profiles, save_sync_profile, sftp_sync_apply, sftp_sync_plan};
+
+// === 数据结构 ===
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileEntry {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub last_modified: u64,  // 毫秒时间戳
+    pub permissions: String, // rwxr-xr-x
+    pub owner: String,
+    pub group: String,
+    pub extension: String,
+}
+
+// === 辅助函数：Unix 权限位转字符串 ===
+fn format_permissions(mode: u32) -> String {
+    let mut s = String::with_capacity(10);
+    s.push(if mode & 0o400 != 0 { 'r' } else { '-' });
+    s.push(if mode & 0o200 != 0 { 'w' } else { '-' });
+    s.push(if mode & 0o100 != 0 { 'x' } else { '-' });
+    s.push(if mode & 0o040 != 0 { 'r' } else { '-' });
+    s.push(if mode & 0o020 != 0 { 'w' } else { '-' });
+    s.push(if mode & 0o010 != 0 { 'x' } else { '-' });
+    s.push(if mode & 0o004 != 0 { 'r' } else { '-' });
+    s.push(if mode & 0o002 != 0 { 'w' } else { '-' });
+    s.push(if mode & 0o001 != 0 { 'x' } else { '-' });
+    s
+}
+
+// === 辅助函数：获取 SFTP 子系统会话 ===
+// SFTP 与 Shell / 监控共用同一条 russh 连接，只是独立的 subsystem 通道
+async fn get_sftp(ssh_state: &State<'_, SshState>, id: &str) -> Result<Arc<SftpSession>, String> {
+    ssh_state.get_sftp(id).await
+}
+
+// === 辅助函数：为单次 SFTP 请求加超时 ===
+pub(crate) async fn with_timeout<T, E: std::fmt::Display>(
+    secs: u64,
+    fut: impl std::future::Future<Output = Result<T, E>>,
+) -> Result<T, String> {
+    tokio::time::timeout(Duration::from_secs(secs), fut)
+        .await
+        .map_err(|_| "SFTP request timed out".to_string())?
+        .map_err(|e| e.to_string())
+}
+
+// ==========================================
+// 1. 获取文件列表 (含 OpenWrt 兼容性检查)
+// ==========================================
+#[tauri::command]
+pub async fn list_ssh_files(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    path: String,
+) -> Result<Vec<FileEntry>, String> {
+    // 子系统初始化失败时 (OpenWrt 未安装 sftp-server) 会返回友好提示
+    let sftp = get_sftp(&ssh_state, &id).await?;
+
+    // 读取大目录可能需要更久，给 5s
+    let dir = with_timeout(5, sftp.read_dir(path.clone()))
+        .await
+        .map_err(|e| format!("Read Dir Error: {}", e))?;
+
+    let mut entries = Vec::new();
+
+    for entry in dir {
+        let file_name = entry.file_name();
+
+        if file_name == "." || file_name == ".." {
+            continue;
+        }
+
+        let stat = entry.metadata();
+        let is_dir = stat.is_dir();
+
+        // 路径拼接：简单的字符串拼接，适配 Linux 路径
+        let full_path = if path.ends_with('/') {
+            format!("{}{}", path, file_name)
+        } else {
+            format!("{}/{}", path, file_name)
+        };
+
+        let extension = Path::new(&file_name)
+            .extension()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let perms_str = if is_dir {
+            format!("d{}", format_permissions(stat.permissions.unwrap_or(0)))
+        } else {
+            format!("-{}", format_permissions(stat.permissions.unwrap_or(0)))
+        };
+
+        entries.push(FileEntry {
+            name: file_name,
+            path: full_path,
+            is_dir,
+            size: stat.size.unwrap_or(0),
+            last_modified: stat.mtime.unwrap_or(0) as u64 * 1000,
+            permissions: perms_str,
+            owner: stat.uid.unwrap_or(0).to_string(),
+            group: stat.gid.unwrap_or(0).to_string(),
+            extension,
+        });
+    }
+
+    // 排序：文件夹在前，文件在后；同类按名称排序
+    entries.sort_by(|a, b| {
+        if a.is_dir == b.is_dir {
+            a.name.cmp(&b.name)
+        } else {
+            b.is_dir.cmp(&a.is_dir)
+        }
+    });
+
+    Ok(entries)
+}
+
+// ==========================================
+// 2. 新建文件夹
+// ==========================================
+#[tauri::command]
+pub async fn sftp_mkdir(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    path: String,
+) -> Result<(), String> {
+    let sftp = get_sftp(&ssh_state, &id).await?;
+    with_timeout(5, sftp.create_dir(path)).await
+}
+
+// ==========================================
+// 3. 新建文件
+// ==========================================
+#[tauri::command]
+pub async fn sftp_create_file(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    path: String,
+) -> Result<(), String> {
+    let sftp = get_sftp(&ssh_state, &id).await?;
+
+    // sftp.create 等同于 open with WRITE | CREATE | TRUNCATE
+    let mut file = with_timeout(5, sftp.create(path)).await?;
+    // 显式关闭句柄，文件创建成功
+    file.shutdown().await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// ==========================================
+// 4. 重命名
+// ==========================================
+#[tauri::command]
+pub async fn sftp_rename(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    old_path: String,
+    new_path: String,
+) -> Result<(), String> {
+    let sftp = get_sftp(&ssh_state, &id).await?;
+    with_timeout(5, sftp.rename(old_path, new_path)).await
+}
+
+// ==========================================
+// 5. 删除 (文件或空文件夹)
+// ==========================================
+#[tauri::command]
+pub async fn sftp_delete(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    path: String,
+    is_dir: bool,
+) -> Result<(), String> {
+    let sftp = get_sftp(&ssh_state, &id).await?;
+
+    // 删除可能稍慢
+    if is_dir {
+        // remove_dir 只能删除空文件夹
+        with_timeout(8, sftp.remove_dir(path)).await
+    } else {
+        with_timeout(8, sftp.remove_file(path)).await
+    }
+}
+
+// === 单文件传输结果 ===
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTransferReport {
+    /// 文件总大小
+    pub bytes: u64,
+    /// 续传起点 (0 表示完整传输)
+    pub resumed_from: u64,
+    pub verification: Option<Verification>,
+}
+
+// 传输后校验：不一致时报错，避免把损坏的文件当成功
+async fn verify_transfer(
+    ssh_state: &State<'_, SshState>,
+    id: &str,
+    sftp: &SftpSession,
+    local_path: &str,
+    remote_path: &str,
+) -> Result<Verification, String> {
+    let handle = ssh_state.get_handle(id).ok();
+    let result = transfer::verify::verify(handle.as_deref(), sftp, local_path, remote_path).await?;
+    if !result.matched {
+        return Err(result.mismatch_message());
+    }
+    Ok(result)
+}
+
+// ==========================================
+// 7. 下载文件 (Remote -> Local)
+// resume: 本地已有部分时从其末尾续传；verify (续传时缺省开启): 完成后校验 SHA-256
+// ==========================================
+#[tauri::command]
+pub async fn sftp_download_file(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    remote_path: String,
+    local_path: String,
+    resume: Option<bool>,
+    verify: Option<bool>,
+) -> Result<FileTransferReport, String> {
+    let sftp = get_sftp(&ssh_state, &id).await?;
+    let resume = resume.unwrap_or(false);
+
+    // 1. 打开远程文件与本地文件，续传时定位到本地已有大小
+    let mut ends = transfer::worker::open_download(&sftp, &remote_path, &local_path, resume).await?;
+
+    // 2. 流式传输 (不加超时，大文件可能耗时很久)
+    tokio::io::copy(&mut ends.src, &mut ends.dst)
+        .await
+        .map_err(|e| format!("Download stream failed: {}", e))?;
+    ends.dst.flush().await.map_err(|e| e.to_string())?;
+
+    // 3. 校验
+    let verification = if verify.unwrap_or(resume) {
+        Some(verify_transfer(&ssh_state, &id, &sftp, &local_path, &remote_path).await?)
+    } else {
+        None
+    };
+    Ok(FileTransferReport { bytes: ends.total, resumed_from: ends.offset, verification })
+}
+
+// ==========================================
+// 8. 上传文件 (Local -> Remote)
+// resume: 远程已有部分时从其末尾续传；verify (续传时缺省开启): 完成后校验 SHA-256
+// ==========================================
+#[tauri::command]
+pub async fn sftp_upload_file(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    local_path: String,
+    remote_path: String,
+    resume: Option<bool>,
+    verify: Option<bool>,
+) -> Result<FileTransferReport, String> {
+    let sftp = get_sftp(&ssh_state, &id).await?;
+    let resume = resume.unwrap_or(false);
+
+    // 1. 打开本地文件与远程文件 (非续传时 create 会覆盖同名文件)
+    let mut ends = transfer::worker::open_upload(&sftp, &local_path, &remote_path, resume).await?;
+
+    // 2. 流式传输
+    tokio::io::copy(&mut ends.src, &mut ends.dst)
+        .await
+        .map_err(|e| format!("Upload stream failed: {}", e))?;
+
+    // 3. 关闭远程句柄确保写入完成
+    ends.dst.shutdown().await.map_err(|e| format!("Flush failed: {}", e))?;
+
+    // 4. 校验
+    let verification = if verify.unwrap_or(resume) {
+        Some(verify_transfer(&ssh_state, &id, &sftp, &local_path, &remote_path).await?)
+    } else {
+        None
+    };
+    Ok(FileTransferReport { bytes: ends.total, resumed_from: ends.offset, verification })
+}
+
+// ==========================================
+// 9. 修改权限 (Chmod) - 修复版
+// ==========================================
+#[tauri::command]
+pub async fn sftp_chmod(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    path: String,
+    mode: String,
+    recursive: bool,
+) -> Result<(), String> {
+    // 1. 解析八进制权限字符串 (例如 "755" -> 493)
+    let mode_num = u32::from_str_radix(&mode, 8)
+        .map_err(|e| format!("Invalid octal mode: {}", e))?;
+
+    if recursive {
+        // === 递归模式 ===
+        // 在同一连接上开 exec 通道执行 "chmod -R" 以获得最佳性能
+        let handle = ssh_state.get_handle(&id)?;
+
+        // 构造命令: chmod -R 755 '/path/to/file' (路径经单引号转义，防止 Shell 注入)
+        let cmd = format!("chmod -R {:03o} {}", mode_num, exec::shell_quote(&path));
+
+        let output = exec::run(&handle, &exec::ExecRequest::new(cmd)).await?;
+        if !output.success() {
+            return Err(format!("Recursive chmod failed: {}", output.error_message()));
+        }
+    } else {
+        // === 单文件模式 ===
+        let sftp = get_sftp(&ssh_state, &id).await?;
+
+        // 1. 先获取当前文件属性
+        let mut stat = with_timeout(5, sftp.metadata(path.clone()))
+            .await
+            .map_err(|e| format!("Failed to get file stat: {}", e))?;
+
+        // 2. 修改权限位
+        stat.permissions = Some(mode_num);
+
+        // 3. 应用修改
+        with_timeout(5, sftp.set_metadata(path, stat))
+            .await
+            .map_err(|e| format!("SFTP setstat failed: {}", e))?;
+    }
+
+    Ok(())
+}
+
+// ==========================================
+// 10. 读取文件内容 (用于内置编辑器)
+// ==========================================
+#[tauri::command]
+pub async fn sftp_read_file(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    path: String,
+) -> Result<String, String> {
+    let sftp = get_sftp(&ssh_state, &id).await?;
+
+    // 1. 打开文件
+    let mut remote_file = with_timeout(10, sftp.open(path))
+        .await
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+
+    // 2. [安全检查] 限制文件大小 (例如 5MB)，防止前端编辑器崩溃
+    let stat = remote_file.metadata().await.map_err(|e| e.to_string())?;
+    if stat.size.unwrap_or(0) > 5 * 1024 * 1024 {
+        return Err("File is too large (>5MB) to edit in built-in editor.".to_string());
+    }
+
+    // 3. 读取为字符串
+    let mut content = String::new();
+    // 如果文件包含非 UTF-8 字符，read_to_string 会报错，这是预期行为
+    tokio::time::timeout(Duration::from_secs(10), remote_file.read_to_string(&mut content))
+        .await
+        .map_err(|_| "SFTP request timed out".to_string())?
+        .map_err(|e| format!("Failed to read text content (Binary file?): {}", e))?;
+
+    Ok(content)
+}
+
+// ==========================================
+// 11. 写入文件内容 (用于内置编辑器保存)
+// ==========================================
+#[tauri::command]
+pub async fn sftp_write_file(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    path: String,
+    content: String,
+) -> Result<(), String> {
+    let sftp = get_sftp(&ssh_state, &id).await?;
+
+    // 1. 以 Create (Write + Truncate) 模式打开文件
+    let mut remote_file = with_timeout(10, sftp.create(path))
+        .await
+        .map_err(|e| format!("Failed to open file for writing: {}", e))?;
+
+    // 2. 写入字符串字节
+    remote_file
+        .write_all(content.as_bytes())
+        .await
+        .map_err(|e| format!("Write failed: {}", e))?;
+
+    // 3. 关闭句柄，强制刷新缓冲区
+    remote_file
+        .shutdown()
+        .await
+        .map_err(|e| format!("Flush failed: {}", e))?;
+
+    Ok(())
+}
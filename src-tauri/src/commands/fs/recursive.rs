@@ -0,0 +1,507 @@
+// src-tauri/src/commands/fs/recursive.rs
+// 目录级操作：预扫描 (文件数 / 总大小) + 递归上传 / 下载 / 删除
+//   符号链接: skip 跳过 / follow 按目标处理 (检测环路) / copy 在目标端重建链接
+//   单个路径失败只记录到 failures，不中断整批；目录创建失败时跳过其下内容
+// 传入 op_id 时推送 fs-batch-progress-{opId} 进度事件
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use russh_sftp::client::fs::Metadata;
+use russh_sftp::client::SftpSession;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::AsyncWriteExt;
+use walkdir::WalkDir;
+
+use crate::commands::ssh::{exec, SshState};
+use crate::commands::transfer::worker::{open_download, open_upload};
+
+use super::with_timeout;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SymlinkMode {
+    #[default]
+    Skip,
+    Follow,
+    Copy,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PathError {
+    pub path: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeScan {
+    pub files: usize,
+    pub dirs: usize,
+    pub symlinks: usize,
+    pub total_bytes: u64,
+    /// 按 skip 模式跳过的链接，以及设备文件 / 管道等无法传输的条目
+    pub skipped: Vec<String>,
+    pub failures: Vec<PathError>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReport {
+    pub files: usize,
+    pub dirs: usize,
+    pub symlinks: usize,
+    pub bytes: u64,
+    pub skipped: Vec<String>,
+    pub failures: Vec<PathError>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchProgress {
+    pub done_entries: usize,
+    pub total_entries: usize,
+    pub done_bytes: u64,
+    pub total_bytes: u64,
+    pub current_path: String,
+}
+
+// === 扫描结果：父目录总在其内容之前 ===
+
+#[derive(Debug, Clone)]
+pub(super) enum Kind {
+    Dir,
+    File,
+    Symlink(String),
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Entry {
+    /// 相对扫描根的路径 ('/' 分隔)，根自身为空串
+    pub rel: String,
+    pub source: String,
+    pub kind: Kind,
+    pub size: u64,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Plan {
+    pub entries: Vec<Entry>,
+    skipped: Vec<String>,
+    failures: Vec<PathError>,
+}
+
+impl Plan {
+    fn fail(&mut self, path: &str, error: impl Into<String>) {
+        self.failures.push(PathError { path: path.to_string(), error: error.into() });
+    }
+
+    fn summary(self) -> TreeScan {
+        let mut scan = TreeScan { skipped: self.skipped, failures: self.failures, ..Default::default() };
+        for entry in &self.entries {
+            match entry.kind {
+                Kind::Dir => scan.dirs += 1,
+                Kind::File => {
+                    scan.files += 1;
+                    scan.total_bytes += entry.size;
+                }
+                Kind::Symlink(_) => scan.symlinks += 1,
+            }
+        }
+        scan
+    }
+}
+
+pub(super) fn join_rel(rel: &str, name: &str) -> String {
+    if rel.is_empty() { name.to_string() } else { format!("{}/{}", rel, name) }
+}
+
+pub(super) fn join_remote(parent: &str, name: &str) -> String {
+    if parent.ends_with('/') { format!("{}{}", parent, name) } else { format!("{}/{}", parent, name) }
+}
+
+pub(super) fn remote_dest(root: &str, rel: &str) -> String {
+    if rel.is_empty() { root.to_string() } else { join_remote(root, rel) }
+}
+
+pub(super) fn local_dest(root: &str, rel: &str) -> PathBuf {
+    rel.split('/').filter(|p| !p.is_empty()).fold(PathBuf::from(root), |path, part| path.join(part))
+}
+
+// --- 本地扫描 (walkdir 跟随链接时自带环路检测) ---
+async fn scan_local(root: &str, mode: SymlinkMode) -> Result<Plan, String> {
+    let root = root.to_string();
+    tokio::task::spawn_blocking(move || {
+        let root_path = PathBuf::from(&root);
+        std::fs::symlink_metadata(&root_path).map_err(|e| format!("{}: {}", root, e))?;
+
+        let mut plan = Plan::default();
+        for item in WalkDir::new(&root_path).follow_links(mode == SymlinkMode::Follow) {
+            let entry = match item {
+                Ok(entry) => entry,
+                Err(e) => {
+                    let path = e.path().map_or_else(|| root.clone(), |p| p.display().to_string());
+                    plan.fail(&path, e.to_string());
+                    continue;
+                }
+            };
+            let source = entry.path().to_string_lossy().to_string();
+            let rel = entry
+                .path()
+                .strip_prefix(&root_path)
+                .map(|p| p.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/"))
+                .unwrap_or_default();
+            let file_type = entry.file_type();
+
+            // 跟随模式下 file_type 已是目标类型，这里只剩未跟随的链接
+            if file_type.is_symlink() {
+                match mode {
+                    SymlinkMode::Copy => match std::fs::read_link(entry.path()) {
+                        Ok(target) => plan.entries.push(Entry {
+                            rel,
+                            source,
+                            kind: Kind::Symlink(target.to_string_lossy().to_string()),
+                            size: 0,
+                        }),
+                        Err(e) => plan.fail(&source, e.to_string()),
+                    },
+                    _ => plan.skipped.push(source),
+                }
+            } else if file_type.is_dir() {
+                plan.entries.push(Entry { rel, source, kind: Kind::Dir, size: 0 });
+            } else if file_type.is_file() {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                plan.entries.push(Entry { rel, source, kind: Kind::File, size });
+            } else {
+                plan.skipped.push(source);
+            }
+        }
+        Ok(plan)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// --- 远程扫描 ---
+struct RemoteWalker<'a> {
+    sftp: &'a SftpSession,
+    mode: SymlinkMode,
+    plan: Plan,
+    /// 待读取的目录 (rel, path)
+    pending: Vec<(String, String)>,
+    /// 跟随链接时已进入过的目录 (规范路径)
+    visited: HashSet<String>,
+}
+
+impl RemoteWalker<'_> {
+    // meta 为 lstat 结果 (readdir 返回的属性同样不跟随链接)
+    async fn visit(&mut self, rel: String, path: String, meta: Metadata) {
+        let meta = if meta.is_symlink() {
+            match self.mode {
+                SymlinkMode::Skip => {
+                    self.plan.skipped.push(path);
+                    return;
+                }
+                SymlinkMode::Copy => {
+                    match with_timeout(5, self.sftp.read_link(path.clone())).await {
+                        Ok(target) => self.plan.entries.push(Entry { rel, source: path, kind: Kind::Symlink(target), size: 0 }),
+                        Err(e) => self.plan.fail(&path, e),
+                    }
+                    return;
+                }
+                SymlinkMode::Follow => match with_timeout(5, self.sftp.metadata(path.clone())).await {
+                    Ok(target) => target,
+                    Err(e) => {
+                        self.plan.fail(&path, format!("Broken symlink: {}", e));
+                        return;
+                    }
+                },
+            }
+        } else {
+            meta
+        };
+
+        if meta.is_dir() {
+            if self.mode == SymlinkMode::Follow {
+                let key = with_timeout(5, self.sftp.canonicalize(path.clone())).await.unwrap_or_else(|_| path.clone());
+                if !self.visited.insert(key) {
+                    self.plan.fail(&path, "Symlink loop detected");
+                    return;
+                }
+            }
+            self.plan.entries.push(Entry { rel: rel.clone(), source: path.clone(), kind: Kind::Dir, size: 0 });
+            self.pending.push((rel, path));
+        } else if meta.is_regular() {
+            let size = meta.size.unwrap_or(0);
+            self.plan.entries.push(Entry { rel, source: path, kind: Kind::File, size });
+        } else {
+            self.plan.skipped.push(path);
+        }
+    }
+}
+
+pub(super) async fn scan_remote(sftp: &SftpSession, root: &str, mode: SymlinkMode) -> Result<Plan, String> {
+    let root_meta = with_timeout(5, sftp.symlink_metadata(root.to_string()))
+        .await
+        .map_err(|e| format!("{}: {}", root, e))?;
+
+    let mut walker = RemoteWalker { sftp, mode, plan: Plan::default(), pending: Vec::new(), visited: HashSet::new() };
+    walker.visit(String::new(), root.to_string(), root_meta).await;
+
+    while let Some((rel, path)) = walker.pending.pop() {
+        let dir = match with_timeout(10, sftp.read_dir(path.clone())).await {
+            Ok(dir) => dir,
+            Err(e) => {
+                walker.plan.fail(&path, e);
+                continue;
+            }
+        };
+        for entry in dir {
+            let name = entry.file_name();
+            if name == "." || name == ".." {
+                continue;
+            }
+            walker.visit(join_rel(&rel, &name), join_remote(&path, &name), entry.metadata()).await;
+        }
+    }
+    Ok(walker.plan)
+}
+
+// --- 执行：逐条处理并汇总 ---
+pub(super) struct Batch<'a> {
+    app: &'a AppHandle,
+    event: Option<String>,
+    total_entries: usize,
+    total_bytes: u64,
+    done_entries: usize,
+    pub report: BatchReport,
+    /// 创建失败的目录，其下内容不再尝试
+    failed_dirs: Vec<String>,
+    last_emit: Instant,
+}
+
+impl<'a> Batch<'a> {
+    pub fn new(app: &'a AppHandle, op_id: Option<String>, plan: &mut Plan) -> Self {
+        let total_bytes = plan.entries.iter().filter(|e| matches!(e.kind, Kind::File)).map(|e| e.size).sum();
+        Self {
+            app,
+            event: op_id.map(|id| format!("fs-batch-progress-{}", id)),
+            total_entries: plan.entries.len(),
+            total_bytes,
+            done_entries: 0,
+            report: BatchReport {
+                skipped: std::mem::take(&mut plan.skipped),
+                failures: std::mem::take(&mut plan.failures),
+                ..Default::default()
+            },
+            failed_dirs: Vec::new(),
+            last_emit: Instant::now(),
+        }
+    }
+
+    pub fn under_failed_dir(&self, rel: &str) -> bool {
+        self.failed_dirs.iter().any(|dir| dir.is_empty() || rel.starts_with(&format!("{}/", dir)))
+    }
+
+    pub fn record(&mut self, entry: &Entry, result: Result<(), String>) {
+        self.done_entries += 1;
+        match result {
+            Ok(()) => match entry.kind {
+                Kind::Dir => self.report.dirs += 1,
+                Kind::File => {
+                    self.report.files += 1;
+                    self.report.bytes += entry.size;
+                }
+                Kind::Symlink(_) => self.report.symlinks += 1,
+            },
+            Err(error) => {
+                if matches!(entry.kind, Kind::Dir) {
+                    self.failed_dirs.push(entry.rel.clone());
+                }
+                self.report.failures.push(PathError { path: entry.source.clone(), error });
+            }
+        }
+
+        let done = self.done_entries == self.total_entries;
+        if let Some(event) = &self.event {
+            if done || self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+                self.last_emit = Instant::now();
+                let _ = self.app.emit(
+                    event,
+                    BatchProgress {
+                        done_entries: self.done_entries,
+                        total_entries: self.total_entries,
+                        done_bytes: self.report.bytes,
+                        total_bytes: self.total_bytes,
+                        current_path: entry.source.clone(),
+                    },
+                );
+            }
+        }
+    }
+}
+
+pub(super) async fn upload_one(sftp: &SftpSession, local: &str, remote: &str) -> Result<(), String> {
+    let mut ends = open_upload(sftp, local, remote, false).await?;
+    tokio::io::copy(&mut ends.src, &mut ends.dst).await.map_err(|e| format!("Upload stream failed: {}", e))?;
+    ends.dst.shutdown().await.map_err(|e| format!("Flush failed: {}", e))
+}
+
+pub(super) async fn download_one(sftp: &SftpSession, remote: &str, local: &str) -> Result<(), String> {
+    let mut ends = open_download(sftp, remote, local, false).await?;
+    tokio::io::copy(&mut ends.src, &mut ends.dst).await.map_err(|e| format!("Download stream failed: {}", e))?;
+    ends.dst.flush().await.map_err(|e| e.to_string())
+}
+
+// 已存在的目录视为成功
+pub(super) async fn ensure_remote_dir(sftp: &SftpSession, path: &str) -> Result<(), String> {
+    if let Err(e) = with_timeout(5, sftp.create_dir(path.to_string())).await {
+        match with_timeout(5, sftp.metadata(path.to_string())).await {
+            Ok(meta) if meta.is_dir() => {}
+            _ => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+// SFTP 的 SYMLINK 请求在 OpenSSH 上参数顺序与规范相反，改用 ln 创建
+pub(super) async fn remote_symlink(ssh_state: &SshState, id: &str, target: &str, link: &str) -> Result<(), String> {
+    let handle = ssh_state.get_handle(id)?;
+    let cmd = format!("ln -sfn -- {} {}", exec::shell_quote(target), exec::shell_quote(link));
+    let output = exec::run(&handle, &exec::ExecRequest::new(cmd)).await?;
+    if !output.success() {
+        return Err(output.error_message());
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn local_symlink(target: &str, link: &Path) -> Result<(), String> {
+    if tokio::fs::symlink_metadata(link).await.is_ok() {
+        tokio::fs::remove_file(link).await.map_err(|e| e.to_string())?;
+    }
+    tokio::fs::symlink(target, link).await.map_err(|e| e.to_string())
+}
+
+#[cfg(not(unix))]
+async fn local_symlink(_target: &str, _link: &Path) -> Result<(), String> {
+    Err("Symbolic links cannot be recreated on this platform".to_string())
+}
+
+// 先删内容再删目录：扫描结果中父目录总在前，倒序处理即可
+pub(super) async fn remove_entries(sftp: &SftpSession, plan: &Plan, batch: &mut Batch<'_>) {
+    for entry in plan.entries.iter().rev() {
+        let result = match entry.kind {
+            Kind::Dir => with_timeout(8, sftp.remove_dir(entry.source.clone())).await,
+            _ => with_timeout(8, sftp.remove_file(entry.source.clone())).await,
+        };
+        batch.record(entry, result);
+    }
+}
+
+// ==============================================================================
+// 🟢 命令
+// ==============================================================================
+
+/// location: local | remote；id 仅在 remote 时使用
+#[tauri::command]
+pub async fn sftp_scan_tree(
+    ssh_state: State<'_, SshState>,
+    id: String,
+    path: String,
+    location: String,
+    symlinks: Option<SymlinkMode>,
+) -> Result<TreeScan, String> {
+    let mode = symlinks.unwrap_or_default();
+    let plan = match location.as_str() {
+        "local" => scan_local(&path, mode).await?,
+        "remote" => {
+            let sftp = ssh_state.get_sftp(&id).await?;
+            scan_remote(&sftp, &path, mode).await?
+        }
+        other => return Err(format!("Unknown location: {}", other)),
+    };
+    Ok(plan.summary())
+}
+
+/// 把本地文件或目录上传为 remote_path (已存在的同名文件会被覆盖)
+#[tauri::command]
+pub async fn sftp_upload_tree(
+    app: AppHandle,
+    ssh_state: State<'_, SshState>,
+    id: String,
+    local_path: String,
+    remote_path: String,
+    symlinks: Option<SymlinkMode>,
+    op_id: Option<String>,
+) -> Result<BatchReport, String> {
+    let sftp = ssh_state.get_sftp(&id).await?;
+    let mut plan = scan_local(&local_path, symlinks.unwrap_or_default()).await?;
+    let mut batch = Batch::new(&app, op_id, &mut plan);
+
+    for entry in &plan.entries {
+        if batch.under_failed_dir(&entry.rel) {
+            continue;
+        }
+        let dest = remote_dest(&remote_path, &entry.rel);
+        let result = match &entry.kind {
+            Kind::Dir => ensure_remote_dir(&sftp, &dest).await,
+            Kind::File => upload_one(&sftp, &entry.source, &dest).await,
+            Kind::Symlink(target) => remote_symlink(&ssh_state, &id, target, &dest).await,
+        };
+        batch.record(entry, result);
+    }
+    Ok(batch.report)
+}
+
+/// 把远程文件或目录下载为 local_path
+#[tauri::command]
+pub async fn sftp_download_tree(
+    app: AppHandle,
+    ssh_state: State<'_, SshState>,
+    id: String,
+    remote_path: String,
+    local_path: String,
+    symlinks: Option<SymlinkMode>,
+    op_id: Option<String>,
+) -> Result<BatchReport, String> {
+    let sftp = ssh_state.get_sftp(&id).await?;
+    let mut plan = scan_remote(&sftp, &remote_path, symlinks.unwrap_or_default()).await?;
+    let mut batch = Batch::new(&app, op_id, &mut plan);
+
+    for entry in &plan.entries {
+        if batch.under_failed_dir(&entry.rel) {
+            continue;
+        }
+        let dest = local_dest(&local_path, &entry.rel);
+        let result = match &entry.kind {
+            Kind::Dir => tokio::fs::create_dir_all(&dest).await.map_err(|e| e.to_string()),
+            Kind::File => download_one(&sftp, &entry.source, &dest.to_string_lossy()).await,
+            Kind::Symlink(target) => local_symlink(target, &dest).await,
+        };
+        batch.record(entry, result);
+    }
+    Ok(batch.report)
+}
+
+/// 递归删除远程路径；链接只删除链接本身，不进入其目标
+#[tauri::command]
+pub async fn sftp_delete_tree(
+    app: AppHandle,
+    ssh_state: State<'_, SshState>,
+    id: String,
+    path: String,
+    op_id: Option<String>,
+) -> Result<BatchReport, String> {
+    let sftp = ssh_state.get_sftp(&id).await?;
+    let mut plan = scan_remote(&sftp, &path, SymlinkMode::Copy).await?;
+    let mut batch = Batch::new(&app, op_id, &mut plan);
+
+    remove_entries(&sftp, &plan, &mut batch).await;
+    Ok(batch.report)
+}
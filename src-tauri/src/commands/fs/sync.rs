@@ -0,0 +1,1001 @@
+// src-tauri/src/commands/fs/sync.rs
+// 本地目录 <-> 远程目录 的文件夹同步 (配置按服务器保存在 sync_profiles 表)
+//   1. 两端扫描 (include / exclude glob 过滤，命中 exclude 的目录整棵跳过；链接与特殊文件不参与)
+//   2. 与上次同步完成时的基线 (sync_state 表) 三方对比，得出 upload / download / delete / conflict 计划
+//   3. 预演只返回计划；执行时重新生成计划，按用户的冲突处理覆盖后逐条执行，并更新基线
+// 只同步文件：删除文件后留下的空目录不会被清理
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use regex::Regex;
+use russh_sftp::client::error::Error as SftpError;
+use russh_sftp::client::SftpSession;
+use russh_sftp::protocol::StatusCode;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite};
+use tauri::{AppHandle, Emitter, State};
+use walkdir::WalkDir;
+
+use crate::commands::ssh::core::SshHandle;
+use crate::commands::ssh::SshState;
+use crate::commands::transfer::{now_ms, verify};
+use crate::models::SyncProfile;
+use crate::state::AppState;
+
+use super::recursive::{
+    download_one, ensure_remote_dir, join_rel, join_remote, local_dest, remote_dest, upload_one, BatchProgress,
+    PathError,
+};
+use super::with_timeout;
+
+// FAT 与部分 SFTP 服务器的时间戳只精确到 2 秒
+const MTIME_TOLERANCE: u64 = 2;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncAction {
+    Upload,
+    Download,
+    DeleteLocal,
+    DeleteRemote,
+    Conflict,
+    /// 计划中不会出现，仅用于执行时手动跳过某个路径
+    Skip,
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct FileState {
+    pub size: u64,
+    /// 修改时间 (秒)
+    pub mtime: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncItem {
+    /// 相对同步根目录的路径 ('/' 分隔)
+    pub path: String,
+    pub action: SyncAction,
+    /// new | missing | modified | differs | deleted | extraneous | bothModified | modifiedAndDeleted
+    pub reason: &'static str,
+    pub local: Option<FileState>,
+    pub remote: Option<FileState>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPlan {
+    pub items: Vec<SyncItem>,
+    /// 两端一致、无需处理的文件数
+    pub unchanged: usize,
+    pub upload_bytes: u64,
+    pub download_bytes: u64,
+    /// 扫描阶段无法读取的路径
+    pub failures: Vec<PathError>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub uploaded: usize,
+    pub downloaded: usize,
+    pub deleted: usize,
+    /// 未处理的冲突与手动跳过的路径
+    pub skipped: usize,
+    pub bytes: u64,
+    pub failures: Vec<PathError>,
+}
+
+// ==============================================================================
+// 🟢 glob 过滤
+// ==============================================================================
+
+// 不含 '/' 的模式匹配任意层级的名称 (同 .gitignore)；含 '/' 的模式从同步根开始匹配
+//   *  不跨目录    ** 可跨目录    ?  单个字符
+fn glob_regex(pattern: &str) -> Result<Regex, String> {
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = trimmed.contains('/');
+    let mut re = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+
+    let mut chars = trimmed.trim_start_matches('/').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // "**/" 也可以匹配零层目录
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            _ => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re).map_err(|e| format!("Invalid glob pattern {}: {}", pattern, e))
+}
+
+// 每行一个模式，忽略空行与 # 注释
+fn compile_globs(text: &str) -> Result<Vec<Regex>, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(glob_regex)
+        .collect()
+}
+
+#[derive(Clone)]
+struct Globs {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl Globs {
+    fn parse(profile: &SyncProfile) -> Result<Self, String> {
+        Ok(Self { include: compile_globs(&profile.include_globs)?, exclude: compile_globs(&profile.exclude_globs)? })
+    }
+
+    fn excludes(&self, rel: &str) -> bool {
+        self.exclude.iter().any(|re| re.is_match(rel))
+    }
+
+    // 命中文件本身或其任一上级目录即算包含 (例如 "src" 包含 src/ 下全部文件)
+    fn includes(&self, rel: &str) -> bool {
+        if self.include.is_empty() {
+            return true;
+        }
+        let mut prefixes = rel.match_indices('/').map(|(i, _)| &rel[..i]).chain(std::iter::once(rel));
+        prefixes.any(|prefix| self.include.iter().any(|re| re.is_match(prefix)))
+    }
+
+    fn accepts_file(&self, rel: &str) -> bool {
+        !self.excludes(rel) && self.includes(rel)
+    }
+}
+
+// ==============================================================================
+// 🟢 扫描
+// ==============================================================================
+
+type FileMap = BTreeMap<String, FileState>;
+
+// 单侧扫描结果；failed_dirs 为未能完整读取的子树 (相对路径，空串表示整个根目录)
+// 这些路径下的文件缺失不代表已删除，计划时整体跳过
+#[derive(Debug, Default)]
+struct Scan {
+    files: FileMap,
+    failures: Vec<PathError>,
+    failed_dirs: Vec<String>,
+}
+
+impl Scan {
+    fn fail(&mut self, rel: String, path: String, error: String) {
+        self.failures.push(PathError { path, error });
+        self.failed_dirs.push(rel);
+    }
+}
+
+fn under_failed_dir(failed_dirs: &[String], rel: &str) -> bool {
+    failed_dirs.iter().any(|dir| {
+        dir.is_empty() || rel == dir || (rel.starts_with(dir.as_str()) && rel[dir.len()..].starts_with('/'))
+    })
+}
+
+fn rel_of(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .map(|p| p.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/"))
+        .unwrap_or_default()
+}
+
+fn local_state(meta: &std::fs::Metadata) -> FileState {
+    let mtime = meta.modified().ok().and_then(|t| t.duration_since(UNIX_EPOCH).ok()).map_or(0, |d| d.as_secs());
+    FileState { size: meta.len(), mtime }
+}
+
+async fn scan_local_files(root: &str, globs: &Globs) -> Result<Scan, String> {
+    let root = PathBuf::from(root);
+    let globs = globs.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut scan = Scan::default();
+        match std::fs::metadata(&root) {
+            Ok(meta) if meta.is_dir() => {}
+            Ok(_) => return Err(format!("{} is not a directory", root.display())),
+            // 首次从远程同步到新目录时，本地目录尚不存在
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(scan),
+            Err(e) => return Err(format!("{}: {}", root.display(), e)),
+        }
+
+        let walker = WalkDir::new(&root)
+            .min_depth(1)
+            .into_iter()
+            .filter_entry(|e| !(e.file_type().is_dir() && globs.excludes(&rel_of(&root, e.path()))));
+        for item in walker {
+            let entry = match item {
+                Ok(entry) => entry,
+                Err(e) => {
+                    // 没有路径的错误 (例如链接环路) 无法定位，按整棵树处理
+                    let (rel, path) = match e.path() {
+                        Some(p) => (rel_of(&root, p), p.display().to_string()),
+                        None => (String::new(), root.display().to_string()),
+                    };
+                    scan.fail(rel, path, e.to_string());
+                    continue;
+                }
+            };
+            let rel = rel_of(&root, entry.path());
+            if !entry.file_type().is_file() || !globs.accepts_file(&rel) {
+                continue;
+            }
+            match entry.metadata() {
+                Ok(meta) => {
+                    scan.files.insert(rel, local_state(&meta));
+                }
+                Err(e) => scan.fail(rel, entry.path().display().to_string(), e.to_string()),
+            }
+        }
+        Ok(scan)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+async fn scan_remote_files(sftp: &SftpSession, root: &str, globs: &Globs) -> Result<Scan, String> {
+    let mut scan = Scan::default();
+    // 只有服务器明确回复 NoSuchFile 才视为目录不存在；超时、权限错误都不能当成空目录
+    match tokio::time::timeout(Duration::from_secs(5), sftp.metadata(root.to_string())).await {
+        Ok(Ok(meta)) if meta.is_dir() => {}
+        Ok(Ok(_)) => return Err(format!("{} is not a directory", root)),
+        // 远程目录尚不存在，首次上传时创建
+        Ok(Err(SftpError::Status(status))) if status.status_code == StatusCode::NoSuchFile => return Ok(scan),
+        Ok(Err(e)) => return Err(format!("{}: {}", root, e)),
+        Err(_) => return Err("SFTP request timed out".to_string()),
+    }
+
+    let mut pending = vec![(String::new(), root.to_string())];
+    while let Some((rel, path)) = pending.pop() {
+        let dir = match with_timeout(10, sftp.read_dir(path.clone())).await {
+            Ok(dir) => dir,
+            Err(error) => {
+                scan.fail(rel, path, error);
+                continue;
+            }
+        };
+        for entry in dir {
+            let name = entry.file_name();
+            if name == "." || name == ".." {
+                continue;
+            }
+            let child = join_rel(&rel, &name);
+            let meta = entry.metadata();
+            if meta.is_dir() {
+                if !globs.excludes(&child) {
+                    pending.push((child, join_remote(&path, &name)));
+                }
+            } else if meta.is_regular() && globs.accepts_file(&child) {
+                let state = FileState { size: meta.size.unwrap_or(0), mtime: meta.mtime.unwrap_or(0) as u64 };
+                scan.files.insert(child, state);
+            }
+        }
+    }
+    Ok(scan)
+}
+
+// ==============================================================================
+// 🟢 计划
+// ==============================================================================
+
+// 上次同步完成时两端的状态
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct BaseRow {
+    path: String,
+    size: i64,
+    local_mtime: i64,
+    remote_mtime: i64,
+}
+
+async fn load_baseline(pool: &Pool<Sqlite>, profile_id: &str) -> Result<HashMap<String, BaseRow>, String> {
+    let rows = sqlx::query_as::<_, BaseRow>(
+        "SELECT path, size, local_mtime, remote_mtime FROM sync_state WHERE profile_id = ?",
+    )
+    .bind(profile_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows.into_iter().map(|row| (row.path.clone(), row)).collect())
+}
+
+fn mtime_eq(a: u64, b: u64) -> bool {
+    a.abs_diff(b) <= MTIME_TOLERANCE
+}
+
+fn changed_since(state: &FileState, size: i64, mtime: i64) -> bool {
+    state.size as i64 != size || !mtime_eq(state.mtime, mtime.max(0) as u64)
+}
+
+// 计划之外执行阶段还需要的信息
+struct Planned {
+    plan: SyncPlan,
+    /// 两端一致的文件，执行后写入基线
+    in_sync: Vec<(String, FileState, FileState)>,
+    /// 两端都已不存在的基线记录
+    stale: Vec<String>,
+}
+
+// 按配置的比较方式判断内容是否一致；hash 只在大小相同时才计算
+async fn same_content(
+    profile: &SyncProfile,
+    sftp: &SftpSession,
+    handle: Option<&SshHandle>,
+    rel: &str,
+    local: &FileState,
+    remote: &FileState,
+) -> Result<bool, String> {
+    if local.size != remote.size {
+        return Ok(false);
+    }
+    match profile.compare.as_str() {
+        "size" => Ok(true),
+        "hash" => {
+            let local_path = local_dest(&profile.local_path, rel);
+            let remote_path = remote_dest(&profile.remote_path, rel);
+            let result = verify::verify(handle, sftp, &local_path.to_string_lossy(), &remote_path).await?;
+            Ok(result.matched)
+        }
+        _ => Ok(mtime_eq(local.mtime, remote.mtime)),
+    }
+}
+
+// 决定单个路径的动作 (不访问文件系统)
+struct Planner<'a> {
+    profile: &'a SyncProfile,
+}
+
+impl Planner<'_> {
+    // 两端都存在但内容不同
+    fn both_present(&self, base: Option<&BaseRow>, local_changed: bool, remote_changed: bool) -> (SyncAction, &'static str) {
+        let reason = if base.is_some() { "modified" } else { "differs" };
+        match self.profile.direction.as_str() {
+            "upload" => (SyncAction::Upload, reason),
+            "download" => (SyncAction::Download, reason),
+            _ => match (base.is_some(), local_changed, remote_changed) {
+                (false, _, _) => (SyncAction::Conflict, "differs"),
+                (true, true, false) => (SyncAction::Upload, "modified"),
+                (true, false, true) => (SyncAction::Download, "modified"),
+                _ => (SyncAction::Conflict, "bothModified"),
+            },
+        }
+    }
+
+    // 只有一端存在：is_local 表示存在于本地
+    fn one_side(&self, base: Option<&BaseRow>, state: &FileState, is_local: bool) -> Option<(SyncAction, &'static str)> {
+        let (copy, delete, mirror_from, mirror_to) = if is_local {
+            (SyncAction::Upload, SyncAction::DeleteLocal, "upload", "download")
+        } else {
+            (SyncAction::Download, SyncAction::DeleteRemote, "download", "upload")
+        };
+        let propagate = self.profile.propagate_deletes;
+        let direction = self.profile.direction.as_str();
+
+        if direction == mirror_from {
+            return Some((copy, if base.is_some() { "missing" } else { "new" }));
+        }
+        if direction == mirror_to {
+            return propagate.then_some((delete, "extraneous"));
+        }
+        let base = match base {
+            Some(base) => base,
+            None => return Some((copy, "new")),
+        };
+        // 另一端在上次同步后删除了该文件
+        let mtime = if is_local { base.local_mtime } else { base.remote_mtime };
+        if changed_since(state, base.size, mtime) {
+            Some((SyncAction::Conflict, "modifiedAndDeleted"))
+        } else if propagate {
+            Some((delete, "deleted"))
+        } else {
+            Some((copy, "missing"))
+        }
+    }
+}
+
+async fn build_plan(
+    ssh_state: &SshState,
+    pool: &Pool<Sqlite>,
+    id: &str,
+    profile: &SyncProfile,
+) -> Result<Planned, String> {
+    let globs = Globs::parse(profile)?;
+    let sftp = ssh_state.get_sftp(id).await?;
+    let local = scan_local_files(&profile.local_path, &globs).await?;
+    let remote = scan_remote_files(&sftp, &profile.remote_path, &globs).await?;
+    let baseline = load_baseline(pool, &profile.id).await?;
+    let handle = ssh_state.get_handle(id).ok();
+
+    let mut failures = local.failures;
+    failures.extend(remote.failures);
+    let failed_dirs: Vec<String> = local.failed_dirs.into_iter().chain(remote.failed_dirs).collect();
+    let (local, remote) = (local.files, remote.files);
+
+    let planner = Planner { profile };
+    let mut planned = Planned { plan: SyncPlan::default(), in_sync: Vec::new(), stale: Vec::new() };
+    let paths: BTreeSet<&String> = local.keys().chain(remote.keys()).chain(baseline.keys()).collect();
+
+    for path in paths {
+        // 扫描不完整的子树：文件 "缺失" 可能只是没读到，不做任何修改，基线原样保留
+        if under_failed_dir(&failed_dirs, path) {
+            continue;
+        }
+        let (l, r, base) = (local.get(path).copied(), remote.get(path).copied(), baseline.get(path));
+        let decision = match (&l, &r) {
+            (Some(l), Some(r)) => {
+                let local_changed = base.map_or(true, |b| changed_since(l, b.size, b.local_mtime));
+                let remote_changed = base.map_or(true, |b| changed_since(r, b.size, b.remote_mtime));
+                let same = if local_changed || remote_changed {
+                    match same_content(profile, &sftp, handle.as_deref(), path, l, r).await {
+                        Ok(same) => same,
+                        Err(error) => {
+                            failures.push(PathError { path: path.clone(), error });
+                            continue;
+                        }
+                    }
+                } else {
+                    true
+                };
+                if same {
+                    planned.in_sync.push((path.clone(), *l, *r));
+                    continue;
+                }
+                Some(planner.both_present(base, local_changed, remote_changed))
+            }
+            (Some(l), None) => planner.one_side(base, l, true),
+            (None, Some(r)) => planner.one_side(base, r, false),
+            (None, None) => {
+                planned.stale.push(path.clone());
+                continue;
+            }
+        };
+
+        if let Some((action, reason)) = decision {
+            match action {
+                SyncAction::Upload => planned.plan.upload_bytes += l.map_or(0, |s| s.size),
+                SyncAction::Download => planned.plan.download_bytes += r.map_or(0, |s| s.size),
+                _ => {}
+            }
+            planned.plan.items.push(SyncItem { path: path.clone(), action, reason, local: l, remote: r });
+        }
+    }
+
+    planned.plan.unchanged = planned.in_sync.len();
+    planned.plan.failures = failures;
+    Ok(planned)
+}
+
+// ==============================================================================
+// 🟢 执行
+// ==============================================================================
+
+fn set_local_mtime(path: &Path, mtime: u64) -> Result<(), String> {
+    let file = std::fs::File::options().write(true).open(path).map_err(|e| e.to_string())?;
+    file.set_modified(UNIX_EPOCH + Duration::from_secs(mtime)).map_err(|e| e.to_string())
+}
+
+struct Applier<'a> {
+    app: &'a AppHandle,
+    sftp: &'a SftpSession,
+    profile: &'a SyncProfile,
+    /// 已确认存在的远程目录
+    remote_dirs: HashSet<String>,
+    event: Option<String>,
+    progress: BatchProgress,
+    last_emit: Instant,
+}
+
+impl Applier<'_> {
+    async fn ensure_remote_parents(&mut self, rel: &str) -> Result<(), String> {
+        let parents = std::iter::once("").chain(rel.match_indices('/').map(|(i, _)| &rel[..i]));
+        for parent in parents {
+            let dir = remote_dest(&self.profile.remote_path, parent);
+            if !self.remote_dirs.contains(&dir) {
+                ensure_remote_dir(self.sftp, &dir).await?;
+                self.remote_dirs.insert(dir);
+            }
+        }
+        Ok(())
+    }
+
+    // 返回传输后两端的状态 (写入基线)
+    async fn upload(&mut self, rel: &str, local: FileState) -> Result<(FileState, FileState), String> {
+        let local_path = local_dest(&self.profile.local_path, rel);
+        let remote_path = remote_dest(&self.profile.remote_path, rel);
+        self.ensure_remote_parents(rel).await?;
+        upload_one(self.sftp, &local_path.to_string_lossy(), &remote_path).await?;
+
+        // 远程 mtime 对齐本地，按 mtime 比较时下次视为一致；只提交时间字段
+        let mut meta = with_timeout(5, self.sftp.metadata(remote_path.clone())).await?;
+        meta.size = None;
+        meta.uid = None;
+        meta.gid = None;
+        meta.permissions = None;
+        meta.atime = Some(local.mtime as u32);
+        meta.mtime = Some(local.mtime as u32);
+        let _ = with_timeout(5, self.sftp.set_metadata(remote_path.clone(), meta)).await;
+
+        let after = with_timeout(5, self.sftp.metadata(remote_path)).await?;
+        Ok((local, FileState { size: after.size.unwrap_or(0), mtime: after.mtime.unwrap_or(0) as u64 }))
+    }
+
+    async fn download(&mut self, rel: &str, remote: FileState) -> Result<(FileState, FileState), String> {
+        let local_path = local_dest(&self.profile.local_path, rel);
+        let remote_path = remote_dest(&self.profile.remote_path, rel);
+        if let Some(parent) = local_path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| e.to_string())?;
+        }
+        download_one(self.sftp, &remote_path, &local_path.to_string_lossy()).await?;
+
+        let _ = set_local_mtime(&local_path, remote.mtime);
+        let meta = tokio::fs::metadata(&local_path).await.map_err(|e| e.to_string())?;
+        Ok((local_state(&meta), remote))
+    }
+
+    async fn delete_local(&self, rel: &str) -> Result<(), String> {
+        tokio::fs::remove_file(local_dest(&self.profile.local_path, rel)).await.map_err(|e| e.to_string())
+    }
+
+    async fn delete_remote(&self, rel: &str) -> Result<(), String> {
+        with_timeout(8, self.sftp.remove_file(remote_dest(&self.profile.remote_path, rel))).await
+    }
+
+    fn tick(&mut self, rel: &str, bytes: u64) {
+        self.progress.done_entries += 1;
+        self.progress.done_bytes += bytes;
+        let done = self.progress.done_entries == self.progress.total_entries;
+        if let Some(event) = &self.event {
+            if done || self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+                self.last_emit = Instant::now();
+                self.progress.current_path = rel.to_string();
+                let _ = self.app.emit(event, self.progress.clone());
+            }
+        }
+    }
+}
+
+// 执行后对基线的修改
+enum BaseChange {
+    Upsert(String, FileState, FileState),
+    Remove(String),
+}
+
+async fn write_baseline(pool: &Pool<Sqlite>, profile_id: &str, changes: Vec<BaseChange>) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    for change in changes {
+        match change {
+            BaseChange::Upsert(path, local, remote) => {
+                sqlx::query(
+                    "INSERT OR REPLACE INTO sync_state (profile_id, path, size, local_mtime, remote_mtime)
+                     VALUES (?, ?, ?, ?, ?)",
+                )
+                .bind(profile_id)
+                .bind(path)
+                .bind(local.size as i64)
+                .bind(local.mtime as i64)
+                .bind(remote.mtime as i64)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            }
+            BaseChange::Remove(path) => {
+                sqlx::query("DELETE FROM sync_state WHERE profile_id = ? AND path = ?")
+                    .bind(profile_id)
+                    .bind(path)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    sqlx::query("UPDATE sync_profiles SET last_synced_at = ? WHERE id = ?")
+        .bind(now_ms())
+        .bind(profile_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())
+}
+
+// ==============================================================================
+// 🟢 配置存取 (sync_profiles 表)
+// ==============================================================================
+
+const PROFILE_COLUMNS: &str = "id, server_id, name, local_path, remote_path, direction, compare, include_globs, \
+     exclude_globs, propagate_deletes, last_synced_at, created_at, updated_at";
+
+async fn find_profile(pool: &Pool<Sqlite>, id: &str) -> Result<Option<SyncProfile>, String> {
+    sqlx::query_as::<_, SyncProfile>(&format!("SELECT {} FROM sync_profiles WHERE id = ?", PROFILE_COLUMNS))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn get_profile(pool: &Pool<Sqlite>, id: &str) -> Result<SyncProfile, String> {
+    find_profile(pool, id).await?.ok_or_else(|| format!("Sync profile {} not found", id))
+}
+
+/// 校验并补全配置 (名称为空时用远程路径)
+fn normalize_profile(profile: &mut SyncProfile) -> Result<(), String> {
+    profile.local_path = profile.local_path.trim().to_string();
+    profile.remote_path = profile.remote_path.trim().to_string();
+    if profile.local_path.is_empty() || profile.remote_path.is_empty() {
+        return Err("Local and remote paths are required".to_string());
+    }
+    profile.name = profile.name.trim().to_string();
+    if profile.name.is_empty() {
+        profile.name = profile.remote_path.clone();
+    }
+    if !matches!(profile.direction.as_str(), "both" | "upload" | "download") {
+        return Err(format!("Unknown sync direction: {}", profile.direction));
+    }
+    if !matches!(profile.compare.as_str(), "size" | "mtime" | "hash") {
+        return Err(format!("Unknown compare mode: {}", profile.compare));
+    }
+    // 提前报告无效的模式
+    Globs::parse(profile)?;
+    Ok(())
+}
+
+async fn clear_baseline(pool: &Pool<Sqlite>, profile_id: &str) -> Result<(), String> {
+    sqlx::query("DELETE FROM sync_state WHERE profile_id = ?")
+        .bind(profile_id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// ==============================================================================
+// 🟢 命令
+// ==============================================================================
+
+#[tauri::command]
+pub async fn list_sync_profiles(app_state: State<'_, AppState>, server_id: String) -> Result<Vec<SyncProfile>, String> {
+    sqlx::query_as::<_, SyncProfile>(&format!(
+        "SELECT {} FROM sync_profiles WHERE server_id = ? ORDER BY created_at ASC",
+        PROFILE_COLUMNS
+    ))
+    .bind(server_id)
+    .fetch_all(&app_state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// id 为空时新建
+#[tauri::command]
+pub async fn save_sync_profile(app_state: State<'_, AppState>, mut profile: SyncProfile) -> Result<SyncProfile, String> {
+    normalize_profile(&mut profile)?;
+    let now = now_ms();
+    let existing = if profile.id.is_empty() { None } else { find_profile(&app_state.db, &profile.id).await? };
+    match &existing {
+        Some(old) => {
+            profile.created_at = old.created_at;
+            profile.last_synced_at = old.last_synced_at;
+            // 换了目录后旧基线不再适用，下次按首次同步处理
+            if old.local_path != profile.local_path || old.remote_path != profile.remote_path {
+                clear_baseline(&app_state.db, &profile.id).await?;
+                profile.last_synced_at = None;
+            }
+        }
+        None => {
+            if profile.id.is_empty() {
+                profile.id = uuid::Uuid::new_v4().to_string();
+            }
+            profile.created_at = now;
+            profile.last_synced_at = None;
+        }
+    }
+    profile.updated_at = now;
+
+    sqlx::query(&format!(
+        "INSERT OR REPLACE INTO sync_profiles ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        PROFILE_COLUMNS
+    ))
+    .bind(&profile.id)
+    .bind(&profile.server_id)
+    .bind(&profile.name)
+    .bind(&profile.local_path)
+    .bind(&profile.remote_path)
+    .bind(&profile.direction)
+    .bind(&profile.compare)
+    .bind(&profile.include_globs)
+    .bind(&profile.exclude_globs)
+    .bind(profile.propagate_deletes)
+    .bind(profile.last_synced_at)
+    .bind(profile.created_at)
+    .bind(profile.updated_at)
+    .execute(&app_state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(profile)
+}
+
+#[tauri::command]
+pub async fn delete_sync_profile(app_state: State<'_, AppState>, id: String) -> Result<(), String> {
+    clear_baseline(&app_state.db, &id).await?;
+    sqlx::query("DELETE FROM sync_profiles WHERE id = ?")
+        .bind(&id)
+        .execute(&app_state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 预演：只扫描对比，不修改任何文件
+#[tauri::command]
+pub async fn sftp_sync_plan(
+    ssh_state: State<'_, SshState>,
+    app_state: State<'_, AppState>,
+    id: String,
+    profile_id: String,
+) -> Result<SyncPlan, String> {
+    let profile = get_profile(&app_state.db, &profile_id).await?;
+    Ok(build_plan(&ssh_state, &app_state.db, &id, &profile).await?.plan)
+}
+
+/// 执行同步。resolutions 按相对路径覆盖计划中的动作 (处理冲突或跳过)，未处理的冲突保持不动
+#[tauri::command]
+pub async fn sftp_sync_apply(
+    app: AppHandle,
+    ssh_state: State<'_, SshState>,
+    app_state: State<'_, AppState>,
+    id: String,
+    profile_id: String,
+    resolutions: Option<HashMap<String, SyncAction>>,
+    op_id: Option<String>,
+) -> Result<SyncReport, String> {
+    let profile = get_profile(&app_state.db, &profile_id).await?;
+    // 以执行时的实际状态为准重新生成计划，预演之后发生的变化不会被覆盖
+    let Planned { mut plan, in_sync, stale } = build_plan(&ssh_state, &app_state.db, &id, &profile).await?;
+    let resolutions = resolutions.unwrap_or_default();
+    for item in &mut plan.items {
+        if let Some(action) = resolutions.get(&item.path) {
+            item.action = *action;
+        }
+    }
+
+    // 扫描有失败时，另一端 "缺失" 的文件可能只是没读到，任何删除都不执行
+    let deletes_blocked = !plan.failures.is_empty();
+    let is_delete = |action: SyncAction| matches!(action, SyncAction::DeleteLocal | SyncAction::DeleteRemote);
+    let runnable = |item: &&SyncItem| {
+        !matches!(item.action, SyncAction::Conflict | SyncAction::Skip) && !(deletes_blocked && is_delete(item.action))
+    };
+    let total_bytes = plan
+        .items
+        .iter()
+        .filter(runnable)
+        .map(|item| match item.action {
+            SyncAction::Upload => item.local.map_or(0, |s| s.size),
+            SyncAction::Download => item.remote.map_or(0, |s| s.size),
+            _ => 0,
+        })
+        .sum();
+
+    let sftp = ssh_state.get_sftp(&id).await?;
+    let mut applier = Applier {
+        app: &app,
+        sftp: &sftp,
+        profile: &profile,
+        remote_dirs: HashSet::new(),
+        event: op_id.map(|op| format!("fs-batch-progress-{}", op)),
+        progress: BatchProgress {
+            done_entries: 0,
+            total_entries: plan.items.iter().filter(runnable).count(),
+            done_bytes: 0,
+            total_bytes,
+            current_path: String::new(),
+        },
+        last_emit: Instant::now(),
+    };
+
+    let mut report = SyncReport { failures: plan.failures, ..Default::default() };
+    let mut changes: Vec<BaseChange> = in_sync.into_iter().map(|(p, l, r)| BaseChange::Upsert(p, l, r)).collect();
+    changes.extend(stale.into_iter().map(BaseChange::Remove));
+
+    for item in &plan.items {
+        let path = item.path.clone();
+        if deletes_blocked && is_delete(item.action) {
+            let error = "Deletion refused: scan reported errors".to_string();
+            report.failures.push(PathError { path, error });
+            continue;
+        }
+        let result = match (item.action, item.local, item.remote) {
+            (SyncAction::Conflict | SyncAction::Skip, _, _) => {
+                report.skipped += 1;
+                continue;
+            }
+            (SyncAction::Upload, Some(local), _) => applier.upload(&path, local).await.map(|(l, r)| {
+                report.uploaded += 1;
+                report.bytes += l.size;
+                BaseChange::Upsert(path.clone(), l, r)
+            }),
+            (SyncAction::Download, _, Some(remote)) => applier.download(&path, remote).await.map(|(l, r)| {
+                report.downloaded += 1;
+                report.bytes += r.size;
+                BaseChange::Upsert(path.clone(), l, r)
+            }),
+            (SyncAction::DeleteLocal, Some(_), _) => applier.delete_local(&path).await.map(|_| {
+                report.deleted += 1;
+                BaseChange::Remove(path.clone())
+            }),
+            (SyncAction::DeleteRemote, _, Some(_)) => applier.delete_remote(&path).await.map(|_| {
+                report.deleted += 1;
+                BaseChange::Remove(path.clone())
+            }),
+            // 手动指定的动作与实际状态不符 (例如对只存在于远程的文件选择上传)
+            _ => Err("Action does not apply to this file".to_string()),
+        };
+        match result {
+            Ok(change) => changes.push(change),
+            Err(error) => report.failures.push(PathError { path: path.clone(), error }),
+        }
+        let bytes = match item.action {
+            SyncAction::Upload => item.local.map_or(0, |s| s.size),
+            SyncAction::Download => item.remote.map_or(0, |s| s.size),
+            _ => 0,
+        };
+        applier.tick(&path, bytes);
+    }
+
+    write_baseline(&app_state.db, &profile.id, changes).await?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(direction: &str, propagate_deletes: bool) -> SyncProfile {
+        SyncProfile {
+            id: "p".to_string(),
+            server_id: "s".to_string(),
+            name: "test".to_string(),
+            local_path: "/tmp/local".to_string(),
+            remote_path: "/srv/remote".to_string(),
+            direction: direction.to_string(),
+            compare: "mtime".to_string(),
+            include_globs: String::new(),
+            exclude_globs: String::new(),
+            propagate_deletes,
+            last_synced_at: None,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    fn base(size: i64, mtime: i64) -> BaseRow {
+        BaseRow { path: "a.txt".to_string(), size, local_mtime: mtime, remote_mtime: mtime }
+    }
+
+    #[test]
+    fn glob_without_slash_matches_any_depth() {
+        let re = glob_regex("*.log").unwrap();
+        assert!(re.is_match("a.log"));
+        assert!(re.is_match("logs/deep/a.log"));
+        assert!(!re.is_match("a.log.gz"));
+
+        let re = glob_regex("node_modules/").unwrap();
+        assert!(re.is_match("node_modules"));
+        assert!(re.is_match("web/node_modules"));
+    }
+
+    #[test]
+    fn glob_with_slash_is_anchored() {
+        let re = glob_regex("src/*.rs").unwrap();
+        assert!(re.is_match("src/main.rs"));
+        assert!(!re.is_match("src/bin/main.rs"));
+        assert!(!re.is_match("lib/src/main.rs"));
+
+        let re = glob_regex("/build").unwrap();
+        assert!(re.is_match("build"));
+        assert!(!re.is_match("x/build"));
+    }
+
+    #[test]
+    fn glob_double_star_and_question_mark() {
+        let re = glob_regex("src/**/*.rs").unwrap();
+        assert!(re.is_match("src/main.rs"));
+        assert!(re.is_match("src/a/b/main.rs"));
+
+        let re = glob_regex("docs/**").unwrap();
+        assert!(re.is_match("docs/a/b.md"));
+
+        let re = glob_regex("file?.txt").unwrap();
+        assert!(re.is_match("file1.txt"));
+        assert!(!re.is_match("file12.txt"));
+        assert!(!re.is_match("file/.txt"));
+
+        let re = glob_regex("a+b(1).txt").unwrap();
+        assert!(re.is_match("a+b(1).txt"));
+    }
+
+    #[test]
+    fn globs_include_ancestor_and_exclude() {
+        let mut p = profile("both", false);
+        p.include_globs = "# comment\nsrc\n".to_string();
+        p.exclude_globs = "*.tmp".to_string();
+        let globs = Globs::parse(&p).unwrap();
+        assert!(globs.accepts_file("src/a/b.rs"));
+        assert!(!globs.accepts_file("src/a/b.tmp"));
+        assert!(!globs.accepts_file("docs/readme.md"));
+    }
+
+    #[test]
+    fn failed_dirs_cover_their_subtree_only() {
+        let failed = vec!["logs".to_string()];
+        assert!(under_failed_dir(&failed, "logs"));
+        assert!(under_failed_dir(&failed, "logs/a/b.txt"));
+        assert!(!under_failed_dir(&failed, "logs2/a.txt"));
+        assert!(!under_failed_dir(&failed, "a.txt"));
+        assert!(under_failed_dir(&[String::new()], "anything"));
+    }
+
+    #[test]
+    fn both_present_mirrors_follow_direction() {
+        let p = profile("upload", false);
+        let planner = Planner { profile: &p };
+        assert_eq!(planner.both_present(None, true, true), (SyncAction::Upload, "differs"));
+        let p = profile("download", false);
+        let planner = Planner { profile: &p };
+        assert_eq!(planner.both_present(Some(&base(1, 1)), false, true), (SyncAction::Download, "modified"));
+    }
+
+    #[test]
+    fn both_present_two_way() {
+        let p = profile("both", false);
+        let planner = Planner { profile: &p };
+        let b = base(1, 1);
+        assert_eq!(planner.both_present(None, true, true), (SyncAction::Conflict, "differs"));
+        assert_eq!(planner.both_present(Some(&b), true, false), (SyncAction::Upload, "modified"));
+        assert_eq!(planner.both_present(Some(&b), false, true), (SyncAction::Download, "modified"));
+        assert_eq!(planner.both_present(Some(&b), true, true), (SyncAction::Conflict, "bothModified"));
+    }
+
+    #[test]
+    fn one_side_mirror() {
+        let state = FileState { size: 10, mtime: 100 };
+        let p = profile("upload", false);
+        let planner = Planner { profile: &p };
+        assert_eq!(planner.one_side(None, &state, true), Some((SyncAction::Upload, "new")));
+        assert_eq!(planner.one_side(Some(&base(10, 100)), &state, true), Some((SyncAction::Upload, "missing")));
+        assert_eq!(planner.one_side(None, &state, false), None);
+
+        let p = profile("upload", true);
+        let planner = Planner { profile: &p };
+        assert_eq!(planner.one_side(None, &state, false), Some((SyncAction::DeleteRemote, "extraneous")));
+    }
+
+    #[test]
+    fn one_side_two_way() {
+        let state = FileState { size: 10, mtime: 100 };
+        let p = profile("both", false);
+        let planner = Planner { profile: &p };
+        assert_eq!(planner.one_side(None, &state, false), Some((SyncAction::Download, "new")));
+        // 另一端删除但不传播删除：重新复制
+        assert_eq!(planner.one_side(Some(&base(10, 101)), &state, true), Some((SyncAction::Upload, "missing")));
+
+        let p = profile("both", true);
+        let planner = Planner { profile: &p };
+        assert_eq!(planner.one_side(Some(&base(10, 100)), &state, true), Some((SyncAction::DeleteLocal, "deleted")));
+        assert_eq!(planner.one_side(Some(&base(10, 100)), &state, false), Some((SyncAction::DeleteRemote, "deleted")));
+        assert_eq!(
+            planner.one_side(Some(&base(10, 50)), &state, true),
+            Some((SyncAction::Conflict, "modifiedAndDeleted"))
+        );
+    }
+}
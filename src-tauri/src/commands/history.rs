@@ -87,11 +87,48 @@ pub async fn record_command_history(
         return Ok(());
     }
 
+    let source_str = source.unwrap_or_else(|| "user".to_string());
+    insert_command_record(&state.db, &server_id, &command, &source_str).await
+}
+
+/// 命令的执行结果 (随审计事件写入)
+pub struct CommandOutcome<'a> {
+    pub status: &'a str, // success | failed | timeout
+    pub exit_code: Option<u32>,
+}
+
+/// 写入命令字典 / 单机统计 / 审计事件 (不做过滤，供片段执行等内部来源复用)
+pub async fn insert_command_record(
+    pool: &SqlitePool,
+    server_id: &str,
+    command: &str,
+    source: &str,
+) -> Result<(), String> {
+    insert_command_event(pool, server_id, command, source, None).await
+}
+
+/// 同 insert_command_record，并记录执行结果 (执行结束后调用)
+pub async fn insert_command_result(
+    pool: &SqlitePool,
+    server_id: &str,
+    command: &str,
+    source: &str,
+    outcome: &CommandOutcome<'_>,
+) -> Result<(), String> {
+    insert_command_event(pool, server_id, command, source, Some(outcome)).await
+}
+
+async fn insert_command_event(
+    pool: &SqlitePool,
+    server_id: &str,
+    command: &str,
+    source: &str,
+    outcome: Option<&CommandOutcome<'_>>,
+) -> Result<(), String> {
     let normalized = command.trim().to_string();
     let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
-    let source_str = source.unwrap_or_else(|| "user".to_string());
 
-    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
 
     // 1. 插入或更新 command_history (Global Dictionary)
     // 使用 ON CONFLICT 更新时间和全局计数
@@ -106,7 +143,7 @@ pub async fn record_command_history(
         "#
     )
     .bind(&normalized)
-    .bind(&command)
+    .bind(command)
     .bind(now)
     .bind(now)
     .fetch_one(&mut *tx)
@@ -126,7 +163,7 @@ pub async fn record_command_history(
         "#
     )
     .bind(history_id)
-    .bind(&server_id)
+    .bind(server_id)
     .bind(now)
     .bind(now)
     .execute(&mut *tx)
@@ -136,14 +173,16 @@ pub async fn record_command_history(
     // 3. 插入 command_events (Audit Log)
     sqlx::query(
         r#"
-        INSERT INTO command_events (command_id, server_id, source, executed_at)
-        VALUES (?, ?, ?, ?)
+        INSERT INTO command_events (command_id, server_id, source, executed_at, status, exit_code)
+        VALUES (?, ?, ?, ?, ?, ?)
         "#
     )
     .bind(history_id)
-    .bind(&server_id)
-    .bind(source_str)
+    .bind(server_id)
+    .bind(source)
     .bind(now)
+    .bind(outcome.map(|o| o.status))
+    .bind(outcome.and_then(|o| o.exit_code).map(i64::from))
     .execute(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
@@ -222,6 +261,8 @@ pub struct ServerHistoryDto {
     pub id: i64,          // 事件 ID (用于删除)
     pub command: String,  // 实际命令内容
     pub created_at: i64,  // 执行时间戳
+    pub status: Option<String>,  // 执行结果 (仅非交互来源有值)
+    pub exit_code: Option<i64>,
 }
 
 /// 获取特定服务器的命令历史 (时间倒序)
@@ -237,7 +278,9 @@ pub async fn get_command_history(
         SELECT 
             e.id as id, 
             h.display_command as command, 
-            e.executed_at as created_at
+            e.executed_at as created_at,
+            e.status as status,
+            e.exit_code as exit_code
         FROM command_events e
         JOIN command_history h ON e.command_id = h.id
         WHERE e.server_id = ?
@@ -5,6 +5,8 @@ pub mod ssh;
 pub mod vault;
 pub mod snippet;
 pub mod proxy;
+pub mod dialer;
 pub mod system;
 pub mod backup;
-pub mod history;
\ No newline at end of file
+pub mod history;
+pub mod transfer;
\ No newline at end of file
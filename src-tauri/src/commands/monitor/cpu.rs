@@ -1,6 +1,5 @@
 use super::MonitorCache;
-use crate::commands::ssh::SshState;
-use std::io::Read;
+use crate::commands::ssh::{exec, SshState};
 use tauri::State;
 
 #[derive(Clone, Copy, Debug)]
@@ -64,29 +63,16 @@ pub async fn get_ssh_cpu_info(
     monitor_cache: State<'_, MonitorCache>,
     id: String,
 ) -> Result<RemoteCpuInfo, String> {
-    let session_arc = {
-        let map = ssh_state.sessions.lock().unwrap();
-        map.get(&id).map(|c| c.monitor_session.clone()).ok_or("SSH not active")?
-    };
+    let handle = ssh_state.get_handle(&id)?;
 
-    let output = tauri::async_runtime::spawn_blocking(move || {
-        let sess_guard = session_arc.lock().unwrap();
-        let sess = sess_guard.as_ref().ok_or("Monitor session unavailable")?;
-        let mut channel = sess.channel_session().map_err(|e| e.to_string())?;
-        
-        // 🟢 组合指令：模型、逻辑数、物理数、负载、CPU 统计
-        let cmd = "grep 'model name' /proc/cpuinfo | head -1 | cut -d: -f2 && \
-                   echo '---SPLIT---' && grep -c '^processor' /proc/cpuinfo && \
-                   echo '---SPLIT---' && grep '^core id' /proc/cpuinfo | sort -u | wc -l && \
-                   echo '---SPLIT---' && cat /proc/loadavg && \
-                   echo '---SPLIT---' && cat /proc/stat | grep '^cpu'";
-        
-        channel.exec(cmd).map_err(|e| e.to_string())?;
-        let mut s = String::new();
-        channel.read_to_string(&mut s).ok();
-        channel.wait_close().ok();
-        Ok::<String, String>(s)
-    }).await.map_err(|e| e.to_string())??;
+    // 🟢 组合指令：模型、逻辑数、物理数、负载、CPU 统计
+    let cmd = "grep 'model name' /proc/cpuinfo | head -1 | cut -d: -f2 && \
+               echo '---SPLIT---' && grep -c '^processor' /proc/cpuinfo && \
+               echo '---SPLIT---' && grep '^core id' /proc/cpuinfo | sort -u | wc -l && \
+               echo '---SPLIT---' && cat /proc/loadavg && \
+               echo '---SPLIT---' && cat /proc/stat | grep '^cpu'";
+
+    let output = exec::run(&handle, &exec::ExecRequest::new(cmd)).await?.stdout;
 
     let parts: Vec<&str> = output.split("---SPLIT---").collect();
     if parts.len() < 5 { return Err("Invalid data format".into()); }
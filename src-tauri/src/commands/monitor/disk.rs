@@ -1,7 +1,6 @@
 // src-tauri/src/commands/monitor/disk.rs
 use super::MonitorCache;
-use crate::commands::ssh::SshState;
-use std::io::Read;
+use crate::commands::ssh::{exec, SshState};
 use std::time::Instant;
 use tauri::State;
 use serde::{Deserialize, Serialize};
@@ -95,28 +94,9 @@ pub async fn get_ssh_disk_info(
     monitor_cache: State<'_, MonitorCache>,
     id: String,
 ) -> Result<RemoteDiskInfo, String> {
-    let session_arc = {
-        let map = ssh_state.sessions.lock().unwrap();
-        match map.get(&id) {
-            Some(conn) => conn.monitor_session.clone(),
-            None => return Err("SSH connection not active".to_string()),
-        }
-    };
-
-    let output = tauri::async_runtime::spawn_blocking(move || {
-        let sess_guard = session_arc.lock().unwrap();
-        let sess = match &*sess_guard {
-            Some(s) => s,
-            None => return Err("Monitor session unavailable".to_string()),
-        };
-        let mut channel = sess.channel_session().map_err(|e| e.to_string())?;
-        let cmd = "lsblk -b -J -o NAME,SIZE,MOUNTPOINT,ROTA,RM,TYPE && echo '---SPLIT---' && df -B1 2>/dev/null && echo '---SPLIT---' && cat /proc/diskstats 2>/dev/null";
-        channel.exec(cmd).map_err(|e| e.to_string())?;
-        let mut s = String::new();
-        channel.read_to_string(&mut s).ok();
-        channel.wait_close().ok();
-        Ok::<String, String>(s)
-    }).await.map_err(|e| e.to_string())??;
+    let handle = ssh_state.get_handle(&id)?;
+    let cmd = "lsblk -b -J -o NAME,SIZE,MOUNTPOINT,ROTA,RM,TYPE && echo '---SPLIT---' && df -B1 2>/dev/null && echo '---SPLIT---' && cat /proc/diskstats 2>/dev/null";
+    let output = exec::run(&handle, &exec::ExecRequest::new(cmd)).await?.stdout;
 
     let parts: Vec<&str> = output.split("---SPLIT---").collect();
     if parts.len() < 3 { return Err("Invalid disk data".into()); }
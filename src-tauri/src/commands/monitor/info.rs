@@ -1,5 +1,4 @@
-use crate::commands::ssh::SshState;
-use std::io::Read;
+use crate::commands::ssh::{exec, SshState};
 use tauri::State;
 
 #[derive(serde::Serialize)]
@@ -17,36 +16,11 @@ pub async fn get_ssh_os_info(
     ssh_state: State<'_, SshState>,
     id: String,
 ) -> Result<RemoteOsInfo, String> {
-    let session_arc = {
-        let map = ssh_state.sessions.lock().unwrap();
-        match map.get(&id) {
-            Some(conn) => conn.monitor_session.clone(),
-            None => return Err("SSH connection not active".to_string()),
-        }
-    };
-
-    let output = tauri::async_runtime::spawn_blocking(move || {
-        let sess_guard = session_arc.lock().unwrap();
-        // [修复] 解包 Option
-        let sess = match &*sess_guard {
-            Some(s) => s,
-            None => return Err("Monitor session unavailable".to_string()),
-        };
+    let handle = ssh_state.get_handle(&id)?;
 
-        // [修复] 显式指定错误类型
-        let mut channel = sess.channel_session().map_err(|e: ssh2::Error| e.to_string())?;
-        
-        let cmd = "cat /proc/uptime && echo '---SPLIT---' && uname -r && echo '---SPLIT---' && uname -m && echo '---SPLIT---' && (grep PRETTY_NAME /etc/os-release || uname -o) && echo '---SPLIT---' && (cat /etc/timezone 2>/dev/null || date +%Z 2>/dev/null || echo 'Unknown')";
-        
-        channel.exec(cmd).map_err(|e: ssh2::Error| e.to_string())?;
+    let cmd = "cat /proc/uptime && echo '---SPLIT---' && uname -r && echo '---SPLIT---' && uname -m && echo '---SPLIT---' && (grep PRETTY_NAME /etc/os-release || uname -o) && echo '---SPLIT---' && (cat /etc/timezone 2>/dev/null || date +%Z 2>/dev/null || echo 'Unknown')";
 
-        let mut s = String::new();
-        channel.read_to_string(&mut s).map_err(|e: std::io::Error| e.to_string())?;
-        channel.wait_close().ok();
-        Ok::<String, String>(s)
-    })
-    .await
-    .map_err(|e| e.to_string())??;
+    let output = exec::run(&handle, &exec::ExecRequest::new(cmd)).await?.stdout;
 
     let parts: Vec<&str> = output.split("---SPLIT---").collect();
     if parts.len() < 5 {
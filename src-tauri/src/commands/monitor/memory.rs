@@ -1,6 +1,5 @@
 // src-tauri/src/commands/monitor/memory.rs
-use crate::commands::ssh::SshState;
-use std::io::Read;
+use crate::commands::ssh::{exec, SshState};
 use tauri::State;
 
 #[derive(serde::Serialize)]
@@ -23,31 +22,8 @@ pub async fn get_ssh_mem_info(
     ssh_state: State<'_, SshState>,
     id: String,
 ) -> Result<RemoteMemInfo, String> {
-    let session_arc = {
-        let map = ssh_state.sessions.lock().unwrap();
-        match map.get(&id) {
-            Some(conn) => conn.monitor_session.clone(),
-            None => return Err("SSH connection not active".to_string()),
-        }
-    };
-
-    let output = tauri::async_runtime::spawn_blocking(move || {
-        let sess_guard = session_arc.lock().unwrap();
-        let sess = match &*sess_guard {
-            Some(s) => s,
-            None => return Err("Monitor session unavailable".to_string()),
-        };
-
-        let mut channel = sess.channel_session().map_err(|e: ssh2::Error| e.to_string())?;
-        channel.exec("cat /proc/meminfo").map_err(|e: ssh2::Error| e.to_string())?;
-
-        let mut s = String::new();
-        channel.read_to_string(&mut s).map_err(|e: std::io::Error| e.to_string())?;
-        channel.wait_close().ok();
-        Ok::<String, String>(s)
-    })
-    .await
-    .map_err(|e| format!("{}", e))??;
+    let handle = ssh_state.get_handle(&id)?;
+    let output = exec::run(&handle, &exec::ExecRequest::new("cat /proc/meminfo")).await?.stdout;
 
     let (mut total, mut free, mut available, mut buffers, mut cached) = (0, 0, 0, 0, 0);
     let (mut s_total, mut s_free) = (0, 0);
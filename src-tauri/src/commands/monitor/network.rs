@@ -1,7 +1,6 @@
 // src-tauri/src/commands/monitor/network.rs
 use super::MonitorCache;
-use crate::commands::ssh::SshState;
-use std::io::Read;
+use crate::commands::ssh::{exec, SshState};
 use std::time::Instant;
 use std::collections::HashMap;
 use tauri::State;
@@ -44,37 +43,12 @@ pub async fn get_ssh_network_info(
     monitor_cache: State<'_, MonitorCache>,
     id: String,
 ) -> Result<RemoteNetworkInfo, String> {
-    let session_arc = {
-        let map = ssh_state.sessions.lock().unwrap();
-        match map.get(&id) {
-            Some(conn) => conn.monitor_session.clone(),
-            None => return Err("SSH connection not active".to_string()),
-        }
-    };
-
-    let output = tauri::async_runtime::spawn_blocking(move || {
-        let sess_guard = session_arc.lock().unwrap();
-        let sess = match &*sess_guard {
-            Some(s) => s,
-            None => return Err("Monitor session unavailable".to_string()),
-        };
-
-        let mut channel = sess
-            .channel_session()
-            .map_err(|e: ssh2::Error| e.to_string())?;
+    let handle = ssh_state.get_handle(&id)?;
 
-        // 🟢 指令组合：流量 + 地址/状态 + TCP 连接数
-        let cmd = "cat /proc/net/dev && echo '---SPLIT---' && ip addr && echo '---SPLIT---' && cat /proc/net/sockstat 2>/dev/null";
+    // 🟢 指令组合：流量 + 地址/状态 + TCP 连接数
+    let cmd = "cat /proc/net/dev && echo '---SPLIT---' && ip addr && echo '---SPLIT---' && cat /proc/net/sockstat 2>/dev/null";
 
-        channel.exec(cmd).map_err(|e: ssh2::Error| e.to_string())?;
-
-        let mut s = String::new();
-        channel.read_to_string(&mut s).ok();
-        channel.wait_close().ok();
-        Ok::<String, String>(s)
-    })
-    .await
-    .map_err(|e| e.to_string())??;
+    let output = exec::run(&handle, &exec::ExecRequest::new(cmd)).await?.stdout;
 
     let parts: Vec<&str> = output.split("---SPLIT---").collect();
     if parts.len() < 3 {
@@ -1,7 +1,20 @@
 use tauri::{command, State};
+use sqlx::{Pool, Sqlite};
 use crate::state::AppState;
 use crate::models::Proxy;
 
+// 🟢 内部函数：按 ID 读取代理 (SSH 拨号 / WebDAV 使用)
+pub async fn internal_get_proxy(pool: &Pool<Sqlite>, id: &str) -> Result<Proxy, String> {
+    sqlx::query_as::<_, Proxy>(
+        "SELECT id, name, proxy_type, host, port, username, password, created_at, updated_at FROM proxies WHERE id = ?"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Proxy {} not found", id))
+}
+
 #[command]
 pub async fn add_proxy(
     state: State<'_, AppState>,
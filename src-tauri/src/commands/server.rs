@@ -62,6 +62,8 @@ pub async fn list_servers(state: State<'_, AppState>) -> Result<Vec<ServerConfig
             keep_alive_interval: row.try_get("keep_alive_interval").ok(),
             auto_reconnect: row.try_get("auto_reconnect").ok(),
             max_reconnects: row.try_get("max_reconnects").ok(),
+            agent_forwarding: row.try_get("agent_forwarding").ok(),
+            x11_forwarding: row.try_get("x11_forwarding").ok(),
         });
     }
 
@@ -150,7 +152,8 @@ pub async fn save_server(
             password_id, password_source, key_id, key_source, private_key_remark,
             os, is_pinned, enable_expiration, expire_date,
             created_at, updated_at, last_connected_at,
-            connect_timeout, keep_alive_interval, auto_reconnect, max_reconnects
+            connect_timeout, keep_alive_interval, auto_reconnect, max_reconnects,
+            agent_forwarding, x11_forwarding
         ) VALUES (
             ?, ?, ?, ?, ?, ?, ?, ?, 
             ?, ?, ?, ?, 
@@ -158,7 +161,8 @@ pub async fn save_server(
             ?, ?, ?, ?, ?,
             ?, ?, ?, ?,
             ?, ?, ?,
-            ?, ?, ?, ?
+            ?, ?, ?, ?,
+            ?, ?
         )
         "#
     )
@@ -193,6 +197,8 @@ pub async fn save_server(
     .bind(server.keep_alive_interval)
     .bind(server.auto_reconnect)
     .bind(server.max_reconnects)
+    .bind(server.agent_forwarding)
+    .bind(server.x11_forwarding)
     .execute(pool)
     .await
     .map_err(|e| format!("保存服务器失败: {}", e))?;
@@ -206,11 +212,31 @@ pub async fn save_server(
 #[command]
 pub async fn delete_server(state: State<'_, AppState>, id: String) -> Result<(), String> {
     sqlx::query("DELETE FROM servers WHERE id = ?")
-        .bind(id)
+        .bind(&id)
         .execute(&state.db)
         .await
         .map_err(|e| format!("删除失败: {}", e))?;
-        
+
+    // 🟢 [新增] 一并清理该服务器的端口转发规则
+    sqlx::query("DELETE FROM port_forwards WHERE server_id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("删除失败: {}", e))?;
+
+    // 🟢 [新增] 以及文件夹同步配置与其基线
+    sqlx::query("DELETE FROM sync_state WHERE profile_id IN (SELECT id FROM sync_profiles WHERE server_id = ?)")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("删除失败: {}", e))?;
+
+    sqlx::query("DELETE FROM sync_profiles WHERE server_id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("删除失败: {}", e))?;
+
     Ok(())
 }
 
@@ -0,0 +1,479 @@
+// src-tauri/src/commands/snippet/bundle.rs
+// 片段导入导出
+//   导出: JSON 包 / 每个片段一个 .sh 文件 (注释形式的 front-matter 头)
+//   导入: 以上两种 + Termius 片段导出 (JSON) + SecureCRT 按钮栏 (ButtonBarV4.ini)
+//   合并: 先按 id、再按标题 (忽略大小写) 匹配已有片段，内容不同即为冲突，按 onConflict 处理
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+
+use crate::models::{Snippet, SnippetDto};
+use crate::state::AppState;
+
+use super::{insert_snippet_row, update_snippet_row};
+
+const BUNDLE_VERSION: u32 = 1;
+const FRONT_MATTER_FENCE: &str = "# ---";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnippetBundle {
+    version: u32,
+    exported_at: i64,
+    snippets: Vec<SnippetDto>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportConflict {
+    pub title: String,
+    pub existing_id: String,
+    /// id | title：与已有片段的匹配方式
+    pub matched_by: String,
+    /// skipped | overwritten | duplicated
+    pub resolution: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub format: String,
+    pub imported: usize,
+    pub updated: usize,
+    /// 与已有片段完全相同
+    pub unchanged: usize,
+    pub conflicts: Vec<ImportConflict>,
+    /// 无法解析的条目 (文件名 / 行号 + 原因)
+    pub errors: Vec<String>,
+}
+
+// ==============================================================================
+// 🟢 导出
+// ==============================================================================
+
+/// format: json (path 为文件) | sh (path 为目录)；ids 为空时导出全部
+#[tauri::command]
+pub async fn export_snippets(
+    state: State<'_, AppState>,
+    path: String,
+    format: String,
+    ids: Option<Vec<String>>,
+) -> Result<usize, String> {
+    let rows = sqlx::query_as::<_, Snippet>("SELECT * FROM snippets ORDER BY title")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    let snippets: Vec<SnippetDto> = rows
+        .into_iter()
+        .filter(|s| ids.as_ref().map_or(true, |ids| ids.contains(&s.id)))
+        .map(SnippetDto::from)
+        .collect();
+
+    match format.as_str() {
+        "json" => {
+            let bundle = SnippetBundle { version: BUNDLE_VERSION, exported_at: now_ms(), snippets };
+            let json = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+            tokio::fs::write(&path, json).await.map_err(|e| format!("Failed to write {}: {}", path, e))?;
+            Ok(bundle.snippets.len())
+        }
+        "sh" => {
+            tokio::fs::create_dir_all(&path).await.map_err(|e| e.to_string())?;
+            let mut used = HashSet::new();
+            for snippet in &snippets {
+                let file = Path::new(&path).join(unique_file_name(&snippet.title, &mut used));
+                tokio::fs::write(&file, to_script(snippet, true))
+                    .await
+                    .map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+            }
+            Ok(snippets.len())
+        }
+        other => Err(format!("Unsupported export format: {}", other)),
+    }
+}
+
+// # ---
+// # id: ...
+// # title: ...
+// # language: bash
+// # tags: a, b
+// # createdAt: 1700000000000
+// # updatedAt: 1700000000000
+// # ---
+// <code>
+// Git 同步不写时间戳，避免两端各自编辑同一片段时时间戳行必然冲突
+pub(crate) fn to_script(snippet: &SnippetDto, with_timestamps: bool) -> String {
+    let mut out = String::new();
+    out.push_str(FRONT_MATTER_FENCE);
+    out.push('\n');
+    let mut fields = vec![
+        ("id", snippet.id.clone()),
+        ("title", snippet.title.replace('\n', " ")),
+        ("language", snippet.language.clone()),
+        ("tags", snippet.tags.join(", ")),
+    ];
+    if with_timestamps {
+        fields.push(("createdAt", snippet.created_at.to_string()));
+        fields.push(("updatedAt", snippet.updated_at.to_string()));
+    }
+    for (key, value) in fields {
+        out.push_str(&format!("# {}: {}\n", key, value));
+    }
+    out.push_str(FRONT_MATTER_FENCE);
+    out.push('\n');
+    out.push_str(&snippet.code);
+    if !snippet.code.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+fn unique_file_name(title: &str, used: &mut HashSet<String>) -> String {
+    let slug: String = title
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let base = if slug.is_empty() { "snippet".to_string() } else { slug.to_lowercase() };
+
+    let mut name = format!("{}.sh", base);
+    let mut n = 2;
+    while !used.insert(name.clone()) {
+        name = format!("{}-{}.sh", base, n);
+        n += 1;
+    }
+    name
+}
+
+// ==============================================================================
+// 🟢 导入
+// ==============================================================================
+
+/// format 为空时按路径自动识别；on_conflict: skip (缺省) | overwrite | duplicate
+#[tauri::command]
+pub async fn import_snippets(
+    state: State<'_, AppState>,
+    path: String,
+    format: Option<String>,
+    on_conflict: Option<String>,
+) -> Result<ImportReport, String> {
+    let on_conflict = on_conflict.unwrap_or_else(|| "skip".to_string());
+    if !["skip", "overwrite", "duplicate"].contains(&on_conflict.as_str()) {
+        return Err(format!("Unknown conflict strategy: {}", on_conflict));
+    }
+    let path = PathBuf::from(&path);
+    let format = match format.filter(|f| !f.is_empty()) {
+        Some(f) => f,
+        None => detect_format(&path).await?,
+    };
+
+    let mut report = ImportReport { format: format.clone(), ..Default::default() };
+    let incoming = match format.as_str() {
+        "json" | "termius" => parse_json(&read_text(&path).await?, &mut report)?,
+        "sh" => parse_script_dir(&path, &mut report).await?,
+        "securecrt" => parse_securecrt(&read_text(&path).await?, &mut report),
+        other => return Err(format!("Unsupported import format: {}", other)),
+    };
+
+    let existing: Vec<SnippetDto> = sqlx::query_as::<_, Snippet>("SELECT * FROM snippets")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(SnippetDto::from)
+        .collect();
+    merge(&state.db, existing, incoming, &on_conflict, &mut report).await?;
+    Ok(report)
+}
+
+async fn merge(
+    pool: &sqlx::SqlitePool,
+    mut existing: Vec<SnippetDto>,
+    incoming: Vec<SnippetDto>,
+    on_conflict: &str,
+    report: &mut ImportReport,
+) -> Result<(), String> {
+    for mut snippet in incoming {
+        let matched = existing
+            .iter()
+            .position(|e| e.id == snippet.id)
+            .map(|i| (i, "id"))
+            .or_else(|| {
+                existing
+                    .iter()
+                    .position(|e| e.title.trim().eq_ignore_ascii_case(snippet.title.trim()))
+                    .map(|i| (i, "title"))
+            });
+
+        let Some((index, matched_by)) = matched else {
+            insert_snippet_row(pool, &snippet).await?;
+            report.imported += 1;
+            existing.push(snippet);
+            continue;
+        };
+
+        let current = &existing[index];
+        if current.code == snippet.code && current.language == snippet.language && same_tags(&current.tags, &snippet.tags) {
+            report.unchanged += 1;
+            continue;
+        }
+
+        let mut conflict = ImportConflict {
+            title: snippet.title.clone(),
+            existing_id: current.id.clone(),
+            matched_by: matched_by.to_string(),
+            resolution: String::new(),
+        };
+        match on_conflict {
+            "overwrite" => {
+                snippet.id = current.id.clone();
+                snippet.created_at = current.created_at;
+                snippet.updated_at = now_ms();
+                update_snippet_row(pool, &snippet).await?;
+                report.updated += 1;
+                conflict.resolution = "overwritten".to_string();
+                existing[index] = snippet;
+            }
+            "duplicate" => {
+                snippet.id = uuid::Uuid::new_v4().to_string();
+                snippet.title = format!("{} (imported)", snippet.title);
+                insert_snippet_row(pool, &snippet).await?;
+                report.imported += 1;
+                conflict.resolution = "duplicated".to_string();
+                existing.push(snippet);
+            }
+            _ => conflict.resolution = "skipped".to_string(),
+        }
+        report.conflicts.push(conflict);
+    }
+    Ok(())
+}
+
+fn same_tags(a: &[String], b: &[String]) -> bool {
+    let norm = |tags: &[String]| tags.iter().map(|t| t.to_lowercase()).collect::<HashSet<_>>();
+    norm(a) == norm(b)
+}
+
+async fn detect_format(path: &Path) -> Result<String, String> {
+    let meta = tokio::fs::metadata(path).await.map_err(|e| format!("{}: {}", path.display(), e))?;
+    if meta.is_dir() {
+        return Ok("sh".to_string());
+    }
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    match ext.as_str() {
+        "json" => Ok("json".to_string()),
+        "ini" => Ok("securecrt".to_string()),
+        _ => Err("Cannot detect import format, please choose one explicitly".to_string()),
+    }
+}
+
+async fn read_text(path: &Path) -> Result<String, String> {
+    let bytes = tokio::fs::read(path).await.map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    // SecureCRT 在 Windows 上可能写出 UTF-8 BOM
+    let text = String::from_utf8_lossy(&bytes).to_string();
+    Ok(text.trim_start_matches('\u{feff}').to_string())
+}
+
+// --- JSON：本应用的导出包，或 Termius 的片段导出 ---
+// Termius 导出为片段数组或 { "snippets": [...] }，字段为 label / script (部分版本带 tags)
+fn parse_json(text: &str, report: &mut ImportReport) -> Result<Vec<SnippetDto>, String> {
+    let root: Value = serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let items = match &root {
+        Value::Array(items) => items.clone(),
+        Value::Object(obj) => match obj.get("snippets") {
+            Some(Value::Array(items)) => items.clone(),
+            _ => return Err("JSON does not contain a snippet list".to_string()),
+        },
+        _ => return Err("JSON does not contain a snippet list".to_string()),
+    };
+
+    let now = now_ms();
+    let mut snippets = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        let str_field = |keys: &[&str]| {
+            keys.iter().find_map(|k| item.get(*k).and_then(Value::as_str)).map(str::to_string)
+        };
+        let code = str_field(&["code", "script", "content", "command"]);
+        let title = str_field(&["title", "label", "name"]);
+        let (Some(code), Some(title)) = (code, title) else {
+            report.errors.push(format!("#{}: missing title or code", i + 1));
+            continue;
+        };
+
+        // Termius 的片段没有 code 字段，以此区分来源
+        let from_termius = item.get("code").is_none();
+        let mut tags: Vec<String> = item
+            .get("tags")
+            .and_then(Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|t| t.as_str().or_else(|| t.get("label").and_then(Value::as_str)))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if from_termius {
+            report.format = "termius".to_string();
+            if !tags.iter().any(|t| t == "termius") {
+                tags.push("termius".to_string());
+            }
+        }
+
+        snippets.push(SnippetDto {
+            id: str_field(&["id"]).unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            title,
+            code,
+            language: str_field(&["language"]).unwrap_or_else(|| "bash".to_string()),
+            tags,
+            created_at: item.get("createdAt").and_then(Value::as_i64).unwrap_or(now),
+            updated_at: item.get("updatedAt").and_then(Value::as_i64).unwrap_or(now),
+        });
+    }
+    Ok(snippets)
+}
+
+// --- .sh 目录：front-matter 缺失时以文件名为标题、整个文件为代码 ---
+async fn parse_script_dir(dir: &Path, report: &mut ImportReport) -> Result<Vec<SnippetDto>, String> {
+    let mut entries = tokio::fs::read_dir(dir).await.map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let mut files = Vec::new();
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("sh") {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    let mut snippets = Vec::new();
+    for file in files {
+        let name = file.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_string();
+        match read_text(&file).await {
+            Ok(text) => snippets.push(parse_script(&text, file.file_stem().and_then(|s| s.to_str()).unwrap_or(&name))),
+            Err(e) => report.errors.push(format!("{}: {}", name, e)),
+        }
+    }
+    Ok(snippets)
+}
+
+pub(crate) fn parse_script(text: &str, fallback_title: &str) -> SnippetDto {
+    let now = now_ms();
+    let mut snippet = SnippetDto {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: fallback_title.to_string(),
+        code: text.to_string(),
+        language: "bash".to_string(),
+        tags: Vec::new(),
+        created_at: now,
+        updated_at: now,
+    };
+
+    // front-matter 必须从首行开始，其后的内容 (包括 shebang) 原样作为代码
+    let mut lines = text.split_inclusive('\n');
+    let Some(first) = lines.next().filter(|l| l.trim_end() == FRONT_MATTER_FENCE) else {
+        return snippet;
+    };
+    let mut offset = first.len();
+    for line in lines {
+        offset += line.len();
+        let line = line.trim_end();
+        if line == FRONT_MATTER_FENCE {
+            snippet.code = text[offset..].to_string();
+            break;
+        }
+        let Some((key, value)) = line.trim_start_matches('#').split_once(':') else { continue };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "id" if !value.is_empty() => snippet.id = value,
+            "title" if !value.is_empty() => snippet.title = value,
+            "language" if !value.is_empty() => snippet.language = value,
+            "tags" => {
+                snippet.tags = value.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect();
+            }
+            "createdAt" => snippet.created_at = value.parse().unwrap_or(now),
+            "updatedAt" => snippet.updated_at = value.parse().unwrap_or(now),
+            _ => {}
+        }
+    }
+    snippet
+}
+
+// --- SecureCRT 按钮栏 (Config/ButtonBarV4.ini) ---
+//   Z:"Default"=00000002
+//    02,List files,ls -la\r
+//    02,Disk usage,df -h\r
+// 每个按钮一行：功能码,标签,参数；只导入 02 (Send String)，\r \n \t \\ 按转义处理，按钮栏名作为标签
+fn parse_securecrt(text: &str, report: &mut ImportReport) -> Vec<SnippetDto> {
+    let now = now_ms();
+    let mut snippets = Vec::new();
+    let mut bar: Option<String> = None;
+
+    for (i, raw) in text.lines().enumerate() {
+        if let Some(rest) = raw.strip_prefix("Z:\"") {
+            bar = rest.split_once('"').map(|(name, _)| name.to_string());
+            continue;
+        }
+        let line = raw.trim();
+        if line.is_empty() || bar.is_none() {
+            continue;
+        }
+        let mut fields = line.splitn(3, ',');
+        let (Some(function), Some(label), Some(arg)) = (fields.next(), fields.next(), fields.next()) else {
+            report.errors.push(format!("line {}: unrecognized button definition", i + 1));
+            continue;
+        };
+        if u32::from_str_radix(function.trim(), 16) != Ok(2) {
+            continue;
+        }
+
+        let code = unescape_securecrt(arg);
+        let code = code.trim_end_matches(['\r', '\n']).replace('\r', "\n");
+        if code.trim().is_empty() {
+            continue;
+        }
+        let mut tags = vec!["securecrt".to_string()];
+        if let Some(bar) = bar.as_ref().filter(|b| !b.is_empty()) {
+            tags.push(bar.clone());
+        }
+        snippets.push(SnippetDto {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: label.trim().to_string(),
+            code,
+            language: "bash".to_string(),
+            tags,
+            created_at: now,
+            updated_at: now,
+        });
+    }
+    snippets
+}
+
+fn unescape_securecrt(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('r') => out.push('\r'),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+pub(crate) fn now_ms() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64
+}
@@ -0,0 +1,697 @@
+// src-tauri/src/commands/snippet/git_sync.rs
+// 片段 Git 同步：snippets 表 <-> 本地工作副本 snippets/<id>.sh (格式同 .sh 导出，不含时间戳)
+//   1. 把数据库当前状态写入工作副本并提交
+//   2. fetch 后 merge 远端分支，由 git 做逐行三方合并
+//   3. 合并成功：工作副本回写数据库 (增 / 改 / 删)，再推送
+//   4. 有冲突：保留进行中的 merge，返回双方版本，逐个选择后完成合并
+// 依赖系统 git 命令；凭证由用户的 git 配置 (ssh-agent / credential helper) 提供
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+use tokio::process::Command;
+
+use crate::models::{Snippet, SnippetDto, SnippetSyncConfig};
+use crate::state::AppState;
+
+use super::bundle::{now_ms, parse_script, to_script};
+use super::{insert_snippet_row, update_snippet_row};
+
+const SNIPPET_DIR: &str = "snippets";
+const COMMIT_NAME: &str = "iShell";
+const COMMIT_EMAIL: &str = "ishell@localhost";
+
+// 同一时间只允许一次同步操作 (工作副本是共享的)
+static SYNC_LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    pub snippet_id: String,
+    pub path: String,
+    /// 本地版本 / 远端版本；为空表示该侧已删除
+    pub local: Option<SnippetDto>,
+    pub remote: Option<SnippetDto>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetSyncReport {
+    pub status: String, // synced | conflicts
+    /// 远端变更回写数据库的数量
+    pub added: usize,
+    pub updated: usize,
+    pub deleted: usize,
+    pub pushed: bool,
+    pub commit: Option<String>,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+// ==============================================================================
+// 🟢 配置
+// ==============================================================================
+
+#[tauri::command]
+pub async fn get_snippet_sync_config(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SnippetSyncConfig, String> {
+    load_config(&app, &state.db).await
+}
+
+#[tauri::command]
+pub async fn save_snippet_sync_config(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    config: SnippetSyncConfig,
+) -> Result<SnippetSyncConfig, String> {
+    let previous = load_config(&app, &state.db).await?;
+    let mut config = SnippetSyncConfig {
+        repo_path: config.repo_path.trim().to_string(),
+        remote_url: config.remote_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty()),
+        branch: config.branch.trim().to_string(),
+        last_commit: None,
+        last_synced_at: None,
+    };
+    if config.repo_path.is_empty() {
+        config.repo_path = default_repo_path(&app)?;
+    }
+    if config.branch.is_empty() {
+        config.branch = "main".to_string();
+    }
+    // 仓库位置不变时保留同步记录
+    if config.repo_path == previous.repo_path && config.branch == previous.branch {
+        config.last_commit = previous.last_commit;
+        config.last_synced_at = previous.last_synced_at;
+    }
+
+    sqlx::query(
+        "INSERT OR REPLACE INTO snippet_sync (id, repo_path, remote_url, branch, last_commit, last_synced_at)
+         VALUES (1, ?, ?, ?, ?, ?)",
+    )
+    .bind(&config.repo_path)
+    .bind(&config.remote_url)
+    .bind(&config.branch)
+    .bind(&config.last_commit)
+    .bind(config.last_synced_at)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+async fn load_config(app: &AppHandle, pool: &sqlx::SqlitePool) -> Result<SnippetSyncConfig, String> {
+    let config = sqlx::query_as::<_, SnippetSyncConfig>(
+        "SELECT repo_path, remote_url, branch, last_commit, last_synced_at FROM snippet_sync WHERE id = 1",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    match config {
+        Some(c) => Ok(c),
+        None => Ok(SnippetSyncConfig {
+            repo_path: default_repo_path(app)?,
+            remote_url: None,
+            branch: "main".to_string(),
+            last_commit: None,
+            last_synced_at: None,
+        }),
+    }
+}
+
+fn default_repo_path(app: &AppHandle) -> Result<String, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join("snippet-sync").to_string_lossy().to_string())
+}
+
+// ==============================================================================
+// 🟢 同步 / 冲突处理
+// ==============================================================================
+
+#[tauri::command]
+pub async fn sync_snippets_git(app: AppHandle, state: State<'_, AppState>) -> Result<SnippetSyncReport, String> {
+    let _guard = SYNC_LOCK.get_or_init(Default::default).lock().await;
+    let config = load_config(&app, &state.db).await?;
+    sync_repo(&state.db, &config).await
+}
+
+/// choice: local | remote；全部冲突处理完后自动完成合并、回写数据库并推送
+#[tauri::command]
+pub async fn resolve_snippet_sync_conflict(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    snippet_id: String,
+    choice: String,
+) -> Result<SnippetSyncReport, String> {
+    let _guard = SYNC_LOCK.get_or_init(Default::default).lock().await;
+    let config = load_config(&app, &state.db).await?;
+    resolve_conflict(&state.db, &config, &snippet_id, &choice).await
+}
+
+/// 放弃进行中的合并，数据库保持不变
+#[tauri::command]
+pub async fn abort_snippet_sync(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let _guard = SYNC_LOCK.get_or_init(Default::default).lock().await;
+    let config = load_config(&app, &state.db).await?;
+    let repo = PathBuf::from(&config.repo_path);
+    if merge_in_progress(&repo).await {
+        git(&repo, &["merge", "--abort"]).await?;
+    }
+    Ok(())
+}
+
+// 调用方需持有 SYNC_LOCK
+async fn sync_repo(pool: &sqlx::SqlitePool, config: &SnippetSyncConfig) -> Result<SnippetSyncReport, String> {
+    let repo = PathBuf::from(&config.repo_path);
+
+    prepare_repo(&repo, config).await?;
+    if merge_in_progress(&repo).await {
+        return conflict_report(&repo).await;
+    }
+
+    // 1. 数据库 -> 工作副本
+    write_snapshot(pool, &repo).await?;
+    // 工作副本专用于同步，直接暂存全部改动 (空目录时按路径 add 会报 pathspec 不匹配)
+    git(&repo, &["add", "-A"]).await?;
+    if !git(&repo, &["status", "--porcelain"]).await?.trim().is_empty() {
+        let message = format!("Update snippets ({})", chrono::Local::now().format("%Y-%m-%d %H:%M"));
+        git(&repo, &["commit", "-q", "-m", &message]).await?;
+    }
+    let baseline = head_commit(&repo).await;
+
+    // 2. 合并远端
+    if config.remote_url.is_some() {
+        git(&repo, &["fetch", "-q", "origin"]).await?;
+        let remote_ref = format!("refs/remotes/origin/{}", config.branch);
+        if git(&repo, &["rev-parse", "-q", "--verify", &remote_ref]).await.is_ok() {
+            // 首次同步时本地与远端历史无关，需允许合并
+            let merged = git(
+                &repo,
+                &["merge", "--no-edit", "--allow-unrelated-histories", "-m", "Merge remote snippets", &remote_ref],
+            )
+            .await;
+            if merged.is_err() {
+                if merge_in_progress(&repo).await {
+                    return conflict_report(&repo).await;
+                }
+                merged?;
+            }
+        }
+    }
+
+    finish_sync(pool, &repo, config, baseline.as_deref()).await
+}
+
+async fn resolve_conflict(
+    pool: &sqlx::SqlitePool,
+    config: &SnippetSyncConfig,
+    snippet_id: &str,
+    choice: &str,
+) -> Result<SnippetSyncReport, String> {
+    let repo = PathBuf::from(&config.repo_path);
+    if !merge_in_progress(&repo).await {
+        return Err("No snippet sync conflicts to resolve".to_string());
+    }
+
+    // 冲突文件名未必等于片段 ID (远端手工添加的文件)，按冲突列表定位
+    let path = conflict_report(&repo)
+        .await?
+        .conflicts
+        .into_iter()
+        .find(|c| c.snippet_id == snippet_id)
+        .map(|c| c.path)
+        .ok_or("This snippet has no pending conflict")?;
+    let stage = match choice {
+        "local" => 2,
+        "remote" => 3,
+        other => return Err(format!("Unknown resolution: {}", other)),
+    };
+    match git(&repo, &["show", &format!(":{}:{}", stage, path)]).await {
+        Ok(content) => {
+            tokio::fs::write(repo.join(&path), content).await.map_err(|e| e.to_string())?;
+            git(&repo, &["add", "--", &path]).await?;
+        }
+        // 所选一侧已删除该片段
+        Err(_) => {
+            git(&repo, &["rm", "-q", "--ignore-unmatch", "--", &path]).await?;
+        }
+    }
+
+    if !unmerged_paths(&repo).await?.is_empty() {
+        return conflict_report(&repo).await;
+    }
+    // 合并中 HEAD 仍是冲突那次同步写入的本地快照
+    let baseline = head_commit(&repo).await;
+    git(&repo, &["commit", "-q", "--no-edit"]).await?;
+    finish_sync(pool, &repo, config, baseline.as_deref()).await
+}
+
+// 合并完成后：工作副本 -> 数据库，推送，记录同步点
+// baseline 为合并前数据库写入工作副本的提交，用于识别之后才在本地发生的修改
+async fn finish_sync(
+    pool: &sqlx::SqlitePool,
+    repo: &Path,
+    config: &SnippetSyncConfig,
+    baseline: Option<&str>,
+) -> Result<SnippetSyncReport, String> {
+    let mut report = apply_snapshot(pool, repo, baseline).await?;
+    report.status = "synced".to_string();
+
+    if config.remote_url.is_some() {
+        let refspec = format!("HEAD:refs/heads/{}", config.branch);
+        git(repo, &["push", "-q", "origin", &refspec]).await?;
+        report.pushed = true;
+    }
+
+    let commit = git(repo, &["rev-parse", "HEAD"]).await.ok().map(|c| c.trim().to_string());
+    sqlx::query("UPDATE snippet_sync SET last_commit = ?, last_synced_at = ? WHERE id = 1")
+        .bind(&commit)
+        .bind(now_ms())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    report.commit = commit;
+    Ok(report)
+}
+
+// ==============================================================================
+// 🟢 工作副本 <-> 数据库
+// ==============================================================================
+
+async fn prepare_repo(repo: &Path, config: &SnippetSyncConfig) -> Result<(), String> {
+    tokio::fs::create_dir_all(repo.join(SNIPPET_DIR))
+        .await
+        .map_err(|e| format!("Cannot create {}: {}", repo.display(), e))?;
+
+    if !repo.join(".git").exists() {
+        git(repo, &["init", "-q"]).await?;
+        // 未提交前 HEAD 指向的分支即首次提交所在分支
+        git(repo, &["symbolic-ref", "HEAD", &format!("refs/heads/{}", config.branch)]).await?;
+    }
+    // 未配置身份时提交会失败，仅对该仓库设置
+    if git(repo, &["config", "user.email"]).await.map_or(true, |v| v.trim().is_empty()) {
+        git(repo, &["config", "user.name", COMMIT_NAME]).await?;
+        git(repo, &["config", "user.email", COMMIT_EMAIL]).await?;
+    }
+
+    let current_remote = git(repo, &["remote", "get-url", "origin"]).await.ok().map(|u| u.trim().to_string());
+    match (&config.remote_url, current_remote) {
+        (Some(url), None) => {
+            git(repo, &["remote", "add", "origin", url]).await?;
+        }
+        (Some(url), Some(current)) if *url != current => {
+            git(repo, &["remote", "set-url", "origin", url]).await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn snippet_path(id: &str) -> String {
+    let safe: String = id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!("{}/{}.sh", SNIPPET_DIR, safe)
+}
+
+// 数据库全部片段写入 snippets/，并删除数据库中已不存在的文件
+async fn write_snapshot(pool: &sqlx::SqlitePool, repo: &Path) -> Result<(), String> {
+    let snippets: Vec<SnippetDto> = sqlx::query_as::<_, Snippet>("SELECT * FROM snippets")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(SnippetDto::from)
+        .collect();
+
+    let mut expected = HashSet::new();
+    for snippet in &snippets {
+        let path = snippet_path(&snippet.id);
+        tokio::fs::write(repo.join(&path), to_script(snippet, false))
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        expected.insert(path);
+    }
+
+    for path in list_snippet_files(repo).await? {
+        if !expected.contains(&path) {
+            let _ = tokio::fs::remove_file(repo.join(&path)).await;
+        }
+    }
+    Ok(())
+}
+
+// 工作副本为准更新数据库 (只写有差异的片段，修订历史由触发器记录)
+// 只覆盖 / 删除自 baseline 快照以来未在本地改动的行：冲突挂起期间新增或编辑的片段保留在数据库，
+// 下次同步时再写入工作副本提交
+async fn apply_snapshot(
+    pool: &sqlx::SqlitePool,
+    repo: &Path,
+    baseline: Option<&str>,
+) -> Result<SnippetSyncReport, String> {
+    let mut existing: HashMap<String, SnippetDto> = sqlx::query_as::<_, Snippet>("SELECT * FROM snippets")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|s| (s.id.clone(), SnippetDto::from(s)))
+        .collect();
+
+    let mut report = SnippetSyncReport::default();
+    for path in list_snippet_files(repo).await? {
+        let text = tokio::fs::read_to_string(repo.join(&path)).await.map_err(|e| format!("{}: {}", path, e))?;
+        let stem = Path::new(&path).file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+        let mut snippet = parse_script(&text, &stem);
+
+        match existing.remove(&snippet.id) {
+            None => {
+                // 快照中有而数据库中没有：快照之后在本地删除，不要复活
+                if snapshot_at(repo, baseline, &path).await.is_some() {
+                    continue;
+                }
+                insert_snippet_row(pool, &snippet).await?;
+                report.added += 1;
+            }
+            Some(current) => {
+                if !same_content(&current, &snippet) && unchanged_since(repo, baseline, &path, &current).await {
+                    snippet.created_at = current.created_at;
+                    snippet.updated_at = now_ms();
+                    update_snippet_row(pool, &snippet).await?;
+                    report.updated += 1;
+                }
+            }
+        }
+    }
+
+    // 工作副本中已不存在的片段 (远端删除)
+    for (id, current) in &existing {
+        if !unchanged_since(repo, baseline, &snippet_path(id), current).await {
+            continue;
+        }
+        sqlx::query("DELETE FROM snippets WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        report.deleted += 1;
+    }
+    Ok(report)
+}
+
+fn same_content(a: &SnippetDto, b: &SnippetDto) -> bool {
+    a.title == b.title && a.code == b.code && a.language == b.language && a.tags == b.tags
+}
+
+async fn head_commit(repo: &Path) -> Option<String> {
+    git(repo, &["rev-parse", "-q", "--verify", "HEAD"]).await.ok().map(|c| c.trim().to_string())
+}
+
+// baseline 提交中 path 对应的片段；没有 baseline 或文件不存在时为 None
+async fn snapshot_at(repo: &Path, baseline: Option<&str>, path: &str) -> Option<SnippetDto> {
+    let text = git(repo, &["show", &format!("{}:{}", baseline?, path)]).await.ok()?;
+    let stem = Path::new(path).file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    Some(parse_script(&text, stem))
+}
+
+// 数据库中的行与 baseline 快照一致 (快照之后未在本地修改)
+async fn unchanged_since(repo: &Path, baseline: Option<&str>, path: &str, current: &SnippetDto) -> bool {
+    snapshot_at(repo, baseline, path).await.is_some_and(|s| same_content(&s, current))
+}
+
+async fn list_snippet_files(repo: &Path) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    let mut entries = match tokio::fs::read_dir(repo.join(SNIPPET_DIR)).await {
+        Ok(e) => e,
+        Err(_) => return Ok(files),
+    };
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.ends_with(".sh") {
+            files.push(format!("{}/{}", SNIPPET_DIR, name));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// ==============================================================================
+// 🟢 冲突
+// ==============================================================================
+
+async fn merge_in_progress(repo: &Path) -> bool {
+    git(repo, &["rev-parse", "-q", "--verify", "MERGE_HEAD"]).await.is_ok()
+}
+
+async fn unmerged_paths(repo: &Path) -> Result<Vec<String>, String> {
+    let out = git(repo, &["diff", "--name-only", "--diff-filter=U"]).await?;
+    Ok(out.lines().map(str::to_string).filter(|l| !l.is_empty()).collect())
+}
+
+async fn conflict_report(repo: &Path) -> Result<SnippetSyncReport, String> {
+    let mut conflicts = Vec::new();
+    for path in unmerged_paths(repo).await? {
+        let stem = Path::new(&path).file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+        // :2 = 本地 (ours)，:3 = 远端 (theirs)；缺失表示该侧删除了片段
+        let local = git(repo, &["show", &format!(":2:{}", path)]).await.ok().map(|t| parse_script(&t, &stem));
+        let remote = git(repo, &["show", &format!(":3:{}", path)]).await.ok().map(|t| parse_script(&t, &stem));
+        let snippet_id = local.as_ref().or(remote.as_ref()).map(|s| s.id.clone()).unwrap_or(stem);
+        conflicts.push(SyncConflict { snippet_id, path, local, remote });
+    }
+    Ok(SnippetSyncReport { status: "conflicts".to_string(), conflicts, ..Default::default() })
+}
+
+async fn git(repo: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        // 需要交互输入凭证时直接失败，而不是挂起
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git (is it installed?): {}", e))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let detail = if stderr.is_empty() { stdout } else { stderr };
+        Err(format!("git {}: {}", args.first().copied().unwrap_or_default(), detail))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tempfile::TempDir;
+
+    // 一个 "设备"：独立的数据库 + 工作副本，共享同一个裸仓库作为远端
+    struct Device {
+        pool: sqlx::SqlitePool,
+        config: SnippetSyncConfig,
+    }
+
+    impl Device {
+        async fn new(root: &Path, name: &str, remote: &Path) -> Self {
+            // 内存库每个连接各自独立，只能用单连接
+            let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+            crate::db::migrate(&pool).await.unwrap();
+            let config = SnippetSyncConfig {
+                repo_path: root.join(name).to_string_lossy().to_string(),
+                remote_url: Some(remote.to_string_lossy().to_string()),
+                branch: "main".to_string(),
+                last_commit: None,
+                last_synced_at: None,
+            };
+            Device { pool, config }
+        }
+
+        async fn sync(&self) -> SnippetSyncReport {
+            sync_repo(&self.pool, &self.config).await.unwrap()
+        }
+
+        async fn code_of(&self, id: &str) -> Option<String> {
+            sqlx::query_scalar("SELECT code FROM snippets WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .unwrap()
+        }
+
+        async fn set_code(&self, id: &str, code: &str) {
+            let mut s = snippet(id, code);
+            s.updated_at = now_ms();
+            update_snippet_row(&self.pool, &s).await.unwrap();
+        }
+    }
+
+    // 代码以换行结尾，与工作副本中的格式一致，避免回写时产生多余的更新
+    fn snippet(id: &str, code: &str) -> SnippetDto {
+        SnippetDto {
+            id: id.to_string(),
+            title: format!("Snippet {}", id),
+            code: code.to_string(),
+            language: "bash".to_string(),
+            tags: vec!["ops".to_string()],
+            created_at: 1,
+            updated_at: 1,
+        }
+    }
+
+    async fn remote(root: &Path) -> PathBuf {
+        let bare = root.join("remote.git");
+        std::fs::create_dir_all(&bare).unwrap();
+        git(&bare, &["init", "-q", "--bare"]).await.unwrap();
+        bare
+    }
+
+    async fn setup() -> (TempDir, Device, Device) {
+        let root = tempfile::tempdir().unwrap();
+        let bare = remote(root.path()).await;
+        let a = Device::new(root.path(), "a", &bare).await;
+        let b = Device::new(root.path(), "b", &bare).await;
+        (root, a, b)
+    }
+
+    #[tokio::test]
+    async fn independent_changes_merge_cleanly() {
+        let (_root, a, b) = setup().await;
+
+        insert_snippet_row(&a.pool, &snippet("one", "echo one\n")).await.unwrap();
+        let report = a.sync().await;
+        assert_eq!(report.status, "synced");
+        assert!(report.pushed);
+        assert_eq!((report.added, report.updated, report.deleted), (0, 0, 0));
+
+        // 首次同步：本地与远端历史无关，合并后取得对方的片段
+        insert_snippet_row(&b.pool, &snippet("two", "echo two\n")).await.unwrap();
+        let report = b.sync().await;
+        assert_eq!(report.status, "synced");
+        assert_eq!(report.added, 1);
+        assert_eq!(b.code_of("one").await.as_deref(), Some("echo one\n"));
+
+        // 双方都有新提交，需要真正的三方合并
+        insert_snippet_row(&a.pool, &snippet("three", "echo three\n")).await.unwrap();
+        let report = a.sync().await;
+        assert_eq!(report.status, "synced");
+        assert_eq!((report.added, report.updated, report.deleted), (1, 0, 0));
+        assert_eq!(a.code_of("two").await.as_deref(), Some("echo two\n"));
+
+        let report = b.sync().await;
+        assert_eq!(report.added, 1);
+        assert_eq!(b.code_of("three").await.as_deref(), Some("echo three\n"));
+        // 两端最终停在同一提交
+        assert_eq!(report.commit, a.sync().await.commit);
+    }
+
+    #[tokio::test]
+    async fn remote_edits_and_deletions_are_applied_to_the_database() {
+        let (_root, a, b) = setup().await;
+
+        insert_snippet_row(&a.pool, &snippet("edit", "echo v1\n")).await.unwrap();
+        insert_snippet_row(&a.pool, &snippet("drop", "echo bye\n")).await.unwrap();
+        a.sync().await;
+        assert_eq!(b.sync().await.added, 2);
+
+        b.set_code("edit", "echo v2\n").await;
+        sqlx::query("DELETE FROM snippets WHERE id = 'drop'").execute(&b.pool).await.unwrap();
+        b.sync().await;
+
+        let report = a.sync().await;
+        assert_eq!(report.status, "synced");
+        assert_eq!((report.added, report.updated, report.deleted), (0, 1, 1));
+        assert_eq!(a.code_of("edit").await.as_deref(), Some("echo v2\n"));
+        assert_eq!(a.code_of("drop").await, None);
+
+        // 回写走 update_snippet_row，修订历史由触发器记录
+        let revisions: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM snippet_revisions WHERE snippet_id = 'edit'")
+            .fetch_one(&a.pool)
+            .await
+            .unwrap();
+        assert_eq!(revisions, 2);
+    }
+
+    #[tokio::test]
+    async fn conflicting_edits_are_surfaced_and_resolved() {
+        let (_root, a, b) = setup().await;
+
+        insert_snippet_row(&a.pool, &snippet("shared", "echo base\n")).await.unwrap();
+        a.sync().await;
+        b.sync().await;
+
+        b.set_code("shared", "echo remote\n").await;
+        b.sync().await;
+        a.set_code("shared", "echo local\n").await;
+
+        let report = a.sync().await;
+        assert_eq!(report.status, "conflicts");
+        assert!(!report.pushed);
+        assert_eq!(report.conflicts.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.snippet_id, "shared");
+        assert_eq!(conflict.path, "snippets/shared.sh");
+        assert_eq!(conflict.local.as_ref().map(|s| s.code.as_str()), Some("echo local\n"));
+        assert_eq!(conflict.remote.as_ref().map(|s| s.code.as_str()), Some("echo remote\n"));
+
+        // 冲突未解决前数据库保持本地版本，再次同步仍返回同一冲突
+        assert_eq!(a.code_of("shared").await.as_deref(), Some("echo local\n"));
+        assert_eq!(a.sync().await.conflicts.len(), 1);
+
+        let err = resolve_conflict(&a.pool, &a.config, "shared", "both").await.unwrap_err();
+        assert_eq!(err, "Unknown resolution: both");
+
+        let report = resolve_conflict(&a.pool, &a.config, "shared", "remote").await.unwrap();
+        assert_eq!(report.status, "synced");
+        assert!(report.pushed);
+        assert_eq!(report.updated, 1);
+        assert_eq!(a.code_of("shared").await.as_deref(), Some("echo remote\n"));
+
+        let err = resolve_conflict(&a.pool, &a.config, "shared", "local").await.unwrap_err();
+        assert_eq!(err, "No snippet sync conflicts to resolve");
+    }
+
+    #[tokio::test]
+    async fn local_changes_made_while_a_conflict_is_pending_survive_resolution() {
+        let (_root, a, b) = setup().await;
+
+        insert_snippet_row(&a.pool, &snippet("shared", "echo base\n")).await.unwrap();
+        insert_snippet_row(&a.pool, &snippet("kept", "echo v1\n")).await.unwrap();
+        a.sync().await;
+        b.sync().await;
+
+        b.set_code("shared", "echo remote\n").await;
+        sqlx::query("DELETE FROM snippets WHERE id = 'kept'").execute(&b.pool).await.unwrap();
+        b.sync().await;
+        a.set_code("shared", "echo local\n").await;
+        assert_eq!(a.sync().await.status, "conflicts");
+
+        // 冲突挂起期间：新增片段，并编辑远端已删除的片段
+        insert_snippet_row(&a.pool, &snippet("later", "echo later\n")).await.unwrap();
+        a.set_code("kept", "echo v2\n").await;
+
+        let report = resolve_conflict(&a.pool, &a.config, "shared", "remote").await.unwrap();
+        assert_eq!(report.status, "synced");
+        assert_eq!(report.deleted, 0);
+        assert_eq!(a.code_of("later").await.as_deref(), Some("echo later\n"));
+        assert_eq!(a.code_of("kept").await.as_deref(), Some("echo v2\n"));
+        let revisions: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM snippet_revisions WHERE snippet_id = 'later'")
+            .fetch_one(&a.pool)
+            .await
+            .unwrap();
+        assert_eq!(revisions, 1);
+
+        // 下次同步时提交保留下来的本地改动
+        a.sync().await;
+        let report = b.sync().await;
+        assert_eq!((report.added, report.updated, report.deleted), (2, 0, 0));
+        assert_eq!(b.code_of("later").await.as_deref(), Some("echo later\n"));
+        assert_eq!(b.code_of("kept").await.as_deref(), Some("echo v2\n"));
+    }
+}
@@ -1,5 +1,24 @@
+// 子模块：批量执行 / 参数化模板 / 导入导出 / 全文检索 / 修订历史 / Git 同步
+pub mod bundle;
+pub mod git_sync;
+pub mod revision;
+pub mod runner;
+pub mod search;
+pub mod template;
+
+pub use bundle::{export_snippets, import_snippets};
+pub use git_sync::{
+    abort_snippet_sync, get_snippet_sync_config, resolve_snippet_sync_conflict, save_snippet_sync_config,
+    sync_snippets_git,
+};
+pub use revision::{diff_snippet_revisions, list_snippet_revisions, restore_snippet_revision};
+pub use runner::execute_snippet;
+pub use search::search_snippets;
+pub use template::{parse_snippet_template, render_snippet_template};
+
 use tauri::State;
 use crate::state::AppState;
+use sqlx::SqlitePool;
 use crate::models::{Snippet, SnippetDto};
 
 #[tauri::command]
@@ -11,55 +30,55 @@ pub async fn get_all_snippets(state: State<'_, AppState>) -> Result<Vec<SnippetD
         .map_err(|e| e.to_string())?;
 
     // 2. 转换为 DTO (解析 tags JSON)
-    let dtos = rows.into_iter().map(|row| SnippetDto {
-        id: row.id,
-        title: row.title,
-        code: row.code,
-        language: row.language,
-        tags: serde_json::from_str(&row.tags).unwrap_or_default(), // JSON String -> Vec
-        created_at: row.created_at,
-        updated_at: row.updated_at,
-    }).collect();
+    let dtos = rows.into_iter().map(SnippetDto::from).collect();
 
     Ok(dtos)
 }
 
 #[tauri::command]
 pub async fn add_snippet(state: State<'_, AppState>, snippet: SnippetDto) -> Result<(), String> {
+    insert_snippet_row(&state.db, &snippet).await
+}
+
+#[tauri::command]
+pub async fn update_snippet(state: State<'_, AppState>, snippet: SnippetDto) -> Result<(), String> {
+    update_snippet_row(&state.db, &snippet).await
+}
+
+pub(crate) async fn insert_snippet_row(pool: &SqlitePool, snippet: &SnippetDto) -> Result<(), String> {
     // Vec -> JSON String
     let tags_json = serde_json::to_string(&snippet.tags).map_err(|e| e.to_string())?;
     
     sqlx::query(
         "INSERT INTO snippets (id, title, code, language, tags, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
     )
-    .bind(snippet.id)
-    .bind(snippet.title)
-    .bind(snippet.code)
-    .bind(snippet.language)
+    .bind(&snippet.id)
+    .bind(&snippet.title)
+    .bind(&snippet.code)
+    .bind(&snippet.language)
     .bind(tags_json)
     .bind(snippet.created_at)
     .bind(snippet.updated_at)
-    .execute(&state.db)
+    .execute(pool)
     .await
     .map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
-#[tauri::command]
-pub async fn update_snippet(state: State<'_, AppState>, snippet: SnippetDto) -> Result<(), String> {
+pub(crate) async fn update_snippet_row(pool: &SqlitePool, snippet: &SnippetDto) -> Result<(), String> {
     let tags_json = serde_json::to_string(&snippet.tags).map_err(|e| e.to_string())?;
 
     sqlx::query(
         "UPDATE snippets SET title=?, code=?, language=?, tags=?, updated_at=? WHERE id=?"
     )
-    .bind(snippet.title)
-    .bind(snippet.code)
-    .bind(snippet.language)
+    .bind(&snippet.title)
+    .bind(&snippet.code)
+    .bind(&snippet.language)
     .bind(tags_json)
     .bind(snippet.updated_at)
-    .bind(snippet.id)
-    .execute(&state.db)
+    .bind(&snippet.id)
+    .execute(pool)
     .await
     .map_err(|e| e.to_string())?;
 
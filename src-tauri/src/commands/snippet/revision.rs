@@ -0,0 +1,234 @@
+// src-tauri/src/commands/snippet/revision.rs
+// 片段修订历史：列出版本 / 任意两版逐行对比 / 恢复旧版本
+// 版本由 db.rs 中的触发器在内容变化时写入；恢复也是一次普通更新，会追加新版本而不是截断历史
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use sqlx::FromRow;
+use tauri::State;
+
+use crate::models::SnippetDto;
+use crate::state::AppState;
+
+use super::update_snippet_row;
+
+// 超过该规模 (行数乘积) 不再求最长公共子序列，整体按删除 + 新增展示
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, FromRow)]
+struct RevisionRow {
+    revision: i64,
+    title: String,
+    code: String,
+    language: String,
+    tags: String,
+    created_at: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetRevision {
+    pub snippet_id: String,
+    pub revision: i64,
+    pub title: String,
+    pub code: String,
+    pub language: String,
+    pub tags: Vec<String>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub kind: String, // equal | added | removed
+    pub text: String,
+    /// 在旧版本 / 新版本中的行号 (从 1 开始)
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionDiff {
+    pub from_revision: i64,
+    pub to_revision: i64,
+    /// 标题 / 语言 / 标签等元数据的变化
+    pub changed_fields: Vec<String>,
+    pub lines: Vec<DiffLine>,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl SnippetRevision {
+    fn from_row(snippet_id: &str, row: RevisionRow) -> Self {
+        Self {
+            snippet_id: snippet_id.to_string(),
+            revision: row.revision,
+            title: row.title,
+            code: row.code,
+            language: row.language,
+            tags: serde_json::from_str(&row.tags).unwrap_or_default(),
+            created_at: row.created_at,
+        }
+    }
+}
+
+const REVISION_COLUMNS: &str = "revision, title, code, language, tags, created_at";
+
+/// 新版本在前
+#[tauri::command]
+pub async fn list_snippet_revisions(
+    state: State<'_, AppState>,
+    snippet_id: String,
+) -> Result<Vec<SnippetRevision>, String> {
+    let rows = sqlx::query_as::<_, RevisionRow>(&format!(
+        "SELECT {} FROM snippet_revisions WHERE snippet_id = ? ORDER BY revision DESC",
+        REVISION_COLUMNS
+    ))
+    .bind(&snippet_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows.into_iter().map(|r| SnippetRevision::from_row(&snippet_id, r)).collect())
+}
+
+/// to_revision 为空时与最新版本对比
+#[tauri::command]
+pub async fn diff_snippet_revisions(
+    state: State<'_, AppState>,
+    snippet_id: String,
+    from_revision: i64,
+    to_revision: Option<i64>,
+) -> Result<RevisionDiff, String> {
+    let to_revision = match to_revision {
+        Some(r) => r,
+        None => sqlx::query_scalar::<_, Option<i64>>("SELECT MAX(revision) FROM snippet_revisions WHERE snippet_id = ?")
+            .bind(&snippet_id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Snippet has no revisions")?,
+    };
+    let old = get_revision(&state.db, &snippet_id, from_revision).await?;
+    let new = get_revision(&state.db, &snippet_id, to_revision).await?;
+
+    let mut changed_fields = Vec::new();
+    if old.title != new.title {
+        changed_fields.push("title".to_string());
+    }
+    if old.language != new.language {
+        changed_fields.push("language".to_string());
+    }
+    if old.tags != new.tags {
+        changed_fields.push("tags".to_string());
+    }
+
+    let lines = diff_lines(&old.code, &new.code);
+    let added = lines.iter().filter(|l| l.kind == "added").count();
+    let removed = lines.iter().filter(|l| l.kind == "removed").count();
+    Ok(RevisionDiff { from_revision, to_revision, changed_fields, lines, added, removed })
+}
+
+/// 以旧版本内容覆盖当前片段，返回更新后的片段
+#[tauri::command]
+pub async fn restore_snippet_revision(
+    state: State<'_, AppState>,
+    snippet_id: String,
+    revision: i64,
+) -> Result<SnippetDto, String> {
+    let target = get_revision(&state.db, &snippet_id, revision).await?;
+    let created_at: i64 = sqlx::query_scalar("SELECT created_at FROM snippets WHERE id = ?")
+        .bind(&snippet_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Snippet not found")?;
+
+    let snippet = SnippetDto {
+        id: snippet_id,
+        title: target.title,
+        code: target.code,
+        language: target.language,
+        tags: target.tags,
+        created_at,
+        updated_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64,
+    };
+    update_snippet_row(&state.db, &snippet).await?;
+    Ok(snippet)
+}
+
+async fn get_revision(pool: &sqlx::SqlitePool, snippet_id: &str, revision: i64) -> Result<SnippetRevision, String> {
+    let row = sqlx::query_as::<_, RevisionRow>(&format!(
+        "SELECT {} FROM snippet_revisions WHERE snippet_id = ? AND revision = ?",
+        REVISION_COLUMNS
+    ))
+    .bind(snippet_id)
+    .bind(revision)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Revision {} not found", revision))?;
+    Ok(SnippetRevision::from_row(snippet_id, row))
+}
+
+// 基于最长公共子序列的逐行对比
+fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // 去掉公共前后缀，缩小 LCS 表
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(x, y)| x == y).count();
+    let (mid_a, mid_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut out = Vec::with_capacity(a.len().max(b.len()));
+    let equal = |out: &mut Vec<DiffLine>, i: usize, j: usize| {
+        out.push(DiffLine { kind: "equal".to_string(), text: a[i].to_string(), old_line: Some(i + 1), new_line: Some(j + 1) });
+    };
+    let removed = |out: &mut Vec<DiffLine>, i: usize| {
+        out.push(DiffLine { kind: "removed".to_string(), text: a[i].to_string(), old_line: Some(i + 1), new_line: None });
+    };
+    let added = |out: &mut Vec<DiffLine>, j: usize| {
+        out.push(DiffLine { kind: "added".to_string(), text: b[j].to_string(), old_line: None, new_line: Some(j + 1) });
+    };
+
+    for k in 0..prefix {
+        equal(&mut out, k, k);
+    }
+
+    let (n, m) = (mid_a.len(), mid_b.len());
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        (0..n).for_each(|i| removed(&mut out, prefix + i));
+        (0..m).for_each(|j| added(&mut out, prefix + j));
+    } else {
+        // lcs[i][j] = mid_a[i..] 与 mid_b[j..] 的 LCS 长度
+        let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if mid_a[i] == mid_b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && mid_a[i] == mid_b[j] {
+                equal(&mut out, prefix + i, prefix + j);
+                i += 1;
+                j += 1;
+            } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                // 同一处改动先列删除行，再列新增行
+                removed(&mut out, prefix + i);
+                i += 1;
+            } else {
+                added(&mut out, prefix + j);
+                j += 1;
+            }
+        }
+    }
+
+    for k in 0..suffix {
+        equal(&mut out, a.len() - suffix + k, b.len() - suffix + k);
+    }
+    out
+}
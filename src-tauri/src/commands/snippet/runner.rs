@@ -0,0 +1,361 @@
+// src-tauri/src/commands/snippet/runner.rs
+// 片段批量执行：按服务器列表或标签选择目标主机，限定并发，逐台建立独立连接非交互执行，
+// 实时推送每台主机的 stdout / stderr / 退出码，结束后返回汇总
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use aes_gcm::{Aes256Gcm, Key};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Semaphore;
+
+use crate::commands::history::{insert_command_result, CommandOutcome};
+use crate::commands::ssh::core::{self, close_session};
+use crate::commands::ssh::exec::{self, ExecRequest, OutputStream};
+use crate::commands::ssh::forward::ForwardSet;
+use crate::commands::vault::{internal_get_secret, VaultState};
+use crate::models::Snippet;
+use crate::state::AppState;
+
+use super::template::{self, SecretMode, ServerFields, Template};
+
+const DEFAULT_CONCURRENCY: usize = 4;
+const MAX_CONCURRENCY: usize = 32;
+const DEFAULT_TIMEOUT_SECS: u64 = 300;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetRunRequest {
+    /// 前端生成，事件名 snippet-output-{runId} / snippet-host-{runId}
+    pub run_id: String,
+    pub snippet_id: String,
+    /// 模板占位符取值 (secret 填 Vault 条目 ID)
+    #[serde(default)]
+    pub values: HashMap<String, String>,
+    #[serde(default)]
+    pub server_ids: Vec<String>,
+    /// 标签选择器：与 server_ids 取并集
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// true 时服务器需包含全部标签，否则任一即可
+    #[serde(default)]
+    pub match_all_tags: bool,
+    pub concurrency: Option<usize>,
+    /// 单台主机的执行超时 (不含连接与认证)
+    pub timeout_secs: Option<u64>,
+    /// 任一主机失败后不再启动排队中的主机 (已在执行的照常结束)
+    #[serde(default)]
+    pub fail_fast: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct HostOutput {
+    server_id: String,
+    stream: &'static str,
+    data: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HostRunResult {
+    pub server_id: String,
+    pub server_name: String,
+    pub status: String, // running | success | failed | skipped
+    pub exit_code: Option<u32>,
+    pub signal: Option<String>,
+    pub timed_out: bool,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl HostRunResult {
+    fn new(target: &Target, status: &str) -> Self {
+        Self {
+            server_id: target.id.clone(),
+            server_name: target.name.clone(),
+            status: status.to_string(),
+            exit_code: None,
+            signal: None,
+            timed_out: false,
+            error: None,
+            duration_ms: 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetRunSummary {
+    pub run_id: String,
+    pub snippet_id: String,
+    pub total: usize,
+    pub succeeded: Vec<String>,
+    pub failed: Vec<String>,
+    pub skipped: Vec<String>,
+    /// 与目标主机顺序一致
+    pub results: Vec<HostRunResult>,
+    pub duration_ms: u64,
+}
+
+// 所有主机共享的执行计划：模板与取值在启动前校验完毕，secret 此时才解密
+struct RunPlan {
+    run_id: String,
+    template: Template,
+    values: HashMap<String, String>,
+    secrets: HashMap<String, String>,
+    exec_request: ExecRequest,
+}
+
+#[derive(Debug, Clone)]
+struct Target {
+    id: String,
+    name: String,
+}
+
+#[tauri::command]
+pub async fn execute_snippet(
+    app: AppHandle,
+    app_state: State<'_, AppState>,
+    vault_state: State<'_, VaultState>,
+    request: SnippetRunRequest,
+) -> Result<SnippetRunSummary, String> {
+    let started = Instant::now();
+
+    let snippet = sqlx::query_as::<_, Snippet>("SELECT * FROM snippets WHERE id = ?")
+        .bind(&request.snippet_id)
+        .fetch_optional(&app_state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Snippet not found")?;
+    let command = interpreter_command(&snippet.language)?;
+    let template = template::parse(&snippet.code)?;
+    let values = template.resolve_values(&request.values)?;
+
+    let targets = resolve_targets(&app_state.db, &request).await?;
+    if targets.is_empty() {
+        return Err("No servers matched the selection".to_string());
+    }
+
+    let master_key = vault_state.0.lock().unwrap().as_ref().cloned();
+    let mut secrets = HashMap::new();
+    for (name, key_id) in template.secret_refs(&values) {
+        let key = master_key.as_ref().ok_or("Vault is locked")?;
+        let secret = internal_get_secret(&app_state.db, key, key_id)
+            .await
+            .map_err(|e| format!("Secret '{}': {}", name, e))?;
+        secrets.insert(name, secret);
+    }
+
+    let semaphore = Arc::new(Semaphore::new(
+        request.concurrency.unwrap_or(DEFAULT_CONCURRENCY).clamp(1, MAX_CONCURRENCY),
+    ));
+    let aborted = Arc::new(AtomicBool::new(false));
+    // 脚本经标准输入交给解释器，避免多行脚本的转义问题，secret 也不会出现在远端进程参数中
+    let plan = Arc::new(RunPlan {
+        run_id: request.run_id.clone(),
+        template,
+        values,
+        secrets,
+        exec_request: ExecRequest {
+            command: command.to_string(),
+            timeout_secs: Some(request.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS)),
+            ..Default::default()
+        },
+    });
+
+    let mut tasks = Vec::with_capacity(targets.len());
+    for target in targets.iter().cloned() {
+        let app = app.clone();
+        let semaphore = semaphore.clone();
+        let aborted = aborted.clone();
+        let plan = plan.clone();
+        let master_key = master_key.clone();
+        let fail_fast = request.fail_fast;
+
+        tasks.push(tauri::async_runtime::spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let host_event = format!("snippet-host-{}", plan.run_id);
+
+            if aborted.load(Ordering::SeqCst) {
+                let result = HostRunResult::new(&target, "skipped");
+                let _ = app.emit(&host_event, &result);
+                return result;
+            }
+            let _ = app.emit(&host_event, HostRunResult::new(&target, "running"));
+
+            let result = run_on_host(&app, &plan, &target, master_key.as_ref()).await;
+            if fail_fast && result.status == "failed" {
+                aborted.store(true, Ordering::SeqCst);
+            }
+            let _ = app.emit(&host_event, &result);
+            result
+        }));
+    }
+
+    let mut results = Vec::with_capacity(tasks.len());
+    for (task, target) in tasks.into_iter().zip(&targets) {
+        results.push(task.await.unwrap_or_else(|e| {
+            let mut failed = HostRunResult::new(target, "failed");
+            failed.error = Some(format!("Task panicked: {}", e));
+            failed
+        }));
+    }
+
+    let ids_with = |status: &str| {
+        results.iter().filter(|r| r.status == status).map(|r| r.server_id.clone()).collect::<Vec<_>>()
+    };
+    Ok(SnippetRunSummary {
+        run_id: request.run_id,
+        snippet_id: request.snippet_id,
+        total: results.len(),
+        succeeded: ids_with("success"),
+        failed: ids_with("failed"),
+        skipped: ids_with("skipped"),
+        results,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+// 每台主机使用独立连接，不占用也不干扰已打开的终端会话
+async fn run_on_host(
+    app: &AppHandle,
+    plan: &RunPlan,
+    target: &Target,
+    master_key: Option<&Key<Aes256Gcm>>,
+) -> HostRunResult {
+    let started = Instant::now();
+    let mut result = HostRunResult::new(target, "failed");
+    let db = app.state::<AppState>().db.clone();
+
+    let prepared = async {
+        let config = core::load_server_config(&db, master_key, &target.id).await?;
+        let fields = ServerFields {
+            id: config.id.clone(),
+            name: target.name.clone(),
+            host: config.host.clone(),
+            port: config.port,
+            username: config.username.clone(),
+        };
+        let script = plan.template.render(&plan.values, Some(&fields), SecretMode::Resolved(&plan.secrets))?;
+        // 写入历史的是渲染后的命令，secret 以掩码代替
+        let recorded = plan.template.render(&plan.values, Some(&fields), SecretMode::Masked)?;
+        // 二次验证提示以 run + 主机区分，前端弹窗可据此显示来源
+        let prompt_id = format!("snippet-{}-{}", plan.run_id, target.id);
+        let handle =
+            core::establish_base_session_async(app, &prompt_id, &config, &Arc::new(ForwardSet::default())).await?;
+        Ok::<_, String>((handle, script, recorded))
+    }
+    .await;
+    let (handle, script, recorded) = match prepared {
+        Ok(v) => v,
+        Err(e) => {
+            result.error = Some(e);
+            result.duration_ms = started.elapsed().as_millis() as u64;
+            return result;
+        }
+    };
+
+    let exec_request = ExecRequest { stdin: Some(script), ..plan.exec_request.clone() };
+    let event = format!("snippet-output-{}", plan.run_id);
+    // 按流分别缓存被截断的 UTF-8 字符
+    let (mut out_buf, mut err_buf) = (Vec::new(), Vec::new());
+    let emit = |stream: OutputStream, data: String| {
+        let _ = app.emit(&event, HostOutput { server_id: target.id.clone(), stream: stream.as_str(), data });
+    };
+
+    let output = exec::run_streaming(&handle, &exec_request, |stream, data| {
+        let buf = match stream {
+            OutputStream::Stdout => &mut out_buf,
+            OutputStream::Stderr => &mut err_buf,
+        };
+        buf.extend_from_slice(data);
+        let text = core::drain_utf8(buf);
+        if !text.is_empty() {
+            emit(stream, text);
+        }
+    })
+    .await;
+
+    for (stream, buf) in [(OutputStream::Stdout, out_buf), (OutputStream::Stderr, err_buf)] {
+        if !buf.is_empty() {
+            emit(stream, String::from_utf8_lossy(&buf).to_string());
+        }
+    }
+    close_session(&handle).await;
+
+    match output {
+        Ok(output) => {
+            if output.success() {
+                result.status = "success".to_string();
+            } else {
+                result.error = Some(output.error_message());
+            }
+            result.exit_code = output.exit_code;
+            result.signal = output.signal;
+            result.timed_out = output.timed_out;
+        }
+        Err(e) => result.error = Some(e),
+    }
+    result.duration_ms = started.elapsed().as_millis() as u64;
+
+    let status = if result.timed_out { "timeout" } else { result.status.as_str() };
+    let outcome = CommandOutcome { status, exit_code: result.exit_code };
+    if let Err(e) = insert_command_result(&db, &target.id, &recorded, "snippet", &outcome).await {
+        eprintln!("[snippet] failed to record run on {}: {}", target.id, e);
+    }
+    result
+}
+
+// 显式 ID 在前 (保持调用方顺序)，标签匹配到的其余服务器按名称排序追加
+async fn resolve_targets(pool: &sqlx::SqlitePool, request: &SnippetRunRequest) -> Result<Vec<Target>, String> {
+    let rows: Vec<(String, String, Option<String>)> =
+        sqlx::query_as("SELECT id, name, tags FROM servers ORDER BY name")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let mut targets: Vec<Target> = Vec::new();
+    for id in &request.server_ids {
+        let (_, name, _) = rows
+            .iter()
+            .find(|(row_id, _, _)| row_id == id)
+            .ok_or_else(|| format!("Server not found: {}", id))?;
+        if !targets.iter().any(|t| &t.id == id) {
+            targets.push(Target { id: id.clone(), name: name.clone() });
+        }
+    }
+
+    if !request.tags.is_empty() {
+        for (id, name, tags) in &rows {
+            let server_tags: Vec<String> = tags
+                .as_deref()
+                .and_then(|t| serde_json::from_str(t).ok())
+                .unwrap_or_default();
+            let has = |tag: &String| server_tags.iter().any(|t| t.eq_ignore_ascii_case(tag));
+            let matched = if request.match_all_tags {
+                request.tags.iter().all(has)
+            } else {
+                request.tags.iter().any(has)
+            };
+            if matched && !targets.iter().any(|t| &t.id == id) {
+                targets.push(Target { id: id.clone(), name: name.clone() });
+            }
+        }
+    }
+    Ok(targets)
+}
+
+// 片段语言 -> 从标准输入读取脚本的解释器
+fn interpreter_command(language: &str) -> Result<&'static str, String> {
+    match language {
+        "bash" => Ok("bash -s"),
+        "text" | "" => Ok("sh -s"),
+        "python" => Ok("python3 -"),
+        "javascript" => Ok("node -"),
+        other => Err(format!("Snippets in '{}' cannot be executed", other)),
+    }
+}
@@ -0,0 +1,173 @@
+// src-tauri/src/commands/snippet/search.rs
+// 片段检索：FTS5 全文匹配 (标题 > 标签 > 代码 加权排序) + 标签 / 语言筛选 + 分面计数 + 分页
+// 索引与 snippet_tags 由 db.rs 中的触发器维护
+
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, QueryBuilder, Row, Sqlite};
+use tauri::State;
+
+use crate::models::{Snippet, SnippetDto};
+use crate::state::AppState;
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetSearchQuery {
+    /// 空格分隔的关键词，全部命中 (按前缀匹配)
+    pub text: Option<String>,
+    /// 需同时带有的标签 (忽略大小写)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub language: Option<String>,
+    pub offset: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetSearchHit {
+    #[serde(flatten)]
+    pub snippet: SnippetDto,
+    /// 相关度 (越大越相关)；无关键词时为空
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetCount {
+    pub value: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetSearchResult {
+    pub items: Vec<SnippetSearchHit>,
+    pub total: i64,
+    pub offset: i64,
+    pub limit: i64,
+    /// 在当前全部筛选条件下的标签分布
+    pub tag_facets: Vec<FacetCount>,
+    /// 不计语言筛选时的语言分布，便于切换语言
+    pub language_facets: Vec<FacetCount>,
+}
+
+// 归一化后的筛选条件
+struct Filter {
+    fts: Option<String>,
+    tags: Vec<String>,
+    language: Option<String>,
+}
+
+#[tauri::command]
+pub async fn search_snippets(
+    state: State<'_, AppState>,
+    query: SnippetSearchQuery,
+) -> Result<SnippetSearchResult, String> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let mut tags: Vec<String> = Vec::new();
+    for tag in query.tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    let filter = Filter {
+        fts: query.text.as_deref().and_then(fts_query),
+        tags,
+        language: query.language.filter(|l| !l.is_empty()),
+    };
+
+    // 1. 当前页
+    let mut qb = QueryBuilder::<Sqlite>::new("");
+    push_matched(&mut qb, &filter, true);
+    qb.push(" SELECT s.*, m.score AS score FROM matched m JOIN snippets s ON s.id = m.id")
+        .push(" ORDER BY m.score DESC, s.created_at DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+    let rows = qb.build().fetch_all(&state.db).await.map_err(|e| e.to_string())?;
+    let mut items = Vec::with_capacity(rows.len());
+    for row in rows {
+        let snippet = Snippet::from_row(&row).map_err(|e| e.to_string())?;
+        items.push(SnippetSearchHit {
+            snippet: SnippetDto::from(snippet),
+            score: row.try_get::<Option<f64>, _>("score").unwrap_or(None),
+        });
+    }
+
+    // 2. 总数
+    let mut qb = QueryBuilder::<Sqlite>::new("");
+    push_matched(&mut qb, &filter, true);
+    qb.push(" SELECT COUNT(*) FROM matched");
+    let total: i64 = qb.build_query_scalar().fetch_one(&state.db).await.map_err(|e| e.to_string())?;
+
+    // 3. 标签分面 (snippet_tags.tag 为 NOCASE，大小写不同的写法合并计数)
+    let mut qb = QueryBuilder::<Sqlite>::new("");
+    push_matched(&mut qb, &filter, true);
+    qb.push(
+        " SELECT t.tag, COUNT(*) AS count FROM snippet_tags t JOIN matched m ON m.id = t.snippet_id \
+         GROUP BY t.tag ORDER BY count DESC, t.tag",
+    );
+    let tag_facets = fetch_facets(qb, &state.db).await?;
+
+    // 4. 语言分面
+    let mut qb = QueryBuilder::<Sqlite>::new("");
+    push_matched(&mut qb, &filter, false);
+    qb.push(
+        " SELECT s.language, COUNT(*) AS count FROM matched m JOIN snippets s ON s.id = m.id \
+         GROUP BY s.language ORDER BY count DESC, s.language",
+    );
+    let language_facets = fetch_facets(qb, &state.db).await?;
+
+    Ok(SnippetSearchResult { items, total, offset, limit, tag_facets, language_facets })
+}
+
+// WITH matched(id, score) AS (...)：满足筛选条件的片段及其相关度
+fn push_matched(qb: &mut QueryBuilder<'_, Sqlite>, filter: &Filter, with_language: bool) {
+    qb.push("WITH matched AS (SELECT s.id AS id, ");
+    // bm25 越小越相关；列权重依次为 snippet_id / title / code / tags
+    if filter.fts.is_some() {
+        qb.push("-bm25(snippets_fts, 0.0, 10.0, 1.0, 5.0) AS score FROM snippets s ");
+        qb.push("JOIN snippets_fts ON snippets_fts.snippet_id = s.id WHERE snippets_fts MATCH ");
+        qb.push_bind(filter.fts.clone().unwrap_or_default());
+    } else {
+        qb.push("NULL AS score FROM snippets s WHERE 1 = 1");
+    }
+
+    if let Some(language) = filter.language.as_ref().filter(|_| with_language) {
+        qb.push(" AND s.language = ").push_bind(language.clone());
+    }
+
+    if !filter.tags.is_empty() {
+        qb.push(" AND s.id IN (SELECT snippet_id FROM snippet_tags WHERE tag IN (");
+        {
+            let mut separated = qb.separated(", ");
+            for tag in &filter.tags {
+                separated.push_bind(tag.clone());
+            }
+        }
+        qb.push(") GROUP BY snippet_id HAVING COUNT(*) = ")
+            .push_bind(filter.tags.len() as i64)
+            .push(")");
+    }
+    qb.push(")");
+}
+
+async fn fetch_facets(mut qb: QueryBuilder<'_, Sqlite>, pool: &sqlx::SqlitePool) -> Result<Vec<FacetCount>, String> {
+    let rows: Vec<(String, i64)> = qb.build_query_as().fetch_all(pool).await.map_err(|e| e.to_string())?;
+    Ok(rows.into_iter().map(|(value, count)| FacetCount { value, count }).collect())
+}
+
+// 用户输入 -> FTS5 查询：每个词加引号按前缀匹配，词之间为 AND；纯符号的词忽略
+fn fts_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .filter(|t| t.chars().any(|c| c.is_alphanumeric()))
+        .map(|t| format!("\"{}\"*", t.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
@@ -0,0 +1,542 @@
+// src-tauri/src/commands/snippet/template.rs
+// 片段模板：{{name:type:quote|raw=default}} 占位符
+//   类型: string (缺省) / number / enum(a|b|c) / secret (值为 Vault 条目 ID，仅执行时解密)
+//   服务器字段: {{server.ip}} {{server.port}} {{server.name}} {{server.username}} {{server.id}}，执行时按目标主机填充
+//   \{{ 输出字面量 {{；不符合上述语法的 {{...}} (Go 模板 {{.Names}}、Helm / Jinja 等) 原样保留
+// 取值默认原样替换；加 :quote 时按 shell 单引号转义 (如 {{msg::quote}})
+// secret 默认转义 (口令常含 $、空格、引号)，已在引号内等需要原样插入时写 {{password:secret:raw}}
+// number / enum 的取值不允许包含 shell 元字符
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::commands::ssh::exec::shell_quote;
+use crate::state::AppState;
+
+const SERVER_FIELDS: [&str; 6] = ["id", "name", "ip", "host", "port", "username"];
+const SECRET_MASK: &str = "******";
+// 未加引号插入命令行时会改变语义的字符
+const SHELL_META: &[char] = &[
+    ';', '&', '|', '<', '>', '(', ')', '$', '`', '\\', '"', '\'', ' ', '\t', '\n', '\r', '*', '?', '[', ']', '#', '~', '{',
+    '}', '!',
+];
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Placeholder {
+    pub name: String,
+    pub kind: String, // string | number | enum | secret | server
+    /// enum 的可选值
+    pub options: Vec<String>,
+    pub default_value: Option<String>,
+    /// 无默认值的用户参数必须填写；server 字段由执行目标提供
+    pub required: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Text(String),
+    /// 占位符下标，:quote / :raw 显式指定的转义方式 (None 时按类型决定，见 Template::render)
+    Slot(usize, Option<bool>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Template {
+    segments: Vec<Segment>,
+    pub placeholders: Vec<Placeholder>,
+}
+
+/// 渲染时的服务器字段
+#[derive(Debug, Clone, Default)]
+pub struct ServerFields {
+    pub id: String,
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+}
+
+impl ServerFields {
+    fn get(&self, field: &str) -> String {
+        match field {
+            "id" => self.id.clone(),
+            "name" => self.name.clone(),
+            "ip" | "host" => self.host.clone(),
+            "port" => self.port.to_string(),
+            _ => self.username.clone(),
+        }
+    }
+}
+
+/// secret 的替换方式
+pub enum SecretMode<'a> {
+    /// 预览：显示为掩码
+    Masked,
+    /// 执行：占位符名 -> 已解密内容
+    Resolved(&'a HashMap<String, String>),
+}
+
+pub fn parse(src: &str) -> Result<Template, String> {
+    let mut segments = Vec::new();
+    let mut placeholders: Vec<Placeholder> = Vec::new();
+    let mut text = String::new();
+    let mut rest = src;
+
+    while let Some(pos) = rest.find("{{") {
+        if rest[..pos].ends_with('\\') {
+            text.push_str(&rest[..pos - 1]);
+            text.push_str("{{");
+            rest = &rest[pos + 2..];
+            continue;
+        }
+        text.push_str(&rest[..pos]);
+        let body_start = pos + 2;
+        let Some(end) = rest[body_start..].find("}}") else {
+            // 没有闭合的 {{ 不是占位符
+            text.push_str(&rest[pos..]);
+            rest = "";
+            break;
+        };
+        let body = &rest[body_start..body_start + end];
+        rest = &rest[body_start + end + 2..];
+        let Some((placeholder, quoted)) = parse_placeholder(body)? else {
+            text.push_str("{{");
+            text.push_str(body);
+            text.push_str("}}");
+            continue;
+        };
+
+        let index = match placeholders.iter().position(|p| p.name == placeholder.name) {
+            Some(i) => {
+                merge_placeholder(&mut placeholders[i], placeholder)?;
+                i
+            }
+            None => {
+                placeholders.push(placeholder);
+                placeholders.len() - 1
+            }
+        };
+        if !text.is_empty() {
+            segments.push(Segment::Text(std::mem::take(&mut text)));
+        }
+        segments.push(Segment::Slot(index, quoted));
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    Ok(Template { segments, placeholders })
+}
+
+fn is_identifier(s: &str) -> bool {
+    s.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// 类型位置只能是单词或 enum(...)，否则整体不视为占位符
+fn is_kind_token(kind: &str) -> bool {
+    kind.is_empty()
+        || kind.chars().all(|c| c.is_ascii_alphabetic())
+        || (kind.starts_with("enum(") && kind.ends_with(')') && !kind.contains('\n'))
+}
+
+// name[:type][:quote|:raw][=default]；Ok(None) 表示不符合语法，按普通文本处理
+fn parse_placeholder(body: &str) -> Result<Option<(Placeholder, Option<bool>)>, String> {
+    let body = body.trim();
+    let (head, default_value) = match body.split_once('=') {
+        Some((h, d)) => (h.trim(), Some(d.to_string())),
+        None => (body, None),
+    };
+    let mut parts = head.splitn(3, ':').map(str::trim);
+    let name = parts.next().unwrap_or_default();
+    let kind = parts.next().unwrap_or_default();
+    let quoted = match parts.next() {
+        None => None,
+        Some("quote") => Some(true),
+        Some("raw") => Some(false),
+        Some(_) => return Ok(None),
+    };
+    let field = name.strip_prefix("server.");
+    if !field.map_or(is_identifier(name), is_identifier) || !is_kind_token(kind) {
+        return Ok(None);
+    }
+
+    if let Some(field) = field {
+        if !SERVER_FIELDS.contains(&field) {
+            return Err(format!("Unknown server field '{}' (expected one of: {})", field, SERVER_FIELDS.join(", ")));
+        }
+        if !kind.is_empty() || default_value.is_some() {
+            return Err(format!("Server field '{}' does not take a type or default", name));
+        }
+        let placeholder = Placeholder {
+            name: name.to_string(),
+            kind: "server".to_string(),
+            options: Vec::new(),
+            default_value: None,
+            required: false,
+        };
+        return Ok(Some((placeholder, quoted)));
+    }
+
+    let (kind, options) = match kind {
+        "" | "string" => ("string", Vec::new()),
+        "number" => ("number", Vec::new()),
+        "secret" => ("secret", Vec::new()),
+        k if k.starts_with("enum(") && k.ends_with(')') => {
+            let options: Vec<String> = k[5..k.len() - 1]
+                .split('|')
+                .map(|o| o.trim().to_string())
+                .filter(|o| !o.is_empty())
+                .collect();
+            if options.is_empty() {
+                return Err(format!("Enum placeholder '{}' has no options", name));
+            }
+            if let Some(option) = options.iter().find(|o| o.contains(SHELL_META)) {
+                return Err(format!("Enum option '{}' of '{}' contains shell metacharacters", option, name));
+            }
+            ("enum", options)
+        }
+        other => return Err(format!("Unknown placeholder type '{}' for '{}'", other, name)),
+    };
+
+    let placeholder = Placeholder {
+        name: name.to_string(),
+        kind: kind.to_string(),
+        options,
+        required: default_value.is_none(),
+        default_value,
+    };
+    if let Some(default) = &placeholder.default_value {
+        if placeholder.kind != "secret" {
+            check_value(&placeholder, default).map_err(|e| format!("Invalid default: {}", e))?;
+        }
+    }
+    Ok(Some((placeholder, quoted)))
+}
+
+// 同名占位符可多次出现：只需声明一次类型与默认值，重复声明必须一致
+fn merge_placeholder(existing: &mut Placeholder, other: Placeholder) -> Result<(), String> {
+    if other.kind != existing.kind {
+        if existing.kind == "string" {
+            existing.kind = other.kind;
+            existing.options = other.options;
+        } else if other.kind != "string" {
+            return Err(format!(
+                "Placeholder '{}' is declared as both {} and {}",
+                existing.name, existing.kind, other.kind
+            ));
+        }
+    } else if !other.options.is_empty() && other.options != existing.options {
+        return Err(format!("Placeholder '{}' has conflicting enum options", existing.name));
+    }
+
+    match (&existing.default_value, other.default_value) {
+        (Some(a), Some(b)) if *a != b => {
+            return Err(format!("Placeholder '{}' has conflicting defaults", existing.name));
+        }
+        (None, Some(b)) => existing.default_value = Some(b),
+        _ => {}
+    }
+    existing.required = existing.default_value.is_none();
+    if let Some(default) = existing.default_value.as_deref().filter(|_| existing.kind != "secret") {
+        check_value(existing, default).map_err(|e| format!("Invalid default: {}", e))?;
+    }
+    Ok(())
+}
+
+fn check_value(placeholder: &Placeholder, value: &str) -> Result<(), String> {
+    match placeholder.kind.as_str() {
+        // inf / NaN 也能被解析为 f64，但不是可用的数值；有限数值本身不会含 shell 元字符
+        "number" if !value.trim().parse::<f64>().is_ok_and(f64::is_finite) => {
+            Err(format!("'{}' must be a number, got '{}'", placeholder.name, value))
+        }
+        "enum" if !placeholder.options.iter().any(|o| o == value) => Err(format!(
+            "'{}' must be one of: {}",
+            placeholder.name,
+            placeholder.options.join(", ")
+        )),
+        // 值会插入命令行，拒绝换行以免拆成多条命令
+        "string" | "number" | "enum" if value.contains('\n') => {
+            Err(format!("'{}' must not contain line breaks", placeholder.name))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl Template {
+    /// 校验用户取值并补全默认值，返回 名称 -> 值 (secret 的值为 Vault 条目 ID)
+    pub fn resolve_values(&self, values: &HashMap<String, String>) -> Result<HashMap<String, String>, String> {
+        let mut resolved = HashMap::new();
+        for p in self.placeholders.iter().filter(|p| p.kind != "server") {
+            let value = values
+                .get(&p.name)
+                .filter(|v| !v.is_empty())
+                .or(p.default_value.as_ref())
+                .ok_or_else(|| format!("Missing value for '{}'", p.name))?;
+            check_value(p, value)?;
+            let value = if p.kind == "number" { value.trim() } else { value.as_str() };
+            resolved.insert(p.name.clone(), value.to_string());
+        }
+        Ok(resolved)
+    }
+
+    /// secret 占位符 -> Vault 条目 ID
+    pub fn secret_refs<'a>(&self, resolved: &'a HashMap<String, String>) -> Vec<(String, &'a str)> {
+        self.placeholders
+            .iter()
+            .filter(|p| p.kind == "secret")
+            .filter_map(|p| resolved.get(&p.name).map(|id| (p.name.clone(), id.as_str())))
+            .collect()
+    }
+
+    /// resolved 来自 resolve_values；未提供 server 时服务器字段原样保留
+    pub fn render(
+        &self,
+        resolved: &HashMap<String, String>,
+        server: Option<&ServerFields>,
+        secrets: SecretMode,
+    ) -> Result<String, String> {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(t) => out.push_str(t),
+                Segment::Slot(i, quoted) => {
+                    let p = &self.placeholders[*i];
+                    let value = match p.kind.as_str() {
+                        "server" => match server {
+                            Some(s) => s.get(&p.name["server.".len()..]),
+                            None => {
+                                let suffix = match quoted {
+                                    Some(true) => "::quote",
+                                    Some(false) => "::raw",
+                                    None => "",
+                                };
+                                out.push_str(&format!("{{{{{}{}}}}}", p.name, suffix));
+                                continue;
+                            }
+                        },
+                        "secret" => match &secrets {
+                            SecretMode::Masked => SECRET_MASK.to_string(),
+                            SecretMode::Resolved(map) => map
+                                .get(&p.name)
+                                .cloned()
+                                .ok_or_else(|| format!("Secret '{}' was not resolved", p.name))?,
+                        },
+                        _ => resolved.get(&p.name).cloned().ok_or_else(|| format!("Missing value for '{}'", p.name))?,
+                    };
+                    // 同名占位符可能只在某一处声明为 secret，按合并后的类型决定
+                    if quoted.unwrap_or(p.kind == "secret") {
+                        out.push_str(&shell_quote(&value));
+                    } else {
+                        out.push_str(&value);
+                    }
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+// ==============================================================================
+// 🟢 命令：占位符结构 / 渲染预览 (secret 以掩码显示，真实内容只在执行时解密)
+// ==============================================================================
+
+#[tauri::command]
+pub fn parse_snippet_template(code: String) -> Result<Vec<Placeholder>, String> {
+    Ok(parse(&code)?.placeholders)
+}
+
+#[tauri::command]
+pub async fn render_snippet_template(
+    app_state: State<'_, AppState>,
+    code: String,
+    values: HashMap<String, String>,
+    server_id: Option<String>,
+) -> Result<String, String> {
+    let template = parse(&code)?;
+    let resolved = template.resolve_values(&values)?;
+
+    let server = match server_id.filter(|id| !id.is_empty()) {
+        Some(id) => {
+            let (name, host, port, username): (String, String, Option<i64>, Option<String>) =
+                sqlx::query_as("SELECT name, ip, port, username FROM servers WHERE id = ?")
+                    .bind(&id)
+                    .fetch_optional(&app_state.db)
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .ok_or("Server not found")?;
+            Some(ServerFields {
+                id,
+                name,
+                host,
+                port: port.unwrap_or(22) as u16,
+                username: username.unwrap_or_default(),
+            })
+        }
+        None => None,
+    };
+    template.render(&resolved, server.as_ref(), SecretMode::Masked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    // 解析并以 Masked 渲染 (不含服务器字段)
+    fn render(src: &str, pairs: &[(&str, &str)]) -> Result<String, String> {
+        let template = parse(src)?;
+        let resolved = template.resolve_values(&values(pairs))?;
+        template.render(&resolved, None, SecretMode::Masked)
+    }
+
+    #[test]
+    fn escaped_braces_stay_literal() {
+        let template = parse(r"echo \{{name}} {{name}}").unwrap();
+        assert_eq!(template.placeholders.len(), 1);
+        assert_eq!(render(r"echo \{{name}} {{name}}", &[("name", "x")]).unwrap(), "echo {{name}} x");
+    }
+
+    #[test]
+    fn foreign_template_syntax_stays_literal() {
+        for src in [
+            "docker ps --format '{{.Names}}'",
+            "docker inspect -f '{{json .Config.Env}}' web",
+            "helm template --set a={{ .Values.x }}",
+            "echo {{a:string:upper}}",
+            "echo {{ unclosed",
+        ] {
+            assert!(parse(src).unwrap().placeholders.is_empty(), "{}", src);
+            assert_eq!(render(src, &[]).unwrap(), src);
+        }
+    }
+
+    #[test]
+    fn repeated_names_merge_into_one_placeholder() {
+        let src = "deploy {{env:enum(dev|prod)=dev}} && notify {{env}}";
+        let template = parse(src).unwrap();
+        assert_eq!(template.placeholders.len(), 1);
+        let p = &template.placeholders[0];
+        assert_eq!((p.kind.as_str(), p.default_value.as_deref(), p.required), ("enum", Some("dev"), false));
+        assert_eq!(p.options, ["dev", "prod"]);
+        assert_eq!(render(src, &[]).unwrap(), "deploy dev && notify dev");
+        assert_eq!(render(src, &[("env", "prod")]).unwrap(), "deploy prod && notify prod");
+
+        // 后出现的声明补全前面的类型与默认值
+        let template = parse("{{n}} {{n:number=3}}").unwrap();
+        assert_eq!(template.placeholders[0].kind, "number");
+        assert!(!template.placeholders[0].required);
+    }
+
+    #[test]
+    fn conflicting_declarations_are_rejected() {
+        let err = parse("{{x=1}} {{x=2}}").unwrap_err();
+        assert_eq!(err, "Placeholder 'x' has conflicting defaults");
+        let err = parse("{{x:number}} {{x:secret}}").unwrap_err();
+        assert_eq!(err, "Placeholder 'x' is declared as both number and secret");
+        let err = parse("{{x:enum(a|b)}} {{x:enum(a|c)}}").unwrap_err();
+        assert_eq!(err, "Placeholder 'x' has conflicting enum options");
+        // 合并后的默认值同样要符合类型
+        assert!(parse("{{n=abc}} {{n:number}}").unwrap_err().starts_with("Invalid default"));
+    }
+
+    #[test]
+    fn enum_options_reject_shell_metacharacters() {
+        for src in ["{{e:enum(a|b;rm -rf /)}}", "{{e:enum(a|$(id))}}", "{{e:enum(a|b c)}}"] {
+            let err = parse(src).unwrap_err();
+            assert!(err.contains("contains shell metacharacters"), "{}: {}", src, err);
+        }
+        assert_eq!(parse("{{e:enum()}}").unwrap_err(), "Enum placeholder 'e' has no options");
+        let err = render("{{e:enum(a|b)}}", &[("e", "c")]).unwrap_err();
+        assert_eq!(err, "'e' must be one of: a, b");
+    }
+
+    #[test]
+    fn numbers_must_be_finite() {
+        for bad in ["NaN", "inf", "-infinity", "1e999", "12abc", "1;id"] {
+            let err = render("sleep {{n:number}}", &[("n", bad)]).unwrap_err();
+            assert!(err.starts_with("'n' must be a number"), "{}: {}", bad, err);
+        }
+        assert_eq!(render("sleep {{n:number}}", &[("n", " 2.5 ")]).unwrap(), "sleep 2.5");
+        assert!(parse("{{n:number=inf}}").unwrap_err().starts_with("Invalid default"));
+    }
+
+    #[test]
+    fn line_breaks_are_rejected() {
+        let err = render("echo {{msg}}", &[("msg", "a\nreboot")]).unwrap_err();
+        assert_eq!(err, "'msg' must not contain line breaks");
+        assert!(parse("{{msg=a\nb}}").unwrap_err().starts_with("Invalid default"));
+    }
+
+    #[test]
+    fn missing_values_are_reported() {
+        assert_eq!(render("echo {{msg}}", &[]).unwrap_err(), "Missing value for 'msg'");
+        // 空字符串视为未填写，回落到默认值
+        assert_eq!(render("echo {{msg=hi}}", &[("msg", "")]).unwrap(), "echo hi");
+    }
+
+    #[test]
+    fn quote_flag_shell_quotes_the_value() {
+        assert_eq!(render("echo {{msg::quote}}", &[("msg", "it's $HOME")]).unwrap(), r"echo 'it'\''s $HOME'");
+        assert_eq!(render("echo {{msg}}", &[("msg", "$HOME")]).unwrap(), "echo $HOME");
+        assert_eq!(render("echo {{msg:string:quote=a b}}", &[]).unwrap(), "echo 'a b'");
+    }
+
+    #[test]
+    fn secrets_are_masked_or_resolved_and_quoted_by_default() {
+        let src = "mysql -p{{pw:secret}} -e {{sql::quote}}";
+        let template = parse(src).unwrap();
+        let resolved = template.resolve_values(&values(&[("pw", "vault-entry-1"), ("sql", "select 1")])).unwrap();
+        assert_eq!(template.secret_refs(&resolved), [("pw".to_string(), "vault-entry-1")]);
+
+        let masked = template.render(&resolved, None, SecretMode::Masked).unwrap();
+        assert_eq!(masked, "mysql -p'******' -e 'select 1'");
+        assert!(!masked.contains("vault-entry-1"));
+
+        let secrets = values(&[("pw", "p@ss w'rd$1")]);
+        let rendered = template.render(&resolved, None, SecretMode::Resolved(&secrets)).unwrap();
+        assert_eq!(rendered, r"mysql -p'p@ss w'\''rd$1' -e 'select 1'");
+
+        let err = template.render(&resolved, None, SecretMode::Resolved(&HashMap::new())).unwrap_err();
+        assert_eq!(err, "Secret 'pw' was not resolved");
+    }
+
+    #[test]
+    fn raw_flag_inserts_secrets_verbatim() {
+        // 同名的后续出现按合并后的 secret 类型同样默认转义
+        let template = parse(r#"curl -u "admin:{{pw:secret:raw}}" && echo {{pw}}"#).unwrap();
+        let resolved = template.resolve_values(&values(&[("pw", "entry")])).unwrap();
+        let secrets = values(&[("pw", "s3cret")]);
+        let rendered = template.render(&resolved, None, SecretMode::Resolved(&secrets)).unwrap();
+        assert_eq!(rendered, r#"curl -u "admin:s3cret" && echo 's3cret'"#);
+    }
+
+    #[test]
+    fn server_fields_render_from_the_target() {
+        let src = "ssh {{server.username}}@{{server.ip::quote}} -p {{server.port}}";
+        let template = parse(src).unwrap();
+        assert!(template.placeholders.iter().all(|p| p.kind == "server" && !p.required));
+        let resolved = template.resolve_values(&HashMap::new()).unwrap();
+
+        let unrendered = template.render(&resolved, None, SecretMode::Masked).unwrap();
+        assert_eq!(unrendered, src);
+
+        let server = ServerFields {
+            id: "s1".to_string(),
+            name: "web".to_string(),
+            host: "10.0.0.5".to_string(),
+            port: 2222,
+            username: "deploy".to_string(),
+        };
+        let rendered = template.render(&resolved, Some(&server), SecretMode::Masked).unwrap();
+        assert_eq!(rendered, "ssh deploy@'10.0.0.5' -p 2222");
+
+        assert!(parse("{{server.password}}").unwrap_err().starts_with("Unknown server field 'password'"));
+        assert_eq!(parse("{{server.ip=1.2.3.4}}").unwrap_err(), "Server field 'server.ip' does not take a type or default");
+    }
+}
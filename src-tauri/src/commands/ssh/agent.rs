@@ -0,0 +1,69 @@
+// src-tauri/src/commands/ssh/agent.rs
+// SSH Agent：经 SSH_AUTH_SOCK 使用本机 ssh-agent 中的身份认证，并可将 agent 转发给远端 Shell
+
+use russh::client::Msg;
+use russh::Channel;
+
+use super::core::SshHandle;
+
+/// 逐个尝试 agent 中的身份，任一被服务端接受即返回 true
+#[cfg(unix)]
+pub async fn authenticate_with_agent(handle: &mut SshHandle, username: &str) -> Result<bool, String> {
+    use russh_keys::agent::client::AgentClient;
+
+    let mut agent = AgentClient::connect_env()
+        .await
+        .map_err(|e| format!("Cannot connect to ssh-agent (SSH_AUTH_SOCK): {}", e))?;
+    let identities = agent
+        .request_identities()
+        .await
+        .map_err(|e| format!("Failed to list agent identities: {}", e))?;
+    if identities.is_empty() {
+        return Err("ssh-agent has no identities (try ssh-add)".to_string());
+    }
+
+    for key in identities {
+        let (returned, result) = handle.authenticate_future(username, key, agent).await;
+        agent = returned;
+        match result {
+            Ok(true) => return Ok(true),
+            Ok(false) => continue,
+            // 硬件令牌拒绝签名 / 未触摸时继续尝试下一把
+            Err(e) => eprintln!("[agent] signing failed: {}", e),
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(not(unix))]
+pub async fn authenticate_with_agent(_handle: &mut SshHandle, _username: &str) -> Result<bool, String> {
+    Err("SSH agent authentication requires SSH_AUTH_SOCK (Unix only)".to_string())
+}
+
+/// 远端打开的 auth-agent@openssh.com 通道：与本机 agent socket 双向转发
+#[cfg(unix)]
+pub fn spawn_agent_bridge(channel: Channel<Msg>) {
+    tauri::async_runtime::spawn(async move {
+        let Some(sock) = std::env::var_os("SSH_AUTH_SOCK") else {
+            let _ = channel.close().await;
+            return;
+        };
+        let mut local = match tokio::net::UnixStream::connect(&sock).await {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("[agent] forward failed: {}", e);
+                let _ = channel.close().await;
+                return;
+            }
+        };
+        let mut remote = channel.into_stream();
+        let _ = tokio::io::copy_bidirectional(&mut remote, &mut local).await;
+    });
+}
+
+#[cfg(not(unix))]
+pub fn spawn_agent_bridge(channel: Channel<Msg>) {
+    tauri::async_runtime::spawn(async move {
+        let _ = channel.close().await;
+    });
+}
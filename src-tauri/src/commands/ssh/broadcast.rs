@@ -0,0 +1,84 @@
+// src-tauri/src/commands/ssh/broadcast.rs
+// 广播输入：一组会话共享键盘输入，每个成员独立投递，可单独暂停而不退出分组
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+/// Key: 分组 ID (仅存在于内存)
+pub type BroadcastGroups = Arc<Mutex<HashMap<String, BroadcastGroup>>>;
+
+/// 会话结束后从所有分组中移除，避免之后每次广播都对已失效的会话报告失败
+pub fn forget_session(groups: &BroadcastGroups, session_id: &str) {
+    let ids = [session_id.to_string()];
+    for group in groups.lock().unwrap().values_mut() {
+        group.remove_members(&ids);
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastMember {
+    pub session_id: String,
+    /// 暂停的成员不接收输入，但保留在分组中
+    pub paused: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastGroup {
+    pub id: String,
+    pub name: String,
+    pub members: Vec<BroadcastMember>,
+}
+
+impl BroadcastGroup {
+    pub fn new(name: String, session_ids: Vec<String>) -> Self {
+        let mut group = Self { id: uuid::Uuid::new_v4().to_string(), name, members: Vec::new() };
+        group.add_members(session_ids);
+        group
+    }
+
+    /// 加入成员 (已在组内的忽略，保持原有暂停状态)
+    pub fn add_members(&mut self, session_ids: Vec<String>) {
+        for id in session_ids {
+            if !self.members.iter().any(|m| m.session_id == id) {
+                self.members.push(BroadcastMember { session_id: id, paused: false });
+            }
+        }
+    }
+
+    pub fn remove_members(&mut self, session_ids: &[String]) {
+        self.members.retain(|m| !session_ids.contains(&m.session_id));
+    }
+
+    pub fn set_paused(&mut self, session_id: &str, paused: bool) -> Result<(), String> {
+        let member = self
+            .members
+            .iter_mut()
+            .find(|m| m.session_id == session_id)
+            .ok_or("Session is not a member of this group")?;
+        member.paused = paused;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastDelivery {
+    pub session_id: String,
+    pub status: String, // sent | paused | failed
+    pub error: Option<String>,
+}
+
+impl BroadcastDelivery {
+    pub fn new(session_id: &str, result: Option<Result<(), String>>) -> Self {
+        let (status, error) = match result {
+            None => ("paused", None),
+            Some(Ok(())) => ("sent", None),
+            Some(Err(e)) => ("failed", Some(e)),
+        };
+        Self { session_id: session_id.to_string(), status: status.to_string(), error }
+    }
+}
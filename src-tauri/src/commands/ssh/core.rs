@@ -1,14 +1,616 @@
-use tauri::{AppHandle};
-use crate::models::SshConfig;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-pub async fn establish_base_session_async(_config: &SshConfig) -> Result<(), String> {
-    Ok(())
+use async_trait::async_trait;
+use russh::client::{self, Handle, Msg, Session};
+use russh::{Channel, ChannelId, ChannelMsg, Disconnect};
+use russh_keys::key;
+use russh_sftp::client::SftpSession;
+use sqlx::{Pool, Row, Sqlite};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+
+use aes_gcm::{Aes256Gcm, Key};
+use crate::commands::dialer;
+use crate::commands::proxy::internal_get_proxy;
+use crate::commands::vault::internal_get_secret;
+use crate::models::{AuthType, ConnectionType, Proxy, SshConfig};
+use super::agent;
+use super::forward::{self, ForwardSet};
+use super::interactive::{self, AuthPrompter};
+use super::x11::{self, X11Forward};
+use super::known_hosts::{self, get_known_hosts_path, HostKeyStatus};
+
+// ==============================================================================
+// 🟢 russh 客户端回调
+// ==============================================================================
+
+pub struct ClientHandler {
+    pub host: String,
+    pub port: u16,
+    /// known_hosts 路径；None 表示仅探测公钥 (check_host_key 使用)，握手随即中止
+    pub known_hosts: Option<PathBuf>,
+    /// 握手时服务端出示的公钥及校验结果
+    pub host_key: Arc<Mutex<Option<(key::PublicKey, HostKeyStatus)>>>,
+    /// 经跳板连接时持有上一跳的句柄
+    pub upstream: Option<Arc<SshHandle>>,
+    /// 允许未记录的主机密钥继续握手 (仅 test_connection 诊断使用，不写入 known_hosts)
+    pub accept_unknown: bool,
+    /// 是否接受远端发起的 agent 转发通道 (按服务器开启)
+    pub agent_forwarding: bool,
+    /// 会话的端口转发状态 (仅目标主机持有，接收 -R 转发进来的连接)
+    pub forwards: Option<Arc<ForwardSet>>,
+}
+
+impl ClientHandler {
+    pub fn new(host: &str, port: u16, known_hosts: Option<PathBuf>) -> Self {
+        Self {
+            host: host.to_string(),
+            port,
+            known_hosts,
+            host_key: Arc::new(Mutex::new(None)),
+            upstream: None,
+            accept_unknown: false,
+            agent_forwarding: false,
+            forwards: None,
+        }
+    }
+}
+
+#[async_trait]
+impl client::Handler for ClientHandler {
+    type Error = russh::Error;
+
+    // 按 known_hosts 校验：只有 Matched 才允许继续握手
+    async fn check_server_key(
+        &mut self,
+        server_public_key: &key::PublicKey,
+    ) -> Result<bool, Self::Error> {
+        let Some(path) = &self.known_hosts else {
+            *self.host_key.lock().unwrap() = Some((server_public_key.clone(), HostKeyStatus::Unknown));
+            return Ok(false);
+        };
+
+        let status = known_hosts::check(path, &self.host, self.port, server_public_key)
+            .unwrap_or_else(|e| {
+                eprintln!("[known_hosts] {}", e);
+                HostKeyStatus::Unknown
+            });
+        let accepted = status == HostKeyStatus::Matched
+            || (self.accept_unknown && status == HostKeyStatus::Unknown);
+        *self.host_key.lock().unwrap() = Some((server_public_key.clone(), status));
+        Ok(accepted)
+    }
+
+    // 远端请求使用本机 agent：未开启转发的连接直接丢弃通道
+    async fn server_channel_open_agent_forward(
+        &mut self,
+        channel: Channel<Msg>,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if self.agent_forwarding {
+            agent::spawn_agent_bridge(channel);
+        }
+        Ok(())
+    }
+
+    // 远端 X 客户端连接：转接到本机 DISPLAY (仅在 Shell 申请过 X11 转发时存在)
+    async fn server_channel_open_x11(
+        &mut self,
+        channel: Channel<Msg>,
+        _originator_address: &str,
+        _originator_port: u32,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        match self.forwards.as_ref().and_then(|f| f.x11()) {
+            Some(display) => x11::spawn_x11_bridge(display, channel),
+            None => {
+                let _ = channel.close().await;
+            }
+        }
+        Ok(())
+    }
+
+    // -R 规则被访问：服务端经 forwarded-tcpip 通道送来连接
+    async fn server_channel_open_forwarded_tcpip(
+        &mut self,
+        channel: Channel<Msg>,
+        _connected_address: &str,
+        connected_port: u32,
+        _originator_address: &str,
+        _originator_port: u32,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        match &self.forwards {
+            Some(forwards) => forward::accept_forwarded(forwards, channel, connected_port),
+            None => {
+                let _ = channel.close().await;
+            }
+        }
+        Ok(())
+    }
+    // 会话任务持有 &mut Session：运行中启动的 -R 规则在这里写出 tcpip-forward (见 forward::start_remote)
+    async fn channel_open_confirmation(
+        &mut self,
+        _id: ChannelId,
+        _max_packet_size: u32,
+        _window_size: u32,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if let Some(forwards) = &self.forwards {
+            for (host, port) in forwards.take_remote_requests() {
+                // russh 0.40 不把全局请求的应答交给 Handler，拒绝时只会记录 "Unhandled packet"
+                session.tcpip_forward(true, &host, port);
+            }
+        }
+        Ok(())
+    }
+}
+
+// 取出握手时被拒绝的主机密钥对应的错误信息
+pub fn host_key_error(host: &str, port: u16, slot: &Mutex<Option<(key::PublicKey, HostKeyStatus)>>) -> Option<String> {
+    let guard = slot.lock().unwrap();
+    let (key, status) = guard.as_ref()?;
+    known_hosts::rejection_message(host, port, key, status)
+}
+
+pub type SshHandle = Handle<ClientHandler>;
+
+/// Shell 读写任务接收的指令 (键盘输入 / 窗口尺寸 / 关闭)
+pub enum ShellInput {
+    Data(Vec<u8>),
+    Resize { cols: u32, rows: u32 },
+    Close,
+}
+
+// ==============================================================================
+// 🟢 凭证解析：servers 表 -> SshConfig (密码/私钥从 Vault 解密)
+// ==============================================================================
+
+// 跳板链最大深度 (防止配置错误导致无限嵌套)
+const MAX_JUMP_HOPS: usize = 8;
+
+pub async fn server_exists(pool: &Pool<Sqlite>, server_id: &str) -> Result<bool, String> {
+    let row = sqlx::query("SELECT 1 FROM servers WHERE id = ?")
+        .bind(server_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.is_some())
+}
+
+pub async fn load_server_config(
+    pool: &Pool<Sqlite>,
+    master_key: Option<&Key<Aes256Gcm>>,
+    server_id: &str,
+) -> Result<SshConfig, String> {
+    let mut config = load_single_server(pool, master_key, server_id).await?;
+    let visited = HashSet::from([server_id.to_string()]);
+    config.jump_hosts = load_jump_chain(pool, master_key, jump_server_id(&config), visited).await?;
+    Ok(config)
+}
+
+/// 从第一台跳板 (first_hop) 起沿 proxy_id 向外解析跳板链：target -> bastion2 -> bastion1，
+/// 按连接顺序 (最外层在前) 返回；visited 为已在链上的服务器 (用于检测环路)
+pub async fn load_jump_chain(
+    pool: &Pool<Sqlite>,
+    master_key: Option<&Key<Aes256Gcm>>,
+    first_hop: Option<String>,
+    mut visited: HashSet<String>,
+) -> Result<Vec<SshConfig>, String> {
+    let mut chain = Vec::new();
+    let mut next = first_hop;
+
+    while let Some(bastion_id) = next {
+        if !visited.insert(bastion_id.clone()) {
+            return Err("Jump host loop detected in proxy chain".to_string());
+        }
+        if chain.len() >= MAX_JUMP_HOPS {
+            return Err(format!("Jump host chain exceeds {} hops", MAX_JUMP_HOPS));
+        }
+        let bastion = load_single_server(pool, master_key, &bastion_id)
+            .await
+            .map_err(|e| format!("Jump host {}: {}", bastion_id, e))?;
+        next = jump_server_id(&bastion);
+        chain.push(bastion);
+    }
+
+    chain.reverse();
+    Ok(chain)
+}
+
+fn jump_server_id(config: &SshConfig) -> Option<String> {
+    match config.connection_type {
+        Some(ConnectionType::Proxy) => config.proxy_id.clone().filter(|id| !id.is_empty()),
+        _ => None,
+    }
+}
+
+async fn load_single_server(
+    pool: &Pool<Sqlite>,
+    master_key: Option<&Key<Aes256Gcm>>,
+    server_id: &str,
+) -> Result<SshConfig, String> {
+    let row = sqlx::query("SELECT * FROM servers WHERE id = ?")
+        .bind(server_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Server not found")?;
+
+    let password_id: Option<String> = row.try_get("password_id").ok().flatten();
+    let password_source: Option<String> = row.try_get("password_source").ok().flatten();
+    let key_id: Option<String> = row.try_get("key_id").ok().flatten();
+    let key_source: Option<String> = row.try_get("key_source").ok().flatten();
+
+    let mut password: Option<String> = row.try_get("password").ok().flatten();
+    let mut private_key: Option<String> = row.try_get("private_key").ok().flatten();
+
+    // 非 manual 来源的凭证都存放在 Vault 中，需要主密钥解密
+    if let Some(pid) = password_id.as_deref().filter(|_| password_source.as_deref() != Some("manual")) {
+        let mk = master_key.ok_or("VAULT_LOCKED")?;
+        password = Some(internal_get_secret(pool, mk, pid).await?);
+    }
+    if let Some(kid) = key_id.as_deref().filter(|_| key_source.as_deref() != Some("manual")) {
+        let mk = master_key.ok_or("VAULT_LOCKED")?;
+        private_key = Some(internal_get_secret(pool, mk, kid).await?);
+    }
+
+    // HTTP / SOCKS 代理：proxy_id 指向 proxies 表
+    let connection_type: Option<ConnectionType> = row.try_get("connection_type").ok();
+    let proxy_id: Option<String> = row.try_get("proxy_id").ok().flatten();
+    let proxy = match (&connection_type, proxy_id.as_deref().filter(|id| !id.is_empty())) {
+        (Some(ConnectionType::Http) | Some(ConnectionType::Socks5), Some(pid)) => {
+            Some(internal_get_proxy(pool, pid).await?)
+        }
+        _ => None,
+    };
+
+    Ok(SshConfig {
+        id: server_id.to_string(),
+        host: row.try_get("ip").unwrap_or_default(),
+        port: row.try_get::<i64, _>("port").unwrap_or(22) as u16,
+        username: row.try_get("username").unwrap_or_else(|_| "root".to_string()),
+        password: password.filter(|p| !p.is_empty()),
+        private_key: private_key.filter(|k| !k.is_empty()),
+        passphrase: row.try_get("passphrase").ok().flatten(),
+        password_id,
+        password_source,
+        connection_type,
+        proxy_id,
+        jump_hosts: Vec::new(),
+        proxy,
+        auth_type: row.try_get("auth_type").ok(),
+        agent_forwarding: row.try_get("agent_forwarding").ok(),
+        x11_forwarding: row.try_get("x11_forwarding").ok(),
+        connect_timeout: row.try_get("connect_timeout").ok(),
+        keep_alive_interval: row.try_get("keep_alive_interval").ok(),
+        auto_reconnect: row.try_get("auto_reconnect").ok(),
+        max_reconnects: row.try_get("max_reconnects").ok(),
+    })
+}
+
+// ==============================================================================
+// 🟢 建立 russh 会话 (TCP + 握手 + 认证)
+// ==============================================================================
+
+// keepalive：按 keep_alive_interval 发送，连续 3 次无响应即判定连接断开
+fn client_config(config: &SshConfig) -> Arc<client::Config> {
+    let interval = config.keep_alive_interval.unwrap_or(60);
+    Arc::new(client::Config {
+        inactivity_timeout: None,
+        keepalive_interval: (interval > 0).then(|| Duration::from_secs(interval as u64)),
+        keepalive_max: 3,
+        ..Default::default()
+    })
+}
+
+pub async fn establish_base_session_async(
+    app: &AppHandle,
+    session_id: &str,
+    config: &SshConfig,
+    forwards: &Arc<ForwardSet>,
+) -> Result<SshHandle, String> {
+    let known_hosts_path = get_known_hosts_path(app).ok_or("Cannot locate ~/.ssh/known_hosts")?;
+    let prompter = AuthPrompter::new(app, session_id);
+
+    let upstream = connect_jump_chain(&known_hosts_path, &config.jump_hosts, &prompter).await?;
+    connect_hop(&known_hosts_path, config, upstream, &prompter, Some(forwards.clone())).await
+}
+
+/// 逐跳建立跳板链：上一跳的 direct-tcpip 通道作为下一跳的传输层，返回最内层跳板句柄
+pub async fn connect_jump_chain(
+    known_hosts_path: &Path,
+    hops: &[SshConfig],
+    prompter: &AuthPrompter,
+) -> Result<Option<Arc<SshHandle>>, String> {
+    let mut upstream: Option<Arc<SshHandle>> = None;
+    for hop in hops {
+        let handle = connect_hop(known_hosts_path, hop, upstream.take(), prompter, None)
+            .await
+            .map_err(|e| format!("Jump host {}:{}: {}", hop.host, hop.port, e))?;
+        upstream = Some(Arc::new(handle));
+    }
+    Ok(upstream)
+}
+
+// 单跳：TCP 直连 / 经代理拨号 / 经上游跳板的 direct-tcpip 通道 -> 握手 -> 主机密钥校验 -> 认证
+async fn connect_hop(
+    known_hosts_path: &Path,
+    hop: &SshConfig,
+    upstream: Option<Arc<SshHandle>>,
+    prompter: &AuthPrompter,
+    forwards: Option<Arc<ForwardSet>>,
+) -> Result<SshHandle, String> {
+    let timeout = Duration::from_secs(hop.connect_timeout.unwrap_or(10) as u64);
+
+    let ssh_config = client_config(hop);
+    let mut handler = ClientHandler::new(&hop.host, hop.port, Some(known_hosts_path.to_path_buf()));
+    handler.agent_forwarding = hop.agent_forwarding.unwrap_or(false);
+    handler.forwards = forwards;
+    let host_key = handler.host_key.clone();
+    let handshake_error = |e: russh::Error| {
+        host_key_error(&hop.host, hop.port, &host_key)
+            .unwrap_or_else(|| format!("Connection failed: {}", e))
+    };
+
+    let connecting = async {
+        match upstream {
+            None => {
+                let stream = dialer::dial(hop.proxy.as_ref(), &hop.host, hop.port).await?;
+                client::connect_stream(ssh_config, stream, handler)
+                    .await
+                    .map_err(handshake_error)
+            }
+            Some(up) => {
+                let channel = up
+                    .channel_open_direct_tcpip(hop.host.clone(), hop.port as u32, "127.0.0.1", 0)
+                    .await
+                    .map_err(|e| format!("Jump host refused tunnel: {}", e))?;
+                // 下一跳的会话任务持有上游句柄，保证跳板连接与目标连接同生共死
+                handler.upstream = Some(up);
+                client::connect_stream(ssh_config, channel.into_stream(), handler)
+                    .await
+                    .map_err(handshake_error)
+            }
+        }
+    };
+
+    let mut handle = tokio::time::timeout(timeout, connecting)
+        .await
+        .map_err(|_| format!("Connection timed out after {}s", timeout.as_secs()))??;
+
+    // 认证不计入连接超时 (需要等待用户输入验证码)
+    authenticate(&mut handle, hop, Some(prompter)).await?;
+    Ok(handle)
+}
+
+// 仅握手到密钥交换阶段，取回服务端公钥后中止 (不认证)；upstream 不为空时经跳板探测，否则按 proxy 拨号
+pub async fn fetch_host_key(
+    host: &str,
+    port: u16,
+    timeout_secs: u64,
+    upstream: Option<Arc<SshHandle>>,
+    proxy: Option<&Proxy>,
+) -> Result<key::PublicKey, String> {
+    let handler = ClientHandler::new(host, port, None);
+    let host_key = handler.host_key.clone();
+    let config = Arc::new(client::Config::default());
+
+    let probing = async {
+        match upstream {
+            None => {
+                let stream = dialer::dial(proxy, host, port).await?;
+                Ok::<_, String>(client::connect_stream(config, stream, handler).await)
+            }
+            Some(up) => {
+                let channel = up
+                    .channel_open_direct_tcpip(host.to_string(), port as u32, "127.0.0.1", 0)
+                    .await
+                    .map_err(|e| format!("Jump host refused tunnel: {}", e))?;
+                Ok(client::connect_stream(config, channel.into_stream(), handler).await)
+            }
+        }
+    };
+    let result = tokio::time::timeout(Duration::from_secs(timeout_secs), probing)
+        .await
+        .map_err(|_| format!("Connection timed out after {}s", timeout_secs))??;
+
+    let captured = host_key.lock().unwrap().take();
+    match (captured, result) {
+        (Some((key, _)), _) => Ok(key),
+        (None, Err(e)) => Err(format!("Handshake failed: {}", e)),
+        (None, Ok(_)) => Err("Server did not present a host key".to_string()),
+    }
+}
+
+pub async fn authenticate(handle: &mut SshHandle, config: &SshConfig, prompter: Option<&AuthPrompter>) -> Result<(), String> {
+    // 0. Agent 认证：逐个尝试 ssh-agent 中的身份 (硬件令牌私钥只存在于 agent 中)
+    let mut agent_error = None;
+    if config.auth_type == Some(AuthType::Agent) {
+        match agent::authenticate_with_agent(handle, &config.username).await {
+            Ok(true) => return Ok(()),
+            Ok(false) => agent_error = Some("Auth Failed: no agent identity was accepted".to_string()),
+            Err(e) => agent_error = Some(e),
+        }
+    }
+
+    // 1. 优先尝试私钥
+    if let Some(pem) = &config.private_key {
+        let key_pair = russh_keys::decode_secret_key(pem, config.passphrase.as_deref())
+            .map_err(|e| format!("Invalid private key: {}", e))?;
+        let ok = handle
+            .authenticate_publickey(&config.username, Arc::new(key_pair))
+            .await
+            .map_err(|e| e.to_string())?;
+        if ok {
+            return Ok(());
+        }
+    }
+
+    // 2. 回退到密码
+    if let Some(password) = &config.password {
+        let ok = handle
+            .authenticate_password(&config.username, password)
+            .await
+            .map_err(|e| e.to_string())?;
+        if ok {
+            return Ok(());
+        }
+    }
+
+    // 3. keyboard-interactive：OTP / Duo 等二次验证 (AuthenticationMethods publickey,keyboard-interactive
+    //    或 password,keyboard-interactive 时，前一步只是部分成功)
+    if interactive::authenticate_keyboard_interactive(handle, config, prompter).await? {
+        return Ok(());
+    }
+
+    Err(agent_error.unwrap_or_else(|| "Auth Failed: permission denied".to_string()))
 }
 
-pub async fn create_shell_channel(_config: &SshConfig) -> Result<(), String> {
-    Ok(())
+// ==============================================================================
+// 🟢 Shell 通道 (PTY + shell)
+// ==============================================================================
+
+pub async fn create_shell_channel(
+    handle: &SshHandle,
+    cols: u32,
+    rows: u32,
+    agent_forwarding: bool,
+    x11: Option<&X11Forward>,
+) -> Result<Channel<Msg>, String> {
+    let mut channel = handle
+        .channel_open_session()
+        .await
+        .map_err(|e| format!("Failed to open channel: {}", e))?;
+
+    // auth-agent-req@openssh.com：远端 Shell 中可使用本机 agent (ssh -A)
+    if agent_forwarding {
+        channel
+            .agent_forward(false)
+            .await
+            .map_err(|e| format!("Agent forwarding request failed: {}", e))?;
+    }
+
+    // x11-req：远端 sshd 据此设置 DISPLAY，之后的 X 客户端连接以 x11 通道送回
+    if let Some(x11) = x11 {
+        let (proto, cookie) = x11.fake_auth();
+        channel
+            .request_x11(false, false, proto, cookie, x11.screen)
+            .await
+            .map_err(|e| format!("X11 forwarding request failed: {}", e))?;
+    }
+
+    channel
+        .request_pty(false, "xterm-256color", cols, rows, 0, 0, &[])
+        .await
+        .map_err(|e| format!("PTY request failed: {}", e))?;
+    channel
+        .request_shell(true)
+        .await
+        .map_err(|e| format!("Shell request failed: {}", e))?;
+
+    Ok(channel)
 }
 
-pub fn spawn_shell_reader(_app: AppHandle, _id: String) {
-    // 暂时留空
+// 读写任务：独占 Channel，输出以 term-data-{id} 事件推送给前端
+pub fn spawn_shell_reader(
+    app: AppHandle,
+    id: String,
+    mut channel: Channel<Msg>,
+    mut input_rx: mpsc::UnboundedReceiver<ShellInput>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let event = format!("term-data-{}", id);
+        // 缓存被截断的 UTF-8 多字节字符，避免中文输出乱码
+        let mut pending: Vec<u8> = Vec::new();
+
+        loop {
+            tokio::select! {
+                msg = channel.wait() => match msg {
+                    Some(ChannelMsg::Data { data }) | Some(ChannelMsg::ExtendedData { data, .. }) => {
+                        pending.extend_from_slice(&data);
+                        let text = drain_utf8(&mut pending);
+                        if !text.is_empty() {
+                            let _ = app.emit(&event, text);
+                        }
+                    }
+                    Some(ChannelMsg::Eof) | Some(ChannelMsg::Close) | None => break,
+                    _ => {}
+                },
+                input = input_rx.recv() => match input {
+                    Some(ShellInput::Data(bytes)) => {
+                        if channel.data(&bytes[..]).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(ShellInput::Resize { cols, rows }) => {
+                        let _ = channel.window_change(cols, rows, 0, 0).await;
+                    }
+                    Some(ShellInput::Close) | None => {
+                        let _ = channel.eof().await;
+                        let _ = channel.close().await;
+                        break;
+                    }
+                },
+            }
+        }
+
+        if !pending.is_empty() {
+            let _ = app.emit(&event, String::from_utf8_lossy(&pending).to_string());
+        }
+        let _ = app.emit(&format!("term-exit-{}", id), ());
+    });
+}
+
+// 取出 buffer 中完整的 UTF-8 前缀，尾部残缺字节留待下一次拼接
+pub fn drain_utf8(buf: &mut Vec<u8>) -> String {
+    match std::str::from_utf8(buf) {
+        Ok(s) => {
+            let out = s.to_string();
+            buf.clear();
+            out
+        }
+        Err(e) if e.error_len().is_none() => {
+            let valid = e.valid_up_to();
+            let out = String::from_utf8_lossy(&buf[..valid]).to_string();
+            buf.drain(..valid);
+            out
+        }
+        Err(_) => {
+            let out = String::from_utf8_lossy(buf).to_string();
+            buf.clear();
+            out
+        }
+    }
+}
+
+pub async fn close_session(handle: &SshHandle) {
+    let _ = handle
+        .disconnect(Disconnect::ByApplication, "session closed", "en")
+        .await;
+}
+
+// ==============================================================================
+// 🟢 多路复用通道：SFTP 子系统 (exec 见 exec.rs，与 Shell 共用同一条连接)
+// ==============================================================================
+
+pub async fn open_sftp_session(handle: &SshHandle) -> Result<SftpSession, String> {
+    let channel = handle
+        .channel_open_session()
+        .await
+        .map_err(|e| format!("Failed to open SFTP channel: {}", e))?;
+
+    // 很多 OpenWrt 没装 sftp-server，subsystem 请求会直接失败
+    channel
+        .request_subsystem(true, "sftp")
+        .await
+        .map_err(|_| "SFTP not enabled on this server. (Please install openssh-sftp-server)".to_string())?;
+
+    tokio::time::timeout(Duration::from_secs(5), SftpSession::new(channel.into_stream()))
+        .await
+        .map_err(|_| "SFTP Connection Timed Out. (Server response slow)".to_string())?
+        .map_err(|e| format!("SFTP init failed: {}", e))
 }
@@ -0,0 +1,92 @@
+// src-tauri/src/commands/ssh/destination.rs
+// 快速连接目标解析：兼容 OpenSSH 命令行写法
+//   user@host / user@host:port / [v6]:port / ssh://user@host:port / ssh -i keyid -p 2222 user@host
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickDestination {
+    pub username: String,
+    pub host: String,
+    pub port: u16,
+    /// -i 指定的 Vault 密钥 (ID 或名称，parse_destination 命令会换成 ID)
+    pub key_id: Option<String>,
+}
+
+pub fn parse_destination(input: &str) -> Result<QuickDestination, String> {
+    let mut tokens = input.split_whitespace().peekable();
+    if tokens.peek() == Some(&"ssh") {
+        tokens.next();
+    }
+
+    let mut target: Option<&str> = None;
+    let mut key_id = None;
+    let mut port_flag: Option<u16> = None;
+    let mut user_flag: Option<String> = None;
+
+    while let Some(tok) = tokens.next() {
+        match tok {
+            "-i" => key_id = Some(tokens.next().ok_or("-i requires a key id")?.to_string()),
+            "-p" => port_flag = Some(parse_port(tokens.next().ok_or("-p requires a port")?)?),
+            "-l" => user_flag = Some(tokens.next().ok_or("-l requires a username")?.to_string()),
+            t if t.starts_with('-') => return Err(format!("Unsupported option: {}", t)),
+            t if target.is_none() => target = Some(t),
+            t => return Err(format!("Unexpected argument: {}", t)),
+        }
+    }
+
+    let raw = target.ok_or("Missing destination (user@host:port)")?;
+    let is_uri = raw.starts_with("ssh://");
+    let rest = raw.strip_prefix("ssh://").unwrap_or(raw).trim_end_matches('/');
+
+    // user@host，用户名中允许出现 @ (按最后一个 @ 切分)
+    let (user, host_port) = match rest.rsplit_once('@') {
+        Some((u, h)) if !u.is_empty() => (Some(u.to_string()), h),
+        Some(_) => return Err("Empty username".to_string()),
+        None => (None, rest),
+    };
+
+    let (host, port) = split_host_port(host_port, is_uri)?;
+    if host.is_empty() {
+        return Err("Missing host".to_string());
+    }
+
+    Ok(QuickDestination {
+        username: user_flag.or(user).unwrap_or_else(|| "root".to_string()),
+        host,
+        // 与 OpenSSH 一致：-p 优先于目标中的端口
+        port: port_flag.or(port).unwrap_or(22),
+        key_id,
+    })
+}
+
+fn parse_port(s: &str) -> Result<u16, String> {
+    match s.parse::<u16>() {
+        Ok(p) if p > 0 => Ok(p),
+        _ => Err(format!("Invalid port: {}", s)),
+    }
+}
+
+// 裸 IPv6 (多个冒号且无方括号) 视为整体主机名
+fn split_host_port(s: &str, is_uri: bool) -> Result<(String, Option<u16>), String> {
+    if let Some(inner) = s.strip_prefix('[') {
+        let (host, tail) = inner.split_once(']').ok_or("Unclosed '[' in host")?;
+        let port = match tail.strip_prefix(':') {
+            Some(p) => Some(parse_port(p)?),
+            None if tail.is_empty() => None,
+            None => return Err(format!("Invalid destination: {}", s)),
+        };
+        return Ok((host.to_string(), port));
+    }
+
+    match s.matches(':').count() {
+        0 => Ok((s.to_string(), None)),
+        1 => {
+            let (host, port) = s.split_once(':').unwrap_or((s, ""));
+            Ok((host.to_string(), Some(parse_port(port)?)))
+        }
+        _ if is_uri => Err("IPv6 addresses in ssh:// URIs must be enclosed in []".to_string()),
+        _ => Ok((s.to_string(), None)),
+    }
+}
@@ -0,0 +1,382 @@
+// src-tauri/src/commands/ssh/diagnose.rs
+// 连接诊断：DNS -> TCP (可经代理) -> SSH Banner -> KEX -> 主机密钥 -> 认证，逐阶段计时
+// 经跳板链连接时：跳板链 -> TCP (最内层跳板的 direct-tcpip 通道) -> KEX -> 主机密钥 -> 认证
+
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use aes_gcm::{Aes256Gcm, Key};
+use russh::client::{self, Msg};
+use russh::Channel;
+use russh_keys::PublicKeyBase64;
+use sqlx::{Pool, Sqlite};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+
+use super::core::{
+    authenticate, close_session, connect_jump_chain, host_key_error, load_jump_chain, ClientHandler, SshHandle,
+};
+use super::interactive::{AuthPrompter, PROMPT_TIMEOUT};
+use super::known_hosts::{compute_fingerprint, HostKeyStatus};
+use crate::commands::dialer;
+use crate::commands::proxy::internal_get_proxy;
+use crate::commands::vault::internal_get_secret;
+use crate::models::{AuthType, ConnectionType, Proxy, SshConfig, TestConnectionPayload};
+
+#[derive(serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticStage {
+    pub name: String,   // jump | dns | tcp | banner | kex | hostKey | auth
+    pub status: String, // ok | warning | failed
+    pub latency_ms: Option<u64>,
+    pub detail: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionTestReport {
+    pub success: bool,
+    /// 失败的阶段名 (成功时为空)
+    pub failed_stage: Option<String>,
+    pub error: Option<String>,
+    pub total_ms: u64,
+    pub stages: Vec<DiagnosticStage>,
+}
+
+// 逐阶段记录结果，某一阶段失败后直接生成报告
+struct Report {
+    started: Instant,
+    stages: Vec<DiagnosticStage>,
+}
+
+impl Report {
+    fn push(&mut self, name: &str, status: &str, since: Option<Instant>, detail: Option<String>) {
+        self.stages.push(DiagnosticStage {
+            name: name.to_string(),
+            status: status.to_string(),
+            latency_ms: since.map(|t| t.elapsed().as_millis() as u64),
+            detail,
+        });
+    }
+
+    fn fail(mut self, name: &str, since: Instant, error: String) -> ConnectionTestReport {
+        self.push(name, "failed", Some(since), Some(error.clone()));
+        self.finish(Some((name.to_string(), error)))
+    }
+
+    fn finish(self, failure: Option<(String, String)>) -> ConnectionTestReport {
+        let (failed_stage, error) = match failure {
+            Some((stage, err)) => (Some(stage), Some(err)),
+            None => (None, None),
+        };
+        ConnectionTestReport {
+            success: failed_stage.is_none(),
+            failed_stage,
+            error,
+            total_ms: self.started.elapsed().as_millis() as u64,
+            stages: self.stages,
+        }
+    }
+}
+
+// 目标主机的传输层：直连 / 经代理的 TCP，或跳板上的 direct-tcpip 通道
+enum Transport {
+    Tcp(TcpStream),
+    /// 通道与持有它的最内层跳板句柄
+    Tunnel(Channel<Msg>, Arc<SshHandle>),
+}
+
+async fn with_timeout<T>(limit: Duration, fut: impl std::future::Future<Output = Result<T, String>>) -> Result<T, String> {
+    tokio::time::timeout(limit, fut)
+        .await
+        .map_err(|_| format!("Timed out after {}s", limit.as_secs()))?
+}
+
+/// 按表单参数逐阶段测试连接；诊断结果总是以报告返回，只有参数本身无效时才返回 Err
+pub async fn run(
+    pool: &Pool<Sqlite>,
+    master_key: Option<&Key<Aes256Gcm>>,
+    known_hosts_path: &Path,
+    prompter: &AuthPrompter,
+    payload: TestConnectionPayload,
+) -> Result<ConnectionTestReport, String> {
+    let limit = Duration::from_secs(payload.connect_timeout.unwrap_or(10).max(1) as u64);
+    let host = payload.ip.trim().to_string();
+    let port = payload.port;
+    let mut report = Report { started: Instant::now(), stages: Vec::new() };
+
+    // 表单切回 direct 时 proxy_id 仍会保留，只有 http / socks5 才走代理，proxy 类型时指向第一台跳板服务器
+    let proxy_id = payload.proxy_id.clone().filter(|id| !id.is_empty());
+    let (proxy, first_hop): (Option<Proxy>, Option<String>) = match (&payload.connection_type, proxy_id) {
+        (Some(ConnectionType::Http) | Some(ConnectionType::Socks5), Some(pid)) => {
+            (Some(internal_get_proxy(pool, &pid).await?), None)
+        }
+        (Some(ConnectionType::Proxy), Some(bastion_id)) => (None, Some(bastion_id)),
+        _ => (None, None),
+    };
+
+    // 0. 跳板链 (逐跳握手并认证；跳板句柄随目标连接的 Handler 一起释放)
+    let mut jump_hosts = Vec::new();
+    let mut upstream = None;
+    if let Some(bastion_id) = first_hop {
+        let t = Instant::now();
+        let chain = match load_jump_chain(pool, master_key, Some(bastion_id), HashSet::new()).await {
+            Ok(chain) => chain,
+            Err(e) => return Ok(report.fail("jump", t, e)),
+        };
+        match connect_jump_chain(known_hosts_path, &chain, prompter).await {
+            Ok(up) => upstream = up,
+            Err(e) => return Ok(report.fail("jump", t, e)),
+        }
+        let route = chain.iter().map(|h| format!("{}@{}:{}", h.username, h.host, h.port)).collect::<Vec<_>>();
+        report.push("jump", "ok", Some(t), Some(route.join(" -> ")));
+        jump_hosts = chain;
+    }
+
+    let transport = match upstream {
+        Some(up) => {
+            // 1. TCP：由最内层跳板解析并连接目标 (通道无法 peek，Banner 交给握手读取)
+            let t = Instant::now();
+            let bastion = jump_hosts.last().map(|h| h.host.clone()).unwrap_or_default();
+            let channel = with_timeout(limit, async {
+                up.channel_open_direct_tcpip(host.clone(), port as u32, "127.0.0.1", 0)
+                    .await
+                    .map_err(|e| format!("Jump host {} refused tunnel to {}:{}: {}", bastion, host, port, e))
+            })
+            .await;
+            match channel {
+                Ok(channel) => {
+                    report.push("tcp", "ok", Some(t), Some(format!("Tunnel via jump host {}", bastion)));
+                    Transport::Tunnel(channel, up)
+                }
+                Err(e) => return Ok(report.fail("tcp", t, e)),
+            }
+        }
+        None => match connect_tcp(&mut report, &host, port, proxy.as_ref(), limit).await {
+            Ok(stream) => Transport::Tcp(stream),
+            Err((stage, t, e)) => return Ok(report.fail(stage, t, e)),
+        },
+    };
+
+    // 4. KEX + 主机密钥 (未记录的密钥仅给出警告，继续测试认证)
+    let t = Instant::now();
+    let mut handler = ClientHandler::new(&host, port, Some(known_hosts_path.to_path_buf()));
+    handler.accept_unknown = true;
+    let host_key = handler.host_key.clone();
+
+    let kex = match transport {
+        Transport::Tcp(stream) => key_exchange(stream, handler, limit).await,
+        Transport::Tunnel(channel, up) => {
+            handler.upstream = Some(up);
+            key_exchange(channel.into_stream(), handler, limit).await
+        }
+    };
+    let captured = host_key.lock().unwrap().clone();
+
+    let mut handle = match (kex, captured) {
+        (Ok(h), captured) => {
+            report.push("kex", "ok", Some(t), None);
+            if let Some((key, status)) = captured {
+                let fingerprint = compute_fingerprint(&key.public_key_bytes());
+                let (stage_status, detail) = match status {
+                    HostKeyStatus::Matched => ("ok", format!("{} {} (known)", key.name(), fingerprint)),
+                    _ => ("warning", format!("{} {} is not in known_hosts", key.name(), fingerprint)),
+                };
+                report.push("hostKey", stage_status, None, Some(detail));
+            }
+            h
+        }
+        // 服务端出示了公钥但被拒绝：KEX 本身已完成，失败点在主机密钥
+        (Err(e), Some(_)) => {
+            report.push("kex", "ok", Some(t), None);
+            let msg = host_key_error(&host, port, &host_key).unwrap_or(e);
+            return Ok(report.fail("hostKey", Instant::now(), msg));
+        }
+        (Err(e), None) => return Ok(report.fail("kex", t, format!("Key exchange failed: {}", e))),
+    };
+
+    // 5. 认证 (manual 使用表单明文，store 从 Vault 解密)
+    let t = Instant::now();
+    let config = match resolve_credentials(pool, master_key, &payload, jump_hosts).await {
+        Ok(c) => c,
+        Err(e) => {
+            close_session(&handle).await;
+            return Ok(report.fail("auth", t, e));
+        }
+    };
+    let method = match (&config.auth_type, &config.private_key) {
+        (Some(AuthType::Agent), _) => "ssh-agent",
+        (_, Some(_)) => "publickey",
+        _ => "password",
+    };
+    // 服务端要求二次验证时需等待用户输入，超时放宽到提示等待时长
+    let auth = with_timeout(limit + PROMPT_TIMEOUT, authenticate(&mut handle, &config, Some(prompter))).await;
+    close_session(&handle).await;
+
+    match auth {
+        Ok(()) => {
+            report.push("auth", "ok", Some(t), Some(format!("Authenticated as {} via {}", config.username, method)));
+            Ok(report.finish(None))
+        }
+        Err(e) => Ok(report.fail("auth", t, e)),
+    }
+}
+
+// 1-3. DNS -> TCP -> Banner (直连或经 HTTP / SOCKS 代理)；失败时返回 (阶段, 开始时间, 错误)
+async fn connect_tcp(
+    report: &mut Report,
+    host: &str,
+    port: u16,
+    proxy: Option<&Proxy>,
+    limit: Duration,
+) -> Result<TcpStream, (&'static str, Instant, String)> {
+    // 1. DNS (经代理时由代理解析目标主机，本地只解析代理地址)
+    let t = Instant::now();
+    let dns_host = proxy.map(|p| p.host.clone()).unwrap_or_else(|| host.to_string());
+    let dns_port = proxy.map(|p| p.port).unwrap_or(port);
+    let addrs: Vec<SocketAddr> = match with_timeout(limit, async {
+        tokio::net::lookup_host((dns_host.as_str(), dns_port))
+            .await
+            .map(|it| it.collect::<Vec<_>>())
+            .map_err(|e| format!("Cannot resolve {}: {}", dns_host, e))
+    })
+    .await
+    {
+        Ok(a) if !a.is_empty() => a,
+        Ok(_) => return Err(("dns", t, format!("{} has no addresses", dns_host))),
+        Err(e) => return Err(("dns", t, e)),
+    };
+    let resolved = addrs.iter().map(|a| a.ip().to_string()).collect::<Vec<_>>().join(", ");
+    let dns_detail = match proxy {
+        Some(p) => format!("Proxy {} -> {} (target resolved by proxy)", p.host, resolved),
+        None => format!("{} -> {}", host, resolved),
+    };
+    report.push("dns", "ok", Some(t), Some(dns_detail));
+
+    // 2. TCP (直连已解析的地址，或经代理拨号)
+    let t = Instant::now();
+    let stream = match proxy {
+        None => with_timeout(limit, async {
+            TcpStream::connect(&addrs[..])
+                .await
+                .map_err(|e| format!("TCP connect to {}:{} failed: {}", host, port, e))
+        })
+        .await,
+        Some(p) => with_timeout(limit, dialer::dial(Some(p), host, port)).await,
+    };
+    let stream = stream.map_err(|e| ("tcp", t, e))?;
+    let _ = stream.set_nodelay(true);
+    let tcp_detail = match (proxy, stream.peer_addr()) {
+        (Some(p), _) => format!("Tunnel via {} proxy {}:{}", p.proxy_type, p.host, p.port),
+        (None, Ok(peer)) => format!("Connected to {}", peer),
+        (None, Err(_)) => "Connected".to_string(),
+    };
+    report.push("tcp", "ok", Some(t), Some(tcp_detail));
+
+    // 3. Banner (peek 不消费数据，握手仍由 russh 完成)
+    let t = Instant::now();
+    let banner = with_timeout(limit, read_banner(&stream)).await.map_err(|e| ("banner", t, e))?;
+    report.push("banner", "ok", Some(t), Some(banner));
+    Ok(stream)
+}
+
+async fn key_exchange<S>(stream: S, handler: ClientHandler, limit: Duration) -> Result<SshHandle, String>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let config = Arc::new(client::Config::default());
+    with_timeout(limit, async {
+        client::connect_stream(config, stream, handler)
+            .await
+            .map_err(|e| e.to_string())
+    })
+    .await
+}
+
+// 读取服务端标识行 (SSH-2.0-xxx)；RFC 4253 允许在其之前输出若干行文本
+async fn read_banner(stream: &TcpStream) -> Result<String, String> {
+    let mut buf = vec![0u8; 4096];
+    loop {
+        let n = stream.peek(&mut buf).await.map_err(|e| format!("Read failed: {}", e))?;
+        if n == 0 {
+            return Err("Server closed the connection before sending an SSH banner".to_string());
+        }
+        let text = String::from_utf8_lossy(&buf[..n]);
+        let start = if text.starts_with("SSH-") { Some(0) } else { text.find("\nSSH-").map(|i| i + 1) };
+        if let Some(line_end) = start.and_then(|s| text[s..].find('\n').map(|e| (s, s + e))) {
+            return Ok(text[line_end.0..line_end.1].trim_end().to_string());
+        }
+        if n == buf.len() {
+            return Err("No SSH banner found (is this an SSH port?)".to_string());
+        }
+        // 标识行尚未完整到达
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+async fn resolve_credentials(
+    pool: &Pool<Sqlite>,
+    master_key: Option<&Key<Aes256Gcm>>,
+    payload: &TestConnectionPayload,
+    jump_hosts: Vec<SshConfig>,
+) -> Result<SshConfig, String> {
+    let auth_type = match payload.auth_type.as_str() {
+        "password" => AuthType::Password,
+        "agent" => AuthType::Agent,
+        _ => AuthType::PrivateKey,
+    };
+    let mut password = None;
+    let mut private_key = None;
+
+    if auth_type == AuthType::Agent {
+        // 身份由 ssh-agent 提供，无需凭证
+    } else if auth_type == AuthType::PrivateKey {
+        private_key = match (payload.key_source.as_deref(), payload.key_id.as_deref()) {
+            (Some("manual"), _) | (_, None) => payload.private_key.clone(),
+            (_, Some(kid)) => {
+                let mk = master_key.ok_or("VAULT_LOCKED")?;
+                Some(internal_get_secret(pool, mk, kid).await?)
+            }
+        };
+        if private_key.as_deref().map_or(true, str::is_empty) {
+            return Err("No private key provided".to_string());
+        }
+    } else {
+        password = match (payload.password_source.as_deref(), payload.password_id.as_deref()) {
+            (Some("manual"), _) | (_, None) => payload.password.clone(),
+            (_, Some(pid)) => {
+                let mk = master_key.ok_or("VAULT_LOCKED")?;
+                Some(internal_get_secret(pool, mk, pid).await?)
+            }
+        };
+        if password.as_deref().map_or(true, str::is_empty) {
+            return Err("No password provided".to_string());
+        }
+    }
+
+    Ok(SshConfig {
+        id: String::new(),
+        host: payload.ip.clone(),
+        port: payload.port,
+        username: payload.username.clone(),
+        password,
+        private_key,
+        passphrase: payload.passphrase.clone().filter(|p| !p.is_empty()),
+        password_id: payload.password_id.clone(),
+        password_source: payload.password_source.clone(),
+        connection_type: payload.connection_type.clone(),
+        proxy_id: payload.proxy_id.clone(),
+        jump_hosts,
+        proxy: None,
+        auth_type: Some(auth_type),
+        agent_forwarding: None,
+        x11_forwarding: None,
+        connect_timeout: payload.connect_timeout,
+        keep_alive_interval: None,
+        auto_reconnect: None,
+        max_reconnects: None,
+    })
+}
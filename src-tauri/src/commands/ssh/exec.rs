@@ -0,0 +1,193 @@
+// src-tauri/src/commands/ssh/exec.rs
+// 无 PTY 的远程命令执行：独立 exec 通道，stdout / stderr / 退出码 / 信号分开返回
+// 监控、文件操作、片段执行都基于此，不再各自拼装通道
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use russh::{ChannelMsg, Sig};
+use serde::{Deserialize, Serialize};
+
+use super::core::SshHandle;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecRequest {
+    pub command: String,
+    /// 写入远端进程的标准输入，写完即发送 EOF
+    pub stdin: Option<String>,
+    /// 以 export 前缀注入 (多数 sshd 的 AcceptEnv 只放行 LANG / LC_*，env 请求常被忽略)
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// 超时后发送 TERM 并关闭通道，已收到的输出照常返回
+    pub timeout_secs: Option<u64>,
+}
+
+impl ExecRequest {
+    pub fn new(command: impl Into<String>) -> Self {
+        Self { command: command.into(), ..Default::default() }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecOutput {
+    pub stdout: String,
+    pub stderr: String,
+    /// 进程被信号终止时为空
+    pub exit_code: Option<u32>,
+    pub signal: Option<String>,
+    pub timed_out: bool,
+    pub duration_ms: u64,
+}
+
+impl ExecOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// 失败时的简短描述 (优先使用 stderr)
+    pub fn error_message(&self) -> String {
+        let stderr = self.stderr.trim();
+        let reason = match (self.timed_out, &self.signal, self.exit_code) {
+            (true, _, _) => "timed out".to_string(),
+            (_, Some(sig), _) => format!("killed by signal {}", sig),
+            (_, _, Some(code)) => format!("exit code {}", code),
+            _ => "no exit status".to_string(),
+        };
+        if stderr.is_empty() { reason } else { format!("{} ({})", stderr, reason) }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputStream::Stdout => "stdout",
+            OutputStream::Stderr => "stderr",
+        }
+    }
+}
+
+/// 执行并收集全部输出
+pub async fn run(handle: &SshHandle, req: &ExecRequest) -> Result<ExecOutput, String> {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut output = run_streaming(handle, req, |stream, data| match stream {
+        OutputStream::Stdout => stdout.extend_from_slice(data),
+        OutputStream::Stderr => stderr.extend_from_slice(data),
+    })
+    .await?;
+    output.stdout = String::from_utf8_lossy(&stdout).to_string();
+    output.stderr = String::from_utf8_lossy(&stderr).to_string();
+    Ok(output)
+}
+
+/// 执行并把输出块交给回调 (返回值中的 stdout / stderr 为空)
+pub async fn run_streaming(
+    handle: &SshHandle,
+    req: &ExecRequest,
+    mut on_output: impl FnMut(OutputStream, &[u8]),
+) -> Result<ExecOutput, String> {
+    let started = Instant::now();
+    let command = build_command(req)?;
+    // 超时覆盖整个过程：打开通道、exec、写入 stdin 与读取输出 (服务端卡死时任何一步都可能挂起)
+    let deadline = req.timeout_secs.filter(|s| *s > 0).map(|s| tokio::time::Instant::now() + Duration::from_secs(s));
+    let mut output = ExecOutput::default();
+
+    let Some(opened) = within(deadline, handle.channel_open_session()).await else {
+        output.timed_out = true;
+        output.duration_ms = started.elapsed().as_millis() as u64;
+        return Ok(output);
+    };
+    let mut channel = opened.map_err(|e| format!("Failed to open SSH channel: {}", e))?;
+
+    let starting = async {
+        channel
+            .exec(true, command)
+            .await
+            .map_err(|e| format!("Failed to exec command: {}", e))?;
+        if let Some(input) = &req.stdin {
+            channel
+                .data(input.as_bytes())
+                .await
+                .map_err(|e| format!("Failed to write stdin: {}", e))?;
+        }
+        // 不发送 EOF 的话，读取 stdin 的命令 (cat / sh -s) 会一直等待
+        let _ = channel.eof().await;
+        Ok::<_, String>(())
+    };
+    let timed_out = match within(deadline, starting).await {
+        Some(result) => {
+            result?;
+            let collecting = async {
+                while let Some(msg) = channel.wait().await {
+                    match msg {
+                        ChannelMsg::Data { data } => on_output(OutputStream::Stdout, &data),
+                        // ext = 1 即 SSH_EXTENDED_DATA_STDERR
+                        ChannelMsg::ExtendedData { data, ext: 1 } => on_output(OutputStream::Stderr, &data),
+                        ChannelMsg::ExitStatus { exit_status } => output.exit_code = Some(exit_status),
+                        ChannelMsg::ExitSignal { signal_name, .. } => output.signal = Some(signal_label(&signal_name)),
+                        ChannelMsg::Close => break,
+                        _ => {}
+                    }
+                }
+            };
+            within(deadline, collecting).await.is_none()
+        }
+        None => true,
+    };
+    if timed_out {
+        output.timed_out = true;
+        let _ = channel.signal(Sig::TERM).await;
+        let _ = channel.close().await;
+    }
+
+    output.duration_ms = started.elapsed().as_millis() as u64;
+    Ok(output)
+}
+
+// 在截止时间前完成时返回 Some；没有截止时间时一直等待
+async fn within<F: std::future::Future>(deadline: Option<tokio::time::Instant>, fut: F) -> Option<F::Output> {
+    match deadline {
+        Some(at) => tokio::time::timeout_at(at, fut).await.ok(),
+        None => Some(fut.await),
+    }
+}
+
+// env 以 export 前缀注入；变量名只允许 [A-Za-z_][A-Za-z0-9_]*，值按单引号转义
+fn build_command(req: &ExecRequest) -> Result<String, String> {
+    if req.command.trim().is_empty() {
+        return Err("Command is empty".to_string());
+    }
+    let mut vars: Vec<_> = req.env.iter().collect();
+    vars.sort();
+
+    let mut prefix = String::new();
+    for (name, value) in vars {
+        let valid = name.chars().next().map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(format!("Invalid environment variable name: {}", name));
+        }
+        prefix.push_str(&format!("export {}={}; ", name, shell_quote(value)));
+    }
+    Ok(format!("{}{}", prefix, req.command))
+}
+
+/// POSIX shell 单引号转义
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+fn signal_label(sig: &Sig) -> String {
+    match sig {
+        Sig::Custom(name) => name.clone(),
+        other => format!("{:?}", other),
+    }
+}
@@ -0,0 +1,551 @@
+// src-tauri/src/commands/ssh/forward.rs
+// 端口转发：-L 本地 / -R 远程 (tcpip-forward) / -D 动态 SOCKS5，全部复用 SshState 中的连接
+//
+// russh 的 tcpip_forward 需要独占句柄：会话建立 (含自动重连) 时直接申请已登记的 -R 规则；
+// 运行中启动的 -R 规则先排队，再由会话任务在 channel_open_confirmation 回调里经 &mut Session 写出
+// (见 start_remote)。取消只需共享句柄，停止时直接发送 cancel-tcpip-forward
+// russh 0.40 客户端会丢弃 tcpip-forward 的 REQUEST_SUCCESS / FAILURE：-R 规则在收到第一条
+// forwarded-tcpip 连接前显示为"未确认"，不当作已运行
+// -L / -D 的监听任务每次接入新连接时取会话当前的句柄，重连后无需重启
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use russh::client::Msg;
+use russh::Channel;
+use serde::Serialize;
+use sqlx::{Pool, Sqlite};
+use tauri::async_runtime::JoinHandle;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::core::SshHandle;
+use super::state::SshConnection;
+use super::x11::X11Forward;
+use crate::models::PortForwardRule;
+
+type Sessions = Arc<Mutex<HashMap<String, SshConnection>>>;
+
+#[derive(Default)]
+pub struct TunnelStats {
+    /// 远端 -> 本地
+    pub bytes_in: AtomicU64,
+    /// 本地 -> 远端
+    pub bytes_out: AtomicU64,
+    pub active: AtomicU64,
+    pub total: AtomicU64,
+}
+
+struct ActiveTunnel {
+    rule: PortForwardRule,
+    stats: Arc<TunnelStats>,
+    /// local / dynamic 的监听任务 (remote 由服务端监听，没有本地任务)
+    listener: Option<JoinHandle<()>>,
+    /// 实际监听端口 (bind_port 为 0 时由系统分配)
+    bound_port: u16,
+    error: Option<String>,
+    /// -R 需等服务端转发进第一条连接才能确认在监听；-L / -D 绑定成功即确认
+    confirmed: bool,
+}
+
+struct RemoteRoute {
+    rule_id: String,
+    target_host: String,
+    target_port: u16,
+    stats: Arc<TunnelStats>,
+}
+
+/// 单个会话的转发状态，随 SshConnection 存活，重连后沿用
+#[derive(Default)]
+pub struct ForwardSet {
+    tunnels: Mutex<HashMap<String, ActiveTunnel>>,
+    /// Key: 远端监听端口 (forwarded-tcpip 通道按 connected_port 找到转发目标)
+    remote_routes: Mutex<HashMap<u32, RemoteRoute>>,
+    /// 运行中新增、尚未写出的 tcpip-forward 申请 (bind_host, bind_port)
+    pending_remote: Mutex<Vec<(String, u32)>>,
+    /// X11 转发参数 (服务器开启 -X 且本机有 DISPLAY 时存在)
+    x11: Option<Arc<X11Forward>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TunnelStatus {
+    pub rule_id: String,
+    pub name: String,
+    pub kind: String,
+    pub bind_host: String,
+    pub bind_port: u16,
+    pub target_host: Option<String>,
+    pub target_port: Option<u16>,
+    pub running: bool,
+    /// false：-R 已申请但服务端是否在监听未知 (拒绝时也不会有回应)
+    pub confirmed: bool,
+    pub active_connections: u64,
+    pub total_connections: u64,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub error: Option<String>,
+}
+
+impl ForwardSet {
+    pub fn new(x11: Option<X11Forward>) -> Self {
+        Self { x11: x11.map(Arc::new), ..Default::default() }
+    }
+
+    pub fn x11(&self) -> Option<Arc<X11Forward>> {
+        self.x11.clone()
+    }
+
+    pub fn is_running(&self, rule_id: &str) -> bool {
+        self.tunnels
+            .lock()
+            .unwrap()
+            .get(rule_id)
+            .map_or(false, |t| t.error.is_none())
+    }
+
+    pub fn status(&self) -> Vec<TunnelStatus> {
+        let tunnels = self.tunnels.lock().unwrap();
+        let mut list: Vec<TunnelStatus> = tunnels
+            .values()
+            .map(|t| TunnelStatus {
+                rule_id: t.rule.id.clone(),
+                name: t.rule.name.clone(),
+                kind: t.rule.kind.clone(),
+                bind_host: t.rule.bind_host.clone(),
+                bind_port: t.bound_port,
+                target_host: t.rule.target_host.clone(),
+                target_port: t.rule.target_port,
+                running: t.error.is_none() && t.confirmed,
+                confirmed: t.confirmed,
+                active_connections: t.stats.active.load(Ordering::Relaxed),
+                total_connections: t.stats.total.load(Ordering::Relaxed),
+                bytes_in: t.stats.bytes_in.load(Ordering::Relaxed),
+                bytes_out: t.stats.bytes_out.load(Ordering::Relaxed),
+                error: t.error.clone(),
+            })
+            .collect();
+        list.sort_by(|a, b| a.name.cmp(&b.name));
+        list
+    }
+
+    /// 登记 -R 规则，下次 request_remote_forwards 时向服务端申请
+    pub fn register_remote(&self, rule: PortForwardRule) {
+        let stats = Arc::new(TunnelStats::default());
+        self.remote_routes.lock().unwrap().insert(
+            rule.bind_port as u32,
+            RemoteRoute {
+                rule_id: rule.id.clone(),
+                target_host: rule.target_host.clone().unwrap_or_default(),
+                target_port: rule.target_port.unwrap_or(0),
+                stats: stats.clone(),
+            },
+        );
+        let bound_port = rule.bind_port;
+        let tunnel = ActiveTunnel { rule, stats, listener: None, bound_port, error: None, confirmed: false };
+        self.tunnels.lock().unwrap().insert(tunnel.rule.id.clone(), tunnel);
+    }
+
+    /// 取出待申请的 -R 监听 (由会话任务在 Handler 回调中调用)
+    pub fn take_remote_requests(&self) -> Vec<(String, u32)> {
+        std::mem::take(&mut *self.pending_remote.lock().unwrap())
+    }
+
+    /// 停止单条转发；-R 同时向服务端取消监听
+    pub fn stop(&self, handle: &Arc<SshHandle>, rule_id: &str) -> bool {
+        let Some(tunnel) = self.tunnels.lock().unwrap().remove(rule_id) else {
+            return false;
+        };
+        self.release(handle, tunnel);
+        true
+    }
+
+    pub fn stop_all(&self, handle: &Arc<SshHandle>) {
+        let tunnels: Vec<ActiveTunnel> = self.tunnels.lock().unwrap().drain().map(|(_, t)| t).collect();
+        for tunnel in tunnels {
+            self.release(handle, tunnel);
+        }
+        self.pending_remote.lock().unwrap().clear();
+    }
+
+    fn release(&self, handle: &Arc<SshHandle>, tunnel: ActiveTunnel) {
+        if let Some(task) = &tunnel.listener {
+            task.abort();
+        }
+        if tunnel.rule.kind != "remote" {
+            return;
+        }
+        let port = tunnel.rule.bind_port as u32;
+        self.remote_routes.lock().unwrap().remove(&port);
+        self.pending_remote.lock().unwrap().retain(|(_, p)| *p != port);
+        // 申请失败的规则在服务端没有监听，无需取消
+        if tunnel.error.is_some() {
+            return;
+        }
+        let handle = handle.clone();
+        let host = tunnel.rule.bind_host;
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = handle.cancel_tcpip_forward(host.clone(), port).await {
+                eprintln!("[forward] cancel {}:{} failed: {}", host, port, e);
+            }
+        });
+    }
+}
+
+// ==============================================================================
+// 🟢 规则存取 (port_forwards 表)
+// ==============================================================================
+
+const RULE_COLUMNS: &str =
+    "id, server_id, name, kind, bind_host, bind_port, target_host, target_port, auto_start, created_at, updated_at";
+
+pub async fn list_rules(pool: &Pool<Sqlite>, server_id: &str) -> Result<Vec<PortForwardRule>, String> {
+    sqlx::query_as::<_, PortForwardRule>(&format!(
+        "SELECT {} FROM port_forwards WHERE server_id = ? ORDER BY created_at ASC",
+        RULE_COLUMNS
+    ))
+    .bind(server_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn get_rule(pool: &Pool<Sqlite>, id: &str) -> Result<PortForwardRule, String> {
+    sqlx::query_as::<_, PortForwardRule>(&format!("SELECT {} FROM port_forwards WHERE id = ?", RULE_COLUMNS))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Port forward {} not found", id))
+}
+
+pub async fn save_rule(pool: &Pool<Sqlite>, rule: &PortForwardRule) -> Result<(), String> {
+    sqlx::query(&format!(
+        "INSERT OR REPLACE INTO port_forwards ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        RULE_COLUMNS
+    ))
+    .bind(&rule.id)
+    .bind(&rule.server_id)
+    .bind(&rule.name)
+    .bind(&rule.kind)
+    .bind(&rule.bind_host)
+    .bind(rule.bind_port)
+    .bind(&rule.target_host)
+    .bind(rule.target_port)
+    .bind(rule.auto_start)
+    .bind(rule.created_at)
+    .bind(rule.updated_at)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn delete_rule(pool: &Pool<Sqlite>, id: &str) -> Result<(), String> {
+    sqlx::query("DELETE FROM port_forwards WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 校验并补全规则 (bind_host 为空时只监听回环地址)
+pub fn normalize_rule(rule: &mut PortForwardRule) -> Result<(), String> {
+    rule.bind_host = rule.bind_host.trim().to_string();
+    if rule.bind_host.is_empty() {
+        rule.bind_host = "127.0.0.1".to_string();
+    }
+    match rule.kind.as_str() {
+        "local" | "remote" => {
+            let host_ok = rule.target_host.as_deref().map_or(false, |h| !h.trim().is_empty());
+            if !host_ok || rule.target_port.unwrap_or(0) == 0 {
+                return Err("Target host and port are required".to_string());
+            }
+        }
+        "dynamic" => {
+            rule.target_host = None;
+            rule.target_port = None;
+        }
+        other => return Err(format!("Unknown forward type: {}", other)),
+    }
+    // 服务端分配的端口无法与 forwarded-tcpip 通道对应
+    if rule.kind == "remote" && rule.bind_port == 0 {
+        return Err("Remote forwards require a fixed port".to_string());
+    }
+    Ok(())
+}
+
+// ==============================================================================
+// 🟢 -R：向服务端申请监听，接收 forwarded-tcpip 通道
+// ==============================================================================
+
+/// 为已登记的 -R 规则申请 tcpip-forward，返回失败信息 (单条失败不影响会话)
+pub async fn request_remote_forwards(handle: &mut SshHandle, forwards: &ForwardSet) -> Vec<String> {
+    // 旧连接上排队的申请随之作废，下面会全部重新申请
+    forwards.pending_remote.lock().unwrap().clear();
+    let rules: Vec<PortForwardRule> = forwards
+        .tunnels
+        .lock()
+        .unwrap()
+        .values()
+        .filter(|t| t.rule.kind == "remote")
+        .map(|t| t.rule.clone())
+        .collect();
+
+    let mut errors = Vec::new();
+    for rule in rules {
+        // 固定端口时 tcpip_forward 只负责发出申请，服务端拒绝只能表现为一直没有转发连接
+        let error = handle
+            .tcpip_forward(rule.bind_host.clone(), rule.bind_port as u32)
+            .await
+            .err()
+            .map(|e| format!("Remote forward {}:{} failed: {}", rule.bind_host, rule.bind_port, e));
+        if let Some(tunnel) = forwards.tunnels.lock().unwrap().get_mut(&rule.id) {
+            tunnel.error = error.clone();
+            tunnel.confirmed = false;
+        }
+        if let Some(e) = error {
+            errors.push(format!("{}: {}", rule.name, e));
+        }
+    }
+    errors
+}
+
+/// 在已建立的会话上启动 -R 规则：申请排队后打开一个空的 session 通道，
+/// 服务端确认时会话任务执行 Handler::channel_open_confirmation，在那里写出 tcpip-forward
+pub async fn start_remote(handle: &SshHandle, forwards: &ForwardSet, rule: PortForwardRule) -> Result<(), String> {
+    let (rule_id, host, port) = (rule.id.clone(), rule.bind_host.clone(), rule.bind_port as u32);
+    forwards.register_remote(rule);
+    forwards.pending_remote.lock().unwrap().push((host.clone(), port));
+
+    let result = match handle.channel_open_session().await {
+        Ok(channel) => {
+            let _ = channel.close().await;
+            Ok(())
+        }
+        Err(e) => Err(format!("Remote forward {}:{} failed: {}", host, port, e)),
+    };
+    if let Err(e) = &result {
+        forwards.pending_remote.lock().unwrap().retain(|(_, p)| *p != port);
+        if let Some(tunnel) = forwards.tunnels.lock().unwrap().get_mut(&rule_id) {
+            tunnel.error = Some(e.clone());
+        }
+    }
+    result
+}
+
+/// 服务端转发进来的连接：按监听端口找到目标并在本机发起连接
+pub fn accept_forwarded(forwards: &ForwardSet, channel: Channel<Msg>, connected_port: u32) {
+    let route = forwards
+        .remote_routes
+        .lock()
+        .unwrap()
+        .get(&connected_port)
+        .map(|r| (r.rule_id.clone(), r.target_host.clone(), r.target_port, r.stats.clone()));
+    // 服务端转发进连接，说明监听确实已建立
+    if let Some((rule_id, ..)) = &route {
+        if let Some(tunnel) = forwards.tunnels.lock().unwrap().get_mut(rule_id) {
+            tunnel.confirmed = true;
+        }
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let Some((_, host, port, stats)) = route else {
+            let _ = channel.close().await;
+            return;
+        };
+        match TcpStream::connect((host.as_str(), port)).await {
+            Ok(local) => {
+                let _ = local.set_nodelay(true);
+                relay(local, channel.into_stream(), stats).await;
+            }
+            Err(e) => {
+                eprintln!("[forward] {}:{} unreachable: {}", host, port, e);
+                let _ = channel.close().await;
+            }
+        }
+    });
+}
+
+// ==============================================================================
+// 🟢 -L / -D：本机监听，经 direct-tcpip 通道转发
+// ==============================================================================
+
+/// 启动本地监听，返回实际监听端口
+pub async fn start_listener(
+    sessions: Sessions,
+    session_id: String,
+    forwards: Arc<ForwardSet>,
+    rule: PortForwardRule,
+) -> Result<u16, String> {
+    let listener = TcpListener::bind((rule.bind_host.as_str(), rule.bind_port))
+        .await
+        .map_err(|e| format!("Cannot listen on {}:{}: {}", rule.bind_host, rule.bind_port, e))?;
+    let bound_port = listener.local_addr().map(|a| a.port()).unwrap_or(rule.bind_port);
+
+    let stats = Arc::new(TunnelStats::default());
+    let dynamic = rule.kind == "dynamic";
+    let target_host = rule.target_host.clone().unwrap_or_default();
+    let target_port = rule.target_port.unwrap_or(0);
+    let task_stats = stats.clone();
+
+    let task = tauri::async_runtime::spawn(async move {
+        while let Ok((socket, peer)) = listener.accept().await {
+            // 会话已关闭：监听随之结束
+            let Some(handle) = current_handle(&sessions, &session_id) else {
+                break;
+            };
+            let stats = task_stats.clone();
+            let target_host = target_host.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = socket.set_nodelay(true);
+                let result = if dynamic {
+                    socks5_accept(socket, &handle, peer, stats).await
+                } else {
+                    match open_direct(&handle, &target_host, target_port, peer).await {
+                        Ok(channel) => {
+                            relay(socket, channel.into_stream(), stats).await;
+                            Ok(())
+                        }
+                        Err(e) => Err(e),
+                    }
+                };
+                if let Err(e) = result {
+                    eprintln!("[forward] {}", e);
+                }
+            });
+        }
+    });
+
+    forwards.tunnels.lock().unwrap().insert(
+        rule.id.clone(),
+        ActiveTunnel { rule, stats, listener: Some(task), bound_port, error: None, confirmed: true },
+    );
+    Ok(bound_port)
+}
+
+fn current_handle(sessions: &Sessions, id: &str) -> Option<Arc<SshHandle>> {
+    sessions.lock().unwrap().get(id).map(|c| c.handle.clone())
+}
+
+async fn open_direct(handle: &SshHandle, host: &str, port: u16, peer: SocketAddr) -> Result<Channel<Msg>, String> {
+    handle
+        .channel_open_direct_tcpip(host.to_string(), port as u32, peer.ip().to_string(), peer.port() as u32)
+        .await
+        .map_err(|e| format!("Tunnel to {}:{} refused: {}", host, port, e))
+}
+
+// SOCKS5 (RFC 1928) 服务端：仅支持无认证 + CONNECT，目标地址交给 SSH 服务端解析
+async fn socks5_accept(
+    mut socket: TcpStream,
+    handle: &SshHandle,
+    peer: SocketAddr,
+    stats: Arc<TunnelStats>,
+) -> Result<(), String> {
+    let io = |e: std::io::Error| format!("SOCKS handshake failed: {}", e);
+
+    let mut head = [0u8; 2];
+    socket.read_exact(&mut head).await.map_err(io)?;
+    if head[0] != 5 {
+        return Err("Only SOCKS5 clients are supported".to_string());
+    }
+    let mut methods = vec![0u8; head[1] as usize];
+    socket.read_exact(&mut methods).await.map_err(io)?;
+    if !methods.contains(&0) {
+        let _ = socket.write_all(&[5, 0xff]).await;
+        return Err("SOCKS client requires authentication".to_string());
+    }
+    socket.write_all(&[5, 0]).await.map_err(io)?;
+
+    let mut req = [0u8; 4];
+    socket.read_exact(&mut req).await.map_err(io)?;
+    let host = match req[3] {
+        1 => {
+            let mut addr = [0u8; 4];
+            socket.read_exact(&mut addr).await.map_err(io)?;
+            Ipv4Addr::from(addr).to_string()
+        }
+        3 => {
+            let mut len = [0u8; 1];
+            socket.read_exact(&mut len).await.map_err(io)?;
+            let mut name = vec![0u8; len[0] as usize];
+            socket.read_exact(&mut name).await.map_err(io)?;
+            String::from_utf8_lossy(&name).to_string()
+        }
+        4 => {
+            let mut addr = [0u8; 16];
+            socket.read_exact(&mut addr).await.map_err(io)?;
+            Ipv6Addr::from(addr).to_string()
+        }
+        _ => {
+            let _ = socks_reply(&mut socket, 0x08).await;
+            return Err("Unsupported SOCKS address type".to_string());
+        }
+    };
+    let mut port = [0u8; 2];
+    socket.read_exact(&mut port).await.map_err(io)?;
+    let port = u16::from_be_bytes(port);
+
+    if req[1] != 1 {
+        let _ = socks_reply(&mut socket, 0x07).await;
+        return Err("Only SOCKS CONNECT is supported".to_string());
+    }
+
+    let channel = match open_direct(handle, &host, port, peer).await {
+        Ok(c) => c,
+        Err(e) => {
+            let _ = socks_reply(&mut socket, 0x05).await;
+            return Err(e);
+        }
+    };
+    socks_reply(&mut socket, 0x00).await.map_err(io)?;
+    relay(socket, channel.into_stream(), stats).await;
+    Ok(())
+}
+
+async fn socks_reply(socket: &mut TcpStream, code: u8) -> std::io::Result<()> {
+    socket.write_all(&[5, code, 0, 1, 0, 0, 0, 0, 0, 0]).await
+}
+
+// ==============================================================================
+// 🟢 双向转发 + 流量统计
+// ==============================================================================
+
+async fn relay<L, R>(local: L, remote: R, stats: Arc<TunnelStats>)
+where
+    L: AsyncRead + AsyncWrite + Unpin + Send,
+    R: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    stats.active.fetch_add(1, Ordering::Relaxed);
+    stats.total.fetch_add(1, Ordering::Relaxed);
+
+    let (local_rd, local_wr) = tokio::io::split(local);
+    let (remote_rd, remote_wr) = tokio::io::split(remote);
+    // 两个方向独立结束，保留半关闭语义 (如 HTTP 请求发送完毕后仍需读取响应)
+    let _ = tokio::join!(
+        pump(local_rd, remote_wr, &stats.bytes_out),
+        pump(remote_rd, local_wr, &stats.bytes_in),
+    );
+
+    stats.active.fetch_sub(1, Ordering::Relaxed);
+}
+
+async fn pump<R, W>(mut reader: R, mut writer: W, counter: &AtomicU64) -> std::io::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut buf = vec![0u8; 32 * 1024];
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        writer.write_all(&buf[..n]).await?;
+        counter.fetch_add(n as u64, Ordering::Relaxed);
+    }
+    writer.shutdown().await
+}
@@ -0,0 +1,147 @@
+// src-tauri/src/commands/ssh/interactive.rs
+// keyboard-interactive 认证 (RFC 4256)：服务端提示 (OTP / Duo / Verification code) 以 ssh-auth-prompt 事件推送给前端，
+// 用户输入经 respond_auth_prompt 命令回传
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use russh::client::{KeyboardInteractiveAuthResponse, Prompt};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::oneshot;
+
+use super::core::SshHandle;
+use super::state::SshState;
+use crate::models::SshConfig;
+
+/// 等待用户输入验证码的最长时间
+pub const PROMPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Key: request_id；None 表示用户取消
+pub type PendingPrompts = Arc<Mutex<HashMap<String, oneshot::Sender<Option<Vec<String>>>>>>;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthPromptField {
+    pub prompt: String,
+    /// false 时前端按密码框显示
+    pub echo: bool,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthPromptRequest {
+    pub request_id: String,
+    pub session_id: String,
+    pub host: String,
+    pub username: String,
+    pub name: String,
+    pub instructions: String,
+    pub prompts: Vec<AuthPromptField>,
+}
+
+/// 将服务端提示转交前端的通道 (每次连接 / 重连 / 诊断各持有一个)
+pub struct AuthPrompter {
+    app: AppHandle,
+    session_id: String,
+}
+
+impl AuthPrompter {
+    pub fn new(app: &AppHandle, session_id: &str) -> Self {
+        Self { app: app.clone(), session_id: session_id.to_string() }
+    }
+
+    async fn ask(&self, hop: &SshConfig, name: String, instructions: String, prompts: &[Prompt]) -> Result<Vec<String>, String> {
+        let pending = self.app.state::<SshState>().auth_prompts.clone();
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let (tx, rx) = oneshot::channel();
+        pending.lock().unwrap().insert(request_id.clone(), tx);
+
+        let request = AuthPromptRequest {
+            request_id: request_id.clone(),
+            session_id: self.session_id.clone(),
+            host: format!("{}:{}", hop.host, hop.port),
+            username: hop.username.clone(),
+            name,
+            instructions,
+            prompts: prompts
+                .iter()
+                .map(|p| AuthPromptField { prompt: p.prompt.clone(), echo: p.echo })
+                .collect(),
+        };
+        let _ = self.app.emit("ssh-auth-prompt", request);
+
+        let answered = tokio::time::timeout(PROMPT_TIMEOUT, rx).await;
+        pending.lock().unwrap().remove(&request_id);
+        // 超时后通知前端关闭对应的输入框
+        let _ = self.app.emit("ssh-auth-prompt-closed", &request_id);
+
+        match answered {
+            Ok(Ok(Some(responses))) if responses.len() == prompts.len() => Ok(responses),
+            Ok(Ok(Some(_))) => Err("Auth Failed: wrong number of responses".to_string()),
+            Ok(Ok(None)) | Ok(Err(_)) => Err("Auth Failed: verification cancelled".to_string()),
+            Err(_) => Err(format!("Auth Failed: no verification code entered within {}s", PROMPT_TIMEOUT.as_secs())),
+        }
+    }
+}
+
+/// 前端提交 (或取消) 某次提示的答案
+pub fn resolve_prompt(pending: &PendingPrompts, request_id: &str, responses: Option<Vec<String>>) -> Result<(), String> {
+    let tx = pending
+        .lock()
+        .unwrap()
+        .remove(request_id)
+        .ok_or("Verification prompt expired")?;
+    tx.send(responses).map_err(|_| "Verification prompt expired".to_string())
+}
+
+/// keyboard-interactive 认证循环；服务端未启用该方式时返回 Ok(false)
+///
+/// 常见的 PAM 配置会先询问 "Password:" 再询问 "Verification code:"，
+/// 已保存的密码会自动填入第一次密码提示，其余提示交给用户输入
+pub async fn authenticate_keyboard_interactive(
+    handle: &mut SshHandle,
+    config: &SshConfig,
+    prompter: Option<&AuthPrompter>,
+) -> Result<bool, String> {
+    let mut password_sent = false;
+    let mut response = handle
+        .authenticate_keyboard_interactive_start(config.username.clone(), None::<String>)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    loop {
+        let answers = match response {
+            KeyboardInteractiveAuthResponse::Success => return Ok(true),
+            KeyboardInteractiveAuthResponse::Failure => return Ok(false),
+            // 空的 InfoRequest 只需回应空列表 (部分服务端用它结束多轮对话)
+            KeyboardInteractiveAuthResponse::InfoRequest { prompts, .. } if prompts.is_empty() => Vec::new(),
+            KeyboardInteractiveAuthResponse::InfoRequest { name, instructions, prompts } => {
+                match saved_password_answer(config, &prompts, password_sent) {
+                    Some(password) => {
+                        password_sent = true;
+                        vec![password]
+                    }
+                    None => match prompter {
+                        Some(p) => p.ask(config, name, instructions, &prompts).await?,
+                        None => return Err("Auth Failed: server requires interactive verification".to_string()),
+                    },
+                }
+            }
+        };
+        response = handle
+            .authenticate_keyboard_interactive_respond(answers)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+}
+
+// 单个不回显的 "Password" 提示：用已保存的密码作答 (只答一次，避免密码错误时反复重试)
+fn saved_password_answer(config: &SshConfig, prompts: &[Prompt], password_sent: bool) -> Option<String> {
+    let password = config.password.as_ref().filter(|_| !password_sent)?;
+    match prompts {
+        [p] if !p.echo && p.prompt.to_lowercase().contains("password") => Some(password.clone()),
+        _ => None,
+    }
+}
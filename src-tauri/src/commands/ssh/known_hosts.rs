@@ -0,0 +1,263 @@
+// src-tauri/src/commands/ssh/known_hosts.rs
+// OpenSSH known_hosts 解析与写入 (支持 |1| 哈希主机名、[host]:port、@revoked)
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use hmac::{Hmac, Mac};
+use russh_keys::key::PublicKey;
+use russh_keys::PublicKeyBase64;
+use sha1::Sha1;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// 主机密钥校验结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostKeyStatus {
+    /// known_hosts 中没有该主机的同类型密钥 (首次连接)
+    Unknown,
+    /// 与已记录的密钥一致
+    Matched,
+    /// 主机已记录了同类型的其他密钥 (可能遭遇中间人攻击)
+    Changed { old_fingerprint: String },
+    /// 该密钥被 @revoked 标记
+    Revoked,
+}
+
+// 辅助函数：获取 known_hosts 路径
+pub fn get_known_hosts_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path().home_dir().ok().map(|p| p.join(".ssh").join("known_hosts"))
+}
+
+// 辅助函数：计算指纹 (SHA256 Base64，与 ssh-keygen -l 输出一致，不带填充)
+pub fn compute_fingerprint(host_key: &[u8]) -> String {
+    use sha2::{Sha256, Digest};
+    let mut hasher = Sha256::new();
+    hasher.update(host_key);
+    let result = hasher.finalize();
+    format!("SHA256:{}", BASE64.encode(result).trim_end_matches('='))
+}
+
+/// 把非 Matched 的校验结果转换为可读错误 (附带新旧指纹)
+pub fn rejection_message(host: &str, port: u16, key: &PublicKey, status: &HostKeyStatus) -> Option<String> {
+    let fingerprint = compute_fingerprint(&key.public_key_bytes());
+
+    match status {
+        HostKeyStatus::Matched => None,
+        HostKeyStatus::Unknown => Some(format!(
+            "HOST_KEY_UNKNOWN: {}:{} presented {} {} which is not in known_hosts",
+            host, port, key.name(), fingerprint
+        )),
+        HostKeyStatus::Changed { old_fingerprint } => Some(format!(
+            "HOST_KEY_CHANGED: {}:{} {} key changed (expected {}, got {}). Possible man-in-the-middle attack, connection blocked.",
+            host, port, key.name(), old_fingerprint, fingerprint
+        )),
+        HostKeyStatus::Revoked => Some(format!(
+            "HOST_KEY_REVOKED: {}:{} presented a revoked key ({})",
+            host, port, fingerprint
+        )),
+    }
+}
+
+// known_hosts 中的主机名：22 端口直接写 host，其余写 [host]:port
+fn lookup_name(host: &str, port: u16) -> String {
+    if port == 22 {
+        host.to_string()
+    } else {
+        format!("[{}]:{}", host, port)
+    }
+}
+
+struct Entry<'a> {
+    marker: Option<&'a str>,
+    hosts: &'a str,
+    key_type: &'a str,
+    key_blob: Vec<u8>,
+}
+
+fn parse_line(line: &str) -> Option<Entry<'_>> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let mut fields = line.split_whitespace();
+    let mut first = fields.next()?;
+    let marker = if first.starts_with('@') {
+        let m = first;
+        first = fields.next()?;
+        Some(m)
+    } else {
+        None
+    };
+
+    let key_type = fields.next()?;
+    let key_blob = BASE64.decode(fields.next()?).ok()?;
+    Some(Entry { marker, hosts: first, key_type, key_blob })
+}
+
+// 简单通配符匹配 (* 与 ?)
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.to_lowercase().chars().collect();
+    let t: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let (mut star, mut mark) = (None, 0);
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some(pi);
+            mark = ti;
+            pi += 1;
+        } else if let Some(s) = star {
+            pi = s + 1;
+            mark += 1;
+            ti = mark;
+        } else {
+            return false;
+        }
+    }
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+    pi == p.len()
+}
+
+// 哈希主机名：|1|base64(salt)|base64(HMAC-SHA1(salt, name))
+fn hashed_match(pattern: &str, name: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('|').collect();
+    if parts.len() != 4 || parts[1] != "1" {
+        return false;
+    }
+    let (Ok(salt), Ok(hash)) = (BASE64.decode(parts[2]), BASE64.decode(parts[3])) else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha1::new_from_slice(&salt) else {
+        return false;
+    };
+    mac.update(name.as_bytes());
+    mac.verify_slice(&hash).is_ok()
+}
+
+// 主机字段匹配：逗号分隔，支持 !negation，命中任一否定模式即视为不匹配
+fn hosts_match(hosts: &str, name: &str) -> bool {
+    if hosts.starts_with("|1|") {
+        return hashed_match(hosts, name);
+    }
+
+    let mut matched = false;
+    for pattern in hosts.split(',') {
+        if let Some(neg) = pattern.strip_prefix('!') {
+            if wildcard_match(neg, name) {
+                return false;
+            }
+        } else if wildcard_match(pattern, name) {
+            matched = true;
+        }
+    }
+    matched
+}
+
+/// 按 OpenSSH 语义校验主机密钥
+pub fn check(path: &Path, host: &str, port: u16, key: &PublicKey) -> Result<HostKeyStatus, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HostKeyStatus::Unknown),
+        Err(e) => return Err(format!("Failed to read known_hosts: {}", e)),
+    };
+
+    let name = lookup_name(host, port);
+    let key_type = key.name();
+    let blob = key.public_key_bytes();
+    let mut status = HostKeyStatus::Unknown;
+
+    for entry in content.lines().filter_map(parse_line) {
+        match entry.marker {
+            // 被吊销的密钥无论主机名如何都拒绝
+            Some("@revoked") => {
+                if entry.key_blob == blob {
+                    return Ok(HostKeyStatus::Revoked);
+                }
+            }
+            Some(_) => continue, // @cert-authority 暂不支持
+            None => {
+                if !hosts_match(entry.hosts, &name) {
+                    continue;
+                }
+                if entry.key_blob == blob {
+                    status = HostKeyStatus::Matched;
+                } else if entry.key_type == key_type && status == HostKeyStatus::Unknown {
+                    status = HostKeyStatus::Changed {
+                        old_fingerprint: compute_fingerprint(&entry.key_blob),
+                    };
+                }
+            }
+        }
+    }
+
+    Ok(status)
+}
+
+// 多主机记录 (a,b,10.0.0.1 ...) 只移除与 name 相同的模式，返回保留其余模式的新行；
+// 哈希记录只对应一个主机，或没有剩余的肯定模式时整行移除 (返回 None)
+fn drop_host_pattern(line: &str, hosts: &str, name: &str) -> Option<String> {
+    if hosts.starts_with("|1|") {
+        return None;
+    }
+    let remaining: Vec<&str> = hosts.split(',').filter(|p| !p.eq_ignore_ascii_case(name)).collect();
+    if !remaining.iter().any(|p| !p.starts_with('!')) {
+        return None;
+    }
+    let rest = line.trim_start().strip_prefix(hosts)?;
+    Some(format!("{}{}", remaining.join(","), rest))
+}
+
+/// 信任主机密钥：移除该主机同类型的旧记录后追加新记录
+pub fn learn(path: &Path, host: &str, port: u16, key: &PublicKey) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create ~/.ssh: {}", e))?;
+    }
+
+    let name = lookup_name(host, port);
+    let key_type = key.name();
+
+    if let Ok(content) = fs::read_to_string(path) {
+        let mut removed = false;
+        let mut kept: Vec<String> = Vec::new();
+        for line in content.lines() {
+            match parse_line(line) {
+                Some(e) if e.marker.is_none() && e.key_type == key_type && hosts_match(e.hosts, &name) => {
+                    removed = true;
+                    kept.extend(drop_host_pattern(line, e.hosts, &name));
+                }
+                _ => kept.push(line.to_string()),
+            }
+        }
+
+        if removed {
+            let mut rewritten = kept.join("\n");
+            rewritten.push('\n');
+            fs::write(path, rewritten).map_err(|e| format!("Failed to write known_hosts: {}", e))?;
+        }
+    }
+
+    // 原文件末尾没有换行时先补一个，避免新记录接在上一行后面
+    let needs_newline = fs::read(path)
+        .map(|b| !b.is_empty() && !b.ends_with(b"\n"))
+        .unwrap_or(false);
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open known_hosts: {}", e))?;
+    if needs_newline {
+        writeln!(file).map_err(|e| format!("Failed to write known_hosts: {}", e))?;
+    }
+    writeln!(file, "{} {} {}", name, key_type, key.public_key_base64())
+        .map_err(|e| format!("Failed to write known_hosts: {}", e))
+}
@@ -1,20 +1,97 @@
-use ssh2::{Channel, Session};
+use russh_keys::key::PublicKey;
+use russh_sftp::client::SftpSession;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use tauri::async_runtime::JoinHandle;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::broadcast::BroadcastGroups;
+use super::core::{open_sftp_session, ShellInput, SshHandle};
+use super::forward::ForwardSet;
+use super::interactive::PendingPrompts;
 
 /// 管理 SSH 连接状态
+/// 每台服务器只有一条认证过的 russh 连接，Shell / SFTP / 监控都是其上的多路复用通道
 pub struct SshConnection {
-    /// Shell 专用 (非阻塞，有读取线程在跑)
-    pub shell_channel: Arc<Mutex<Channel>>,
-
-    /// Monitor 专用 (阻塞，空闲状态，随时可用)
-    /// Option 用于容错，允许监控连接建立失败
-    pub monitor_session: Arc<Mutex<Option<Session>>>,
-    // 2. [新增] SFTP 专用 Session (文件管理)
-    pub sftp_session: Arc<Mutex<Option<Session>>>,
+    /// russh 连接句柄 (打开 exec / subsystem 通道，断开时发送 Disconnect)
+    pub handle: Arc<SshHandle>,
+
+    /// Shell 输入通道 (键盘数据 / 窗口尺寸交给读写任务写入 russh Channel)
+    pub shell_tx: UnboundedSender<ShellInput>,
+
+    /// SFTP 子系统通道 (首次使用时懒加载，失败不影响 Shell)
+    pub sftp_session: Arc<tokio::sync::Mutex<Option<Arc<SftpSession>>>>,
+
+    /// 当前终端尺寸 (cols, rows)，重连后按此重新申请 PTY
+    pub pty_size: (u32, u32),
+
+    /// 断线重连守护任务
+    pub supervisor: Option<JoinHandle<()>>,
+
+    /// 端口转发 (-L / -R / -D)，重连后沿用
+    pub forwards: Arc<ForwardSet>,
+}
+
+impl SshConnection {
+    /// 停止守护任务、端口转发并关闭 Shell (连接本身由调用方断开)
+    pub fn shutdown(&self) {
+        if let Some(task) = &self.supervisor {
+            task.abort();
+        }
+        self.forwards.stop_all(&self.handle);
+        let _ = self.shell_tx.send(ShellInput::Close);
+    }
+}
+
+/// check_host_key 探测到、等待用户确认的主机公钥
+pub struct PendingHostKey {
+    pub host: String,
+    pub port: u16,
+    pub key: PublicKey,
 }
 
 #[derive(Default)]
 pub struct SshState {
     pub sessions: Arc<Mutex<HashMap<String, SshConnection>>>,
+    /// Key: 服务器 ID (trust_host_key 只写入此处缓存的公钥，防止前端伪造指纹)
+    pub pending_host_keys: Arc<Mutex<HashMap<String, PendingHostKey>>>,
+    /// keyboard-interactive 认证中等待前端作答的提示
+    pub auth_prompts: PendingPrompts,
+    /// Key: 分组 ID (广播输入，仅存在于内存)
+    pub broadcast_groups: BroadcastGroups,
+}
+
+impl SshState {
+    /// 获取连接句柄 (监控 / exec 使用)
+    pub fn get_handle(&self, id: &str) -> Result<Arc<SshHandle>, String> {
+        let map = self.sessions.lock().unwrap();
+        let conn = map.get(id).ok_or("SSH connection not active")?;
+        Ok(conn.handle.clone())
+    }
+
+    /// 把键盘输入交给会话的 Shell 读写任务
+    pub fn send_input(&self, id: &str, data: Vec<u8>) -> Result<(), String> {
+        let map = self.sessions.lock().unwrap();
+        let conn = map.get(id).ok_or("SSH connection not active")?;
+        conn.shell_tx
+            .send(ShellInput::Data(data))
+            .map_err(|_| "Shell channel closed".to_string())
+    }
+
+    /// 获取 SFTP 会话，不存在时在同一条连接上打开 sftp 子系统
+    pub async fn get_sftp(&self, id: &str) -> Result<Arc<SftpSession>, String> {
+        let (handle, slot) = {
+            let map = self.sessions.lock().unwrap();
+            let conn = map.get(id).ok_or("SSH connection not active")?;
+            (conn.handle.clone(), conn.sftp_session.clone())
+        };
+
+        let mut guard = slot.lock().await;
+        if let Some(sftp) = guard.as_ref() {
+            return Ok(sftp.clone());
+        }
+        let sftp = Arc::new(open_sftp_session(&handle).await?);
+        *guard = Some(sftp.clone());
+        Ok(sftp)
+    }
 }
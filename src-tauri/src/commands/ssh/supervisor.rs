@@ -0,0 +1,162 @@
+// src-tauri/src/commands/ssh/supervisor.rs
+// 会话守护：检测传输层断开，按 auto_reconnect / max_reconnects 指数退避重连
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+
+use super::broadcast::{self, BroadcastGroups};
+use super::core::{self, create_shell_channel, spawn_shell_reader};
+use super::forward;
+use super::state::SshConnection;
+use crate::models::SshConfig;
+
+// 传输层存活检查间隔 (keepalive 由 russh 按 keep_alive_interval 发送)
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+// 退避上限
+const MAX_BACKOFF_SECS: u64 = 30;
+
+#[derive(serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStateEvent {
+    pub state: String, // connecting | connected | reconnecting | failed | disconnected
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub message: Option<String>,
+}
+
+/// 推送会话状态 (ssh-state-{id})，前端据此显示连接状态
+pub fn emit_state(app: &AppHandle, id: &str, state: &str, attempt: u32, max_attempts: u32, message: Option<String>) {
+    let _ = app.emit(
+        &format!("ssh-state-{}", id),
+        SessionStateEvent { state: state.to_string(), attempt, max_attempts, message },
+    );
+}
+
+// 直接在终端里打印状态行，无需前端额外处理
+fn emit_term_notice(app: &AppHandle, id: &str, color: &str, text: &str) {
+    let _ = app.emit(&format!("term-data-{}", id), format!("\r\n\x1b[{}m[{}]\x1b[0m\r\n", color, text));
+}
+
+fn backoff(attempt: u32) -> Duration {
+    let secs = 2u64.saturating_pow(attempt.saturating_sub(1)).saturating_mul(2);
+    Duration::from_secs(secs.min(MAX_BACKOFF_SECS))
+}
+
+/// 为会话启动守护任务；会话被 disconnect_ssh 移除或替换时任务随之中止
+pub fn spawn_supervisor(
+    app: AppHandle,
+    sessions: Arc<Mutex<HashMap<String, SshConnection>>>,
+    groups: BroadcastGroups,
+    id: String,
+    config: SshConfig,
+) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let auto_reconnect = config.auto_reconnect.unwrap_or(false);
+        let max_attempts = config.max_reconnects.unwrap_or(3);
+
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+
+            let handle = {
+                let map = sessions.lock().unwrap();
+                match map.get(&id) {
+                    Some(conn) => conn.handle.clone(),
+                    None => return, // 用户主动断开
+                }
+            };
+            if !handle.is_closed() {
+                continue;
+            }
+
+            // === 传输层已断开 ===
+            if !auto_reconnect || max_attempts == 0 {
+                emit_term_notice(&app, &id, "31", "Connection lost");
+                emit_state(&app, &id, "disconnected", 0, 0, Some("Connection lost".to_string()));
+                drop_session(&sessions, &groups, &id);
+                return;
+            }
+
+            let mut recovered = false;
+            for attempt in 1..=max_attempts {
+                let wait = backoff(attempt);
+                emit_term_notice(
+                    &app,
+                    &id,
+                    "33",
+                    &format!("Connection lost. Reconnecting in {}s ({}/{})...", wait.as_secs(), attempt, max_attempts),
+                );
+                emit_state(&app, &id, "reconnecting", attempt, max_attempts, None);
+                tokio::time::sleep(wait).await;
+
+                if !sessions.lock().unwrap().contains_key(&id) {
+                    return;
+                }
+
+                match reestablish(&app, &sessions, &id, &config).await {
+                    Ok(()) => {
+                        emit_term_notice(&app, &id, "32", "Reconnected");
+                        emit_state(&app, &id, "connected", attempt, max_attempts, None);
+                        recovered = true;
+                        break;
+                    }
+                    Err(e) => {
+                        emit_state(&app, &id, "reconnecting", attempt, max_attempts, Some(e));
+                    }
+                }
+            }
+
+            if !recovered {
+                emit_term_notice(&app, &id, "31", "Reconnect failed");
+                emit_state(&app, &id, "failed", max_attempts, max_attempts, Some("Reconnect attempts exhausted".to_string()));
+                drop_session(&sessions, &groups, &id);
+                return;
+            }
+        }
+    })
+}
+
+// 移除已断开的会话并释放其转发监听、退出广播分组 (同 disconnect_ssh)；shutdown 会中止当前守护任务，调用后应立即返回
+fn drop_session(sessions: &Arc<Mutex<HashMap<String, SshConnection>>>, groups: &BroadcastGroups, id: &str) {
+    let conn = sessions.lock().unwrap().remove(id);
+    if let Some(conn) = conn {
+        conn.shutdown();
+        broadcast::forget_session(groups, id);
+    }
+}
+
+// 重新建立连接 + Shell，SFTP 通道置空后按需在新连接上重开，监控 exec 本就是按次打开；端口转发沿用原规则
+async fn reestablish(
+    app: &AppHandle,
+    sessions: &Arc<Mutex<HashMap<String, SshConnection>>>,
+    id: &str,
+    config: &SshConfig,
+) -> Result<(), String> {
+    let (cols, rows, forwards) = sessions
+        .lock()
+        .unwrap()
+        .get(id)
+        .map(|c| (c.pty_size.0, c.pty_size.1, c.forwards.clone()))
+        .ok_or("Session closed during reconnect")?;
+
+    let mut handle = core::establish_base_session_async(app, id, config, &forwards).await?;
+    // -R 监听随旧连接失效，需在新连接上重新申请 (-L / -D 监听自动使用新句柄)
+    for e in forward::request_remote_forwards(&mut handle, &forwards).await {
+        emit_term_notice(app, id, "33", &e);
+    }
+    let x11 = forwards.x11();
+    let channel = create_shell_channel(&handle, cols, rows, config.agent_forwarding.unwrap_or(false), x11.as_deref()).await?;
+
+    let (shell_tx, shell_rx) = mpsc::unbounded_channel();
+    spawn_shell_reader(app.clone(), id.to_string(), channel, shell_rx);
+
+    let mut map = sessions.lock().unwrap();
+    let conn = map.get_mut(id).ok_or("Session closed during reconnect")?;
+    conn.handle = Arc::new(handle);
+    conn.shell_tx = shell_tx;
+    conn.sftp_session = Arc::new(tokio::sync::Mutex::new(None));
+    Ok(())
+}
@@ -0,0 +1,184 @@
+// src-tauri/src/commands/ssh/x11.rs
+// X11 转发 (ssh -X)：向远端出示随机伪造的 cookie，X11 通道建立时校验并替换为本机 DISPLAY 的真实 cookie，
+// 真实凭证不会离开本机
+
+use std::sync::Arc;
+
+use russh::client::Msg;
+use russh::Channel;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const COOKIE_PROTO: &str = "MIT-MAGIC-COOKIE-1";
+
+enum DisplaySocket {
+    #[cfg(unix)]
+    Unix(std::path::PathBuf),
+    Tcp(String, u16),
+}
+
+pub struct X11Forward {
+    socket: DisplaySocket,
+    pub screen: u32,
+    /// 本机 X server 的认证 (协议名, cookie)；xauth 无记录时按无认证连接
+    real_auth: Option<(String, Vec<u8>)>,
+    fake_cookie: [u8; 16],
+}
+
+impl X11Forward {
+    /// 按本机 DISPLAY 准备转发参数
+    pub async fn from_env() -> Result<Self, String> {
+        let display = std::env::var("DISPLAY")
+            .ok()
+            .filter(|d| !d.is_empty())
+            .ok_or("DISPLAY is not set (is an X server running?)")?;
+        let (socket, screen) = parse_display(&display)?;
+        Ok(Self {
+            socket,
+            screen,
+            real_auth: read_xauth(&display).await,
+            fake_cookie: rand::random(),
+        })
+    }
+
+    /// x11-req 中发送给远端的认证参数
+    pub fn fake_auth(&self) -> (&'static str, String) {
+        (COOKIE_PROTO, to_hex(&self.fake_cookie))
+    }
+}
+
+// [host]:display[.screen]；host 为空或 unix 时走 /tmp/.X11-unix，XQuartz 的 DISPLAY 本身就是 socket 路径
+fn parse_display(display: &str) -> Result<(DisplaySocket, u32), String> {
+    let invalid = || format!("Unsupported DISPLAY: {}", display);
+    let (host, rest) = display.rsplit_once(':').ok_or_else(invalid)?;
+    let (number, screen) = match rest.split_once('.') {
+        Some((n, s)) => (n, s.parse::<u32>().map_err(|_| invalid())?),
+        None => (rest, 0),
+    };
+    let number: u16 = number.parse().map_err(|_| invalid())?;
+
+    #[cfg(unix)]
+    {
+        if host.starts_with('/') {
+            return Ok((DisplaySocket::Unix(format!("{}:{}", host, number).into()), screen));
+        }
+        if host.is_empty() || host == "unix" {
+            return Ok((DisplaySocket::Unix(format!("/tmp/.X11-unix/X{}", number).into()), screen));
+        }
+    }
+
+    let host = if host.is_empty() { "127.0.0.1" } else { host };
+    Ok((DisplaySocket::Tcp(host.to_string(), 6000 + number), screen))
+}
+
+// `xauth list $DISPLAY` 输出: "host/unix:0  MIT-MAGIC-COOKIE-1  <hex>"
+async fn read_xauth(display: &str) -> Option<(String, Vec<u8>)> {
+    let output = tokio::process::Command::new("xauth")
+        .args(["list", display])
+        .output()
+        .await
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut fields = text.lines().next()?.split_whitespace().skip(1);
+    let proto = fields.next()?.to_string();
+    let cookie = from_hex(fields.next()?)?;
+    Some((proto, cookie))
+}
+
+/// 远端打开的 x11 通道：校验伪造 cookie 后转接到本机 X server
+pub fn spawn_x11_bridge(forward: Arc<X11Forward>, channel: Channel<Msg>) {
+    tauri::async_runtime::spawn(async move {
+        let mut remote = channel.into_stream();
+        let setup = match rewrite_setup(&mut remote, &forward).await {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("[x11] {}", e);
+                return;
+            }
+        };
+
+        let result = match &forward.socket {
+            #[cfg(unix)]
+            DisplaySocket::Unix(path) => match tokio::net::UnixStream::connect(path).await {
+                Ok(mut local) => match local.write_all(&setup).await {
+                    Ok(()) => tokio::io::copy_bidirectional(&mut remote, &mut local).await.map(|_| ()),
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(e),
+            },
+            DisplaySocket::Tcp(host, port) => match TcpStream::connect((host.as_str(), *port)).await {
+                Ok(mut local) => match local.write_all(&setup).await {
+                    Ok(()) => tokio::io::copy_bidirectional(&mut remote, &mut local).await.map(|_| ()),
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(e),
+            },
+        };
+        if let Err(e) = result {
+            eprintln!("[x11] local display: {}", e);
+        }
+    });
+}
+
+// X11 连接建立包：byte-order(1) pad(1) major(2) minor(2) name_len(2) data_len(2) pad(2) name data (均按 4 字节对齐)
+async fn rewrite_setup<R: AsyncRead + Unpin>(remote: &mut R, forward: &X11Forward) -> Result<Vec<u8>, String> {
+    let io = |e: std::io::Error| format!("X11 setup read failed: {}", e);
+
+    let mut head = [0u8; 12];
+    remote.read_exact(&mut head).await.map_err(io)?;
+    let little_endian = match head[0] {
+        b'l' => true,
+        b'B' => false,
+        _ => return Err("Invalid X11 byte order".to_string()),
+    };
+    let read_u16 = |i: usize| {
+        let bytes = [head[i], head[i + 1]];
+        let v = if little_endian { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) };
+        v as usize
+    };
+    let write_u16 = |v: usize| {
+        if little_endian { (v as u16).to_le_bytes() } else { (v as u16).to_be_bytes() }
+    };
+    let (name_len, data_len) = (read_u16(6), read_u16(8));
+
+    let mut name = vec![0u8; pad4(name_len)];
+    remote.read_exact(&mut name).await.map_err(io)?;
+    let mut data = vec![0u8; pad4(data_len)];
+    remote.read_exact(&mut data).await.map_err(io)?;
+
+    if &name[..name_len] != COOKIE_PROTO.as_bytes() || data[..data_len] != forward.fake_cookie {
+        return Err("X11 connection rejected: authentication cookie mismatch".to_string());
+    }
+
+    let (real_name, real_data): (&[u8], &[u8]) = match &forward.real_auth {
+        Some((proto, cookie)) => (proto.as_bytes(), cookie.as_slice()),
+        None => (&[][..], &[][..]),
+    };
+    let mut setup = head[..6].to_vec();
+    setup.extend_from_slice(&write_u16(real_name.len()));
+    setup.extend_from_slice(&write_u16(real_data.len()));
+    setup.extend_from_slice(&[0, 0]);
+    for part in [real_name, real_data] {
+        setup.extend_from_slice(part);
+        setup.resize(setup.len() + pad4(part.len()) - part.len(), 0);
+    }
+    Ok(setup)
+}
+
+fn pad4(n: usize) -> usize {
+    (n + 3) & !3
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
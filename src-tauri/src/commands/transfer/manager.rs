@@ -0,0 +1,395 @@
+// src-tauri/src/commands/transfer/manager.rs
+// 传输队列：内存任务表 + 调度 (同时运行的任务不超过 max_workers)
+// 状态变化写入 transfers 表并推送 transfer-status 事件；暂停 / 取消通过 AtomicU8 通知工作任务
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Emitter};
+
+use crate::models::TransferJob;
+
+use super::now_ms;
+use super::worker::{self, Outcome};
+
+pub const CTRL_RUN: u8 = 0;
+pub const CTRL_PAUSE: u8 = 1;
+pub const CTRL_CANCEL: u8 = 2;
+
+const DEFAULT_WORKERS: usize = 3;
+pub const MAX_WORKERS: usize = 16;
+
+struct Job {
+    record: TransferJob,
+    control: Arc<AtomicU8>,
+}
+
+struct Queue {
+    jobs: HashMap<String, Job>,
+    /// 入队顺序 (先进先出调度)
+    order: Vec<String>,
+    max_workers: usize,
+    running: usize,
+}
+
+#[derive(Clone)]
+pub struct TransferManager {
+    db: SqlitePool,
+    queue: Arc<Mutex<Queue>>,
+}
+
+impl TransferManager {
+    /// 启动时载入持久化的队列：上次未完成的任务一律转为暂停，由用户决定何时恢复
+    pub async fn load(db: SqlitePool) -> Result<Self, String> {
+        let max_workers: Option<i64> = sqlx::query_scalar("SELECT max_workers FROM transfer_settings WHERE id = 1")
+            .fetch_optional(&db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        sqlx::query("UPDATE transfers SET status = 'paused' WHERE status IN ('queued', 'running')")
+            .execute(&db)
+            .await
+            .map_err(|e| e.to_string())?;
+        let records = sqlx::query_as::<_, TransferJob>("SELECT * FROM transfers ORDER BY created_at")
+            .fetch_all(&db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let mut queue = Queue {
+            jobs: HashMap::new(),
+            order: Vec::new(),
+            max_workers: max_workers.map_or(DEFAULT_WORKERS, |n| (n as usize).clamp(1, MAX_WORKERS)),
+            running: 0,
+        };
+        for record in records {
+            queue.order.push(record.id.clone());
+            queue.jobs.insert(record.id.clone(), Job { record, control: Arc::new(AtomicU8::new(CTRL_RUN)) });
+        }
+        Ok(Self { db, queue: Arc::new(Mutex::new(queue)) })
+    }
+
+    /// 新任务在前
+    pub fn list(&self) -> Vec<TransferJob> {
+        let queue = self.queue.lock().unwrap();
+        queue.order.iter().rev().filter_map(|id| queue.jobs.get(id)).map(|j| j.record.clone()).collect()
+    }
+
+    pub fn get(&self, id: &str) -> Result<TransferJob, String> {
+        let queue = self.queue.lock().unwrap();
+        queue.jobs.get(id).map(|j| j.record.clone()).ok_or_else(|| "Transfer not found".to_string())
+    }
+
+    pub fn max_workers(&self) -> usize {
+        self.queue.lock().unwrap().max_workers
+    }
+
+    pub async fn set_max_workers(&self, app: &AppHandle, max_workers: usize) -> Result<usize, String> {
+        let max_workers = max_workers.clamp(1, MAX_WORKERS);
+        sqlx::query(
+            "INSERT INTO transfer_settings (id, max_workers) VALUES (1, ?)
+             ON CONFLICT(id) DO UPDATE SET max_workers = excluded.max_workers",
+        )
+        .bind(max_workers as i64)
+        .execute(&self.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        // 调小时正在运行的任务不受影响，只是不再启动新任务
+        self.queue.lock().unwrap().max_workers = max_workers;
+        self.schedule(app);
+        Ok(max_workers)
+    }
+
+    pub async fn enqueue(&self, app: &AppHandle, record: TransferJob) -> Result<TransferJob, String> {
+        sqlx::query(
+            "INSERT INTO transfers (id, session_id, server_id, kind, name, local_path, remote_path,
+                total_bytes, transferred, status, error, verify, checksum, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&record.id)
+        .bind(&record.session_id)
+        .bind(&record.server_id)
+        .bind(&record.kind)
+        .bind(&record.name)
+        .bind(&record.local_path)
+        .bind(&record.remote_path)
+        .bind(record.total_bytes)
+        .bind(record.transferred)
+        .bind(&record.status)
+        .bind(&record.error)
+        .bind(record.verify)
+        .bind(&record.checksum)
+        .bind(record.created_at)
+        .bind(record.updated_at)
+        .execute(&self.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        {
+            let mut queue = self.queue.lock().unwrap();
+            queue.order.push(record.id.clone());
+            queue.jobs.insert(
+                record.id.clone(),
+                Job { record: record.clone(), control: Arc::new(AtomicU8::new(CTRL_RUN)) },
+            );
+        }
+        let _ = app.emit("transfer-status", &record);
+        self.schedule(app);
+        Ok(record)
+    }
+
+    /// 排队中的任务直接转为暂停；运行中的任务在下一个数据块后停下
+    pub async fn pause(&self, app: &AppHandle, id: &str) -> Result<TransferJob, String> {
+        let updated = {
+            let mut queue = self.queue.lock().unwrap();
+            let job = queue.jobs.get_mut(id).ok_or("Transfer not found")?;
+            match job.record.status.as_str() {
+                "queued" => {
+                    job.record.status = "paused".to_string();
+                    job.record.updated_at = now_ms();
+                    Some(job.record.clone())
+                }
+                "running" => {
+                    job.control.store(CTRL_PAUSE, Ordering::Release);
+                    None
+                }
+                _ => return Err("Transfer is not active".to_string()),
+            }
+        };
+        match updated {
+            Some(record) => {
+                self.save(&record).await?;
+                let _ = app.emit("transfer-status", &record);
+                Ok(record)
+            }
+            None => self.get(id),
+        }
+    }
+
+    /// 暂停 / 失败 / 已取消的任务重新排队；session_id 用于换绑到新的连接 (例如重启之后)
+    pub async fn resume(&self, app: &AppHandle, id: &str, session_id: Option<String>) -> Result<TransferJob, String> {
+        let record = {
+            let mut queue = self.queue.lock().unwrap();
+            let job = queue.jobs.get_mut(id).ok_or("Transfer not found")?;
+            match job.record.status.as_str() {
+                // 尚未停下的暂停请求直接撤回
+                "running" => {
+                    job.control.store(CTRL_RUN, Ordering::Release);
+                    return Ok(job.record.clone());
+                }
+                "queued" => return Ok(job.record.clone()),
+                "completed" => return Err("Transfer already completed".to_string()),
+                "cancelled" => job.record.transferred = 0,
+                _ => {}
+            }
+            if let Some(session_id) = session_id.filter(|s| !s.is_empty()) {
+                job.record.session_id = session_id;
+            }
+            job.record.status = "queued".to_string();
+            job.record.error = None;
+            job.record.updated_at = now_ms();
+            job.record.clone()
+        };
+        self.save(&record).await?;
+        let _ = app.emit("transfer-status", &record);
+        self.schedule(app);
+        self.get(id)
+    }
+
+    /// 取消后丢弃已传输的部分文件
+    pub async fn cancel(&self, app: &AppHandle, id: &str) -> Result<TransferJob, String> {
+        let stopped = {
+            let mut queue = self.queue.lock().unwrap();
+            let job = queue.jobs.get_mut(id).ok_or("Transfer not found")?;
+            match job.record.status.as_str() {
+                "running" => {
+                    job.control.store(CTRL_CANCEL, Ordering::Release);
+                    None
+                }
+                "queued" | "paused" | "failed" => {
+                    let partial = job.record.clone();
+                    job.record.status = "cancelled".to_string();
+                    job.record.transferred = 0;
+                    job.record.error = None;
+                    job.record.updated_at = now_ms();
+                    Some((partial, job.record.clone()))
+                }
+                _ => return Err("Transfer is not active".to_string()),
+            }
+        };
+        match stopped {
+            Some((partial, record)) => {
+                if partial.transferred > 0 {
+                    worker::discard_partial(app, &partial).await;
+                }
+                self.save(&record).await?;
+                let _ = app.emit("transfer-status", &record);
+                Ok(record)
+            }
+            None => self.get(id),
+        }
+    }
+
+    /// delete_file 仅对下载有效：删除已下载到本地的文件
+    pub async fn remove(&self, id: &str, delete_file: bool) -> Result<(), String> {
+        let record = {
+            let mut queue = self.queue.lock().unwrap();
+            let job = queue.jobs.get(id).ok_or("Transfer not found")?;
+            if job.record.status == "running" {
+                return Err("Cancel the transfer before removing it".to_string());
+            }
+            let record = job.record.clone();
+            queue.jobs.remove(id);
+            queue.order.retain(|x| x != id);
+            record
+        };
+        sqlx::query("DELETE FROM transfers WHERE id = ?")
+            .bind(id)
+            .execute(&self.db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if delete_file && record.kind == "download" {
+            if let Err(e) = tokio::fs::remove_file(&record.local_path).await {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    return Err(format!("Failed to delete local file: {}", e));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 清除已结束 (完成 / 失败 / 取消) 的记录，返回清除数量
+    pub async fn clear_finished(&self) -> Result<usize, String> {
+        let finished: Vec<String> = {
+            let mut guard = self.queue.lock().unwrap();
+            let queue = &mut *guard;
+            let finished: Vec<String> = queue
+                .jobs
+                .values()
+                .filter(|j| matches!(j.record.status.as_str(), "completed" | "failed" | "cancelled"))
+                .map(|j| j.record.id.clone())
+                .collect();
+            for id in &finished {
+                queue.jobs.remove(id);
+            }
+            queue.order.retain(|id| queue.jobs.contains_key(id));
+            finished
+        };
+        sqlx::query("DELETE FROM transfers WHERE status IN ('completed', 'failed', 'cancelled')")
+            .execute(&self.db)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(finished.len())
+    }
+
+    /// 工作任务上报进度 (仅更新内存)
+    pub fn set_progress(&self, id: &str, transferred: u64, total: u64) {
+        let mut queue = self.queue.lock().unwrap();
+        if let Some(job) = queue.jobs.get_mut(id) {
+            job.record.transferred = transferred as i64;
+            job.record.total_bytes = total as i64;
+        }
+    }
+
+    /// 校验通过后记录文件的 SHA-256
+    pub fn set_checksum(&self, id: &str, checksum: String) {
+        let mut queue = self.queue.lock().unwrap();
+        if let Some(job) = queue.jobs.get_mut(id) {
+            job.record.checksum = Some(checksum);
+        }
+    }
+
+    /// 定期把进度写入数据库，异常退出后可从该位置续传
+    pub async fn checkpoint(&self, id: &str) {
+        if let Ok(record) = self.get(id) {
+            let _ = self.save(&record).await;
+        }
+    }
+
+    // 在空闲名额内按入队顺序启动排队中的任务
+    pub fn schedule(&self, app: &AppHandle) {
+        let mut started = Vec::new();
+        {
+            let mut queue = self.queue.lock().unwrap();
+            while queue.running < queue.max_workers {
+                let Some(id) = queue.order.iter().find(|id| queue.jobs[*id].record.status == "queued").cloned() else {
+                    break;
+                };
+                let job = queue.jobs.get_mut(&id).unwrap();
+                job.record.status = "running".to_string();
+                job.record.updated_at = now_ms();
+                job.control.store(CTRL_RUN, Ordering::Release);
+                started.push((job.record.clone(), job.control.clone()));
+                queue.running += 1;
+            }
+        }
+
+        for (record, control) in started {
+            let manager = self.clone();
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = manager.save(&record).await;
+                let _ = app.emit("transfer-status", &record);
+                let outcome = worker::run(&app, &manager, &record, &control).await;
+                manager.finish(&app, &record.id, outcome).await;
+            });
+        }
+    }
+
+    async fn finish(&self, app: &AppHandle, id: &str, outcome: Outcome) {
+        let record = {
+            let mut queue = self.queue.lock().unwrap();
+            queue.running = queue.running.saturating_sub(1);
+            queue.jobs.get_mut(id).map(|job| {
+                let r = &mut job.record;
+                match outcome {
+                    Outcome::Completed => {
+                        r.status = "completed".to_string();
+                        r.transferred = r.total_bytes;
+                        r.error = None;
+                    }
+                    Outcome::Paused => r.status = "paused".to_string(),
+                    Outcome::Cancelled => {
+                        r.status = "cancelled".to_string();
+                        r.transferred = 0;
+                    }
+                    Outcome::Failed(e) => {
+                        r.status = "failed".to_string();
+                        r.error = Some(e);
+                    }
+                }
+                r.updated_at = now_ms();
+                job.control.store(CTRL_RUN, Ordering::Release);
+                r.clone()
+            })
+        };
+        if let Some(record) = record {
+            let _ = self.save(&record).await;
+            let _ = app.emit("transfer-status", &record);
+        }
+        self.schedule(app);
+    }
+
+    async fn save(&self, record: &TransferJob) -> Result<(), String> {
+        sqlx::query(
+            "UPDATE transfers SET session_id = ?, total_bytes = ?, transferred = ?, status = ?, error = ?, checksum = ?,
+                updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(&record.session_id)
+        .bind(record.total_bytes)
+        .bind(record.transferred)
+        .bind(&record.status)
+        .bind(&record.error)
+        .bind(&record.checksum)
+        .bind(record.updated_at)
+        .bind(&record.id)
+        .execute(&self.db)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+// src-tauri/src/commands/transfer/mod.rs
+// 文件传输队列：上传 / 下载任务排队执行，支持暂停、断点续传、取消、完整性校验与并发数设置
+//   事件: transfer-status (任务记录变化) / transfer-progress (节流后的进度、速率、预计剩余时间)
+// 队列持久化在 transfers 表，应用重启后未完成的任务以暂停状态恢复
+
+pub mod manager;
+pub mod verify;
+pub(crate) mod worker;
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::models::TransferJob;
+
+pub use manager::TransferManager;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferRequest {
+    pub session_id: String,
+    #[serde(default)]
+    pub server_id: Option<String>,
+    pub kind: String, // upload | download
+    pub local_path: String,
+    pub remote_path: String,
+    /// 显示名称，缺省取源文件名
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 完成后校验 SHA-256，缺省开启
+    #[serde(default)]
+    pub verify: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferSettings {
+    pub max_workers: usize,
+}
+
+pub(crate) fn now_ms() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64
+}
+
+#[tauri::command]
+pub async fn enqueue_transfer(
+    app: AppHandle,
+    manager: State<'_, TransferManager>,
+    request: TransferRequest,
+) -> Result<TransferJob, String> {
+    let source = match request.kind.as_str() {
+        "upload" => &request.local_path,
+        "download" => &request.remote_path,
+        other => return Err(format!("Unknown transfer kind: {}", other)),
+    };
+    let name = request
+        .name
+        .clone()
+        .filter(|n| !n.is_empty())
+        .or_else(|| Path::new(source).file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| source.clone());
+
+    let now = now_ms();
+    let record = TransferJob {
+        id: uuid::Uuid::new_v4().to_string(),
+        session_id: request.session_id,
+        server_id: request.server_id,
+        kind: request.kind,
+        name,
+        local_path: request.local_path,
+        remote_path: request.remote_path,
+        total_bytes: 0,
+        transferred: 0,
+        status: "queued".to_string(),
+        error: None,
+        verify: request.verify.unwrap_or(true),
+        checksum: None,
+        created_at: now,
+        updated_at: now,
+    };
+    manager.enqueue(&app, record).await
+}
+
+#[tauri::command]
+pub fn list_transfers(manager: State<'_, TransferManager>) -> Vec<TransferJob> {
+    manager.list()
+}
+
+#[tauri::command]
+pub async fn pause_transfer(
+    app: AppHandle,
+    manager: State<'_, TransferManager>,
+    id: String,
+) -> Result<TransferJob, String> {
+    manager.pause(&app, &id).await
+}
+
+/// session_id 为空时沿用原会话
+#[tauri::command]
+pub async fn resume_transfer(
+    app: AppHandle,
+    manager: State<'_, TransferManager>,
+    id: String,
+    session_id: Option<String>,
+) -> Result<TransferJob, String> {
+    manager.resume(&app, &id, session_id).await
+}
+
+#[tauri::command]
+pub async fn cancel_transfer(
+    app: AppHandle,
+    manager: State<'_, TransferManager>,
+    id: String,
+) -> Result<TransferJob, String> {
+    manager.cancel(&app, &id).await
+}
+
+#[tauri::command]
+pub async fn remove_transfer(
+    manager: State<'_, TransferManager>,
+    id: String,
+    delete_file: Option<bool>,
+) -> Result<(), String> {
+    manager.remove(&id, delete_file.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn clear_finished_transfers(manager: State<'_, TransferManager>) -> Result<usize, String> {
+    manager.clear_finished().await
+}
+
+#[tauri::command]
+pub fn get_transfer_settings(manager: State<'_, TransferManager>) -> TransferSettings {
+    TransferSettings { max_workers: manager.max_workers() }
+}
+
+#[tauri::command]
+pub async fn set_transfer_concurrency(
+    app: AppHandle,
+    manager: State<'_, TransferManager>,
+    max_workers: usize,
+) -> Result<TransferSettings, String> {
+    let max_workers = manager.set_max_workers(&app, max_workers).await?;
+    Ok(TransferSettings { max_workers })
+}
@@ -0,0 +1,145 @@
+// src-tauri/src/commands/transfer/verify.rs
+// 传输完成后的完整性校验
+//   1. 远端 sha256sum / shasum -a 256 与本地 SHA-256 对比 (只需一次 exec)
+//   2. 远端没有可用 shell 或命令时，经 SFTP 回读远端文件按块对比哈希，定位第一个不一致的块
+
+use russh_sftp::client::SftpSession;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncReadExt;
+
+use crate::commands::fs::with_timeout;
+use crate::commands::ssh::core::SshHandle;
+use crate::commands::ssh::exec;
+
+const BLOCK_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Verification {
+    pub method: String, // sha256sum | block
+    pub local_sha256: String,
+    pub remote_sha256: Option<String>,
+    pub matched: bool,
+    /// 按块对比时第一个不一致块的起始偏移
+    pub mismatch_offset: Option<u64>,
+}
+
+impl Verification {
+    pub fn mismatch_message(&self) -> String {
+        match self.mismatch_offset {
+            Some(offset) => format!("Checksum mismatch ({}): first differing block at byte {}", self.method, offset),
+            None => format!(
+                "Checksum mismatch ({}): local {} / remote {}",
+                self.method,
+                self.local_sha256,
+                self.remote_sha256.as_deref().unwrap_or("-")
+            ),
+        }
+    }
+}
+
+/// handle 为空时直接按块对比
+pub async fn verify(
+    handle: Option<&SshHandle>,
+    sftp: &SftpSession,
+    local_path: &str,
+    remote_path: &str,
+) -> Result<Verification, String> {
+    if let Some(handle) = handle {
+        if let Some(remote_sha256) = remote_sha256(handle, remote_path).await {
+            let local_sha256 = local_sha256(local_path).await?;
+            return Ok(Verification {
+                method: "sha256sum".to_string(),
+                matched: local_sha256 == remote_sha256,
+                local_sha256,
+                remote_sha256: Some(remote_sha256),
+                mismatch_offset: None,
+            });
+        }
+    }
+    compare_blocks(sftp, local_path, remote_path).await
+}
+
+// 命令不存在或输出格式不符时返回 None，交给按块对比
+async fn remote_sha256(handle: &SshHandle, remote_path: &str) -> Option<String> {
+    let path = exec::shell_quote(remote_path);
+    let cmd = format!(
+        "sha256sum -- {p} 2>/dev/null || shasum -a 256 -- {p} 2>/dev/null || openssl dgst -sha256 -r {p}",
+        p = path
+    );
+    let output = exec::run(handle, &exec::ExecRequest::new(cmd)).await.ok()?;
+    if !output.success() {
+        return None;
+    }
+    let digest = output.stdout.split_whitespace().next()?.to_ascii_lowercase();
+    (digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())).then_some(digest)
+}
+
+async fn local_sha256(path: &str) -> Result<String, String> {
+    let path = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        use std::io::Read;
+        let mut file = std::fs::File::open(&path).map_err(|e| format!("Failed to open local file: {}", e))?;
+        let mut hasher = Sha256::new();
+        let mut buf = vec![0u8; BLOCK_SIZE];
+        loop {
+            let n = file.read(&mut buf).map_err(|e| e.to_string())?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+async fn compare_blocks(sftp: &SftpSession, local_path: &str, remote_path: &str) -> Result<Verification, String> {
+    let mut local = tokio::fs::File::open(local_path)
+        .await
+        .map_err(|e| format!("Failed to open local file: {}", e))?;
+    let mut remote = with_timeout(10, sftp.open(remote_path.to_string()))
+        .await
+        .map_err(|e| format!("Failed to open remote file: {}", e))?;
+
+    let (mut local_hash, mut remote_hash) = (Sha256::new(), Sha256::new());
+    let (mut local_buf, mut remote_buf) = (vec![0u8; BLOCK_SIZE], vec![0u8; BLOCK_SIZE]);
+    let mut offset = 0u64;
+    let mut mismatch_offset = None;
+    loop {
+        let a = read_block(&mut local, &mut local_buf).await?;
+        let b = read_block(&mut remote, &mut remote_buf).await?;
+        if a == 0 && b == 0 {
+            break;
+        }
+        local_hash.update(&local_buf[..a]);
+        remote_hash.update(&remote_buf[..b]);
+        if mismatch_offset.is_none() && Sha256::digest(&local_buf[..a]) != Sha256::digest(&remote_buf[..b]) {
+            mismatch_offset = Some(offset);
+        }
+        offset += a.max(b) as u64;
+    }
+
+    Ok(Verification {
+        method: "block".to_string(),
+        local_sha256: format!("{:x}", local_hash.finalize()),
+        remote_sha256: Some(format!("{:x}", remote_hash.finalize())),
+        matched: mismatch_offset.is_none(),
+        mismatch_offset,
+    })
+}
+
+// 读满一个块 (文件末尾除外)，保证两端按相同边界切块
+async fn read_block<R: tokio::io::AsyncRead + Unpin>(reader: &mut R, buf: &mut [u8]) -> Result<usize, String> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let n = reader.read(&mut buf[filled..]).await.map_err(|e| format!("Read failed: {}", e))?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(filled)
+}
@@ -0,0 +1,283 @@
+// src-tauri/src/commands/transfer/worker.rs
+// 单个传输任务的执行：分块读写，每块之间检查暂停 / 取消信号；完成后按需校验 SHA-256
+// 进度事件 transfer-progress 按时间节流；已传输字节数定期落盘，续传时以目标端实际大小为起点
+
+use std::io::SeekFrom;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use russh_sftp::client::fs::File as SftpFile;
+use russh_sftp::client::SftpSession;
+use russh_sftp::protocol::OpenFlags;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+
+use crate::commands::fs::with_timeout;
+use crate::commands::ssh::SshState;
+use crate::models::TransferJob;
+
+use super::manager::{TransferManager, CTRL_CANCEL, CTRL_PAUSE};
+use super::verify;
+
+const CHUNK_SIZE: usize = 256 * 1024;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(2);
+// 单个数据块的读写超过该时间视为连接卡死
+const STALL_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub enum Outcome {
+    Completed,
+    Paused,
+    Cancelled,
+    Failed(String),
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferProgress {
+    pub id: String,
+    pub phase: &'static str, // transfer | verify
+    pub transferred: u64,
+    pub total: u64,
+    /// 字节 / 秒 (平滑后)
+    pub rate: f64,
+    pub eta_secs: Option<u64>,
+}
+
+pub async fn run(app: &AppHandle, manager: &TransferManager, job: &TransferJob, control: &AtomicU8) -> Outcome {
+    match transfer(app, manager, job, control).await {
+        Ok(outcome) => outcome,
+        Err(e) => Outcome::Failed(e),
+    }
+}
+
+async fn transfer(
+    app: &AppHandle,
+    manager: &TransferManager,
+    job: &TransferJob,
+    control: &AtomicU8,
+) -> Result<Outcome, String> {
+    let sftp = app.state::<SshState>().get_sftp(&job.session_id).await?;
+    // 已有进度说明是续传：起点以目标端实际大小为准 (落盘的进度可能落后于已写入的数据)
+    let resume = job.transferred > 0;
+
+    let (outcome, total) = match job.kind.as_str() {
+        "upload" => {
+            let mut ends = open_upload(&sftp, &job.local_path, &job.remote_path, resume).await?;
+            let outcome = pump(app, manager, job, control, &mut ends.src, &mut ends.dst, ends.offset, ends.total).await;
+            // 暂停 / 出错时同样关闭句柄，保证已报告的字节都已写入
+            ends.dst.shutdown().await.map_err(|e| format!("Flush failed: {}", e))?;
+            (outcome?, ends.total)
+        }
+        "download" => {
+            let mut ends = open_download(&sftp, &job.remote_path, &job.local_path, resume).await?;
+            let outcome = pump(app, manager, job, control, &mut ends.src, &mut ends.dst, ends.offset, ends.total).await;
+            ends.dst.flush().await.map_err(|e| e.to_string())?;
+            (outcome?, ends.total)
+        }
+        other => return Err(format!("Unknown transfer kind: {}", other)),
+    };
+
+    match outcome {
+        Outcome::Cancelled => discard_partial(app, job).await,
+        Outcome::Completed if job.verify => {
+            let _ = app.emit(
+                "transfer-progress",
+                TransferProgress {
+                    id: job.id.clone(),
+                    phase: "verify",
+                    transferred: total,
+                    total,
+                    rate: 0.0,
+                    eta_secs: None,
+                },
+            );
+            let handle = app.state::<SshState>().get_handle(&job.session_id).ok();
+            let result = verify::verify(handle.as_deref(), &sftp, &job.local_path, &job.remote_path).await?;
+            if !result.matched {
+                // 目标文件已不可信，重试时从头传输
+                manager.set_progress(&job.id, 0, total);
+                return Err(result.mismatch_message());
+            }
+            manager.set_checksum(&job.id, result.local_sha256);
+        }
+        _ => {}
+    }
+    Ok(outcome)
+}
+
+/// 已打开的源 / 目标文件，均已定位到续传起点
+pub(crate) struct Endpoints<R, W> {
+    pub src: R,
+    pub dst: W,
+    pub offset: u64,
+    pub total: u64,
+}
+
+// 目标端已有内容比源文件还长，说明不是同一个文件的前半段，从头开始
+fn resume_offset(existing: u64, total: u64) -> u64 {
+    if existing > total { 0 } else { existing }
+}
+
+/// resume 为 true 时从远端已有大小处续传，否则截断重写
+pub(crate) async fn open_upload(
+    sftp: &SftpSession,
+    local_path: &str,
+    remote_path: &str,
+    resume: bool,
+) -> Result<Endpoints<tokio::fs::File, SftpFile>, String> {
+    let mut src = tokio::fs::File::open(local_path)
+        .await
+        .map_err(|e| format!("Failed to open local file: {}", e))?;
+    let total = src.metadata().await.map_err(|e| e.to_string())?.len();
+    let existing = if resume {
+        with_timeout(5, sftp.metadata(remote_path.to_string())).await.ok().and_then(|m| m.size).unwrap_or(0)
+    } else {
+        0
+    };
+    let offset = resume_offset(existing, total);
+
+    // 续传时不能截断已写入的部分
+    let mut dst = if offset > 0 {
+        with_timeout(10, sftp.open_with_flags(remote_path.to_string(), OpenFlags::CREATE | OpenFlags::WRITE)).await
+    } else {
+        with_timeout(10, sftp.create(remote_path.to_string())).await
+    }
+    .map_err(|e| format!("Failed to create remote file: {}", e))?;
+    if offset > 0 {
+        src.seek(SeekFrom::Start(offset)).await.map_err(|e| e.to_string())?;
+        dst.seek(SeekFrom::Start(offset)).await.map_err(|e| e.to_string())?;
+    }
+    Ok(Endpoints { src, dst, offset, total })
+}
+
+/// resume 为 true 时从本地已有大小处续传，否则截断重写
+pub(crate) async fn open_download(
+    sftp: &SftpSession,
+    remote_path: &str,
+    local_path: &str,
+    resume: bool,
+) -> Result<Endpoints<SftpFile, tokio::fs::File>, String> {
+    let mut src = with_timeout(10, sftp.open(remote_path.to_string()))
+        .await
+        .map_err(|e| format!("Failed to open remote file: {}", e))?;
+    let total = src.metadata().await.map_err(|e| e.to_string())?.size.unwrap_or(0);
+    let existing = if resume { tokio::fs::metadata(local_path).await.map(|m| m.len()).unwrap_or(0) } else { 0 };
+    let offset = resume_offset(existing, total);
+
+    let mut dst = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(offset == 0)
+        .open(local_path)
+        .await
+        .map_err(|e| format!("Failed to create local file: {}", e))?;
+    if offset > 0 {
+        src.seek(SeekFrom::Start(offset)).await.map_err(|e| e.to_string())?;
+        dst.seek(SeekFrom::Start(offset)).await.map_err(|e| e.to_string())?;
+    }
+    Ok(Endpoints { src, dst, offset, total })
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn pump<R, W>(
+    app: &AppHandle,
+    manager: &TransferManager,
+    job: &TransferJob,
+    control: &AtomicU8,
+    src: &mut R,
+    dst: &mut W,
+    offset: u64,
+    total: u64,
+) -> Result<Outcome, String>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let event = "transfer-progress";
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut done = offset;
+    let mut meter = RateMeter::new(done);
+    let mut last_checkpoint = Instant::now();
+    manager.set_progress(&job.id, done, total);
+
+    loop {
+        match control.load(Ordering::Acquire) {
+            CTRL_PAUSE => return Ok(Outcome::Paused),
+            CTRL_CANCEL => return Ok(Outcome::Cancelled),
+            _ => {}
+        }
+
+        let n = tokio::time::timeout(STALL_TIMEOUT, src.read(&mut buf))
+            .await
+            .map_err(|_| "Transfer stalled".to_string())?
+            .map_err(|e| format!("Read failed: {}", e))?;
+        if n == 0 {
+            break;
+        }
+        tokio::time::timeout(STALL_TIMEOUT, dst.write_all(&buf[..n]))
+            .await
+            .map_err(|_| "Transfer stalled".to_string())?
+            .map_err(|e| format!("Write failed: {}", e))?;
+
+        done += n as u64;
+        manager.set_progress(&job.id, done, total.max(done));
+        if let Some(progress) = meter.tick(&job.id, done, total.max(done), false) {
+            let _ = app.emit(event, progress);
+        }
+        if last_checkpoint.elapsed() >= CHECKPOINT_INTERVAL {
+            manager.checkpoint(&job.id).await;
+            last_checkpoint = Instant::now();
+        }
+    }
+
+    if let Some(progress) = meter.tick(&job.id, done, done, true) {
+        let _ = app.emit(event, progress);
+    }
+    Ok(Outcome::Completed)
+}
+
+/// 删除取消任务留下的半成品：下载删本地文件，上传删远程文件
+pub async fn discard_partial(app: &AppHandle, job: &TransferJob) {
+    match job.kind.as_str() {
+        "download" => {
+            let _ = tokio::fs::remove_file(&job.local_path).await;
+        }
+        _ => {
+            if let Ok(sftp) = app.state::<SshState>().get_sftp(&job.session_id).await {
+                let _ = with_timeout(8, sftp.remove_file(job.remote_path.clone())).await;
+            }
+        }
+    }
+}
+
+// 速率统计：按节流窗口计算瞬时速率后做指数平滑，避免界面数字跳动
+struct RateMeter {
+    last_emit: Instant,
+    last_bytes: u64,
+    rate: f64,
+}
+
+impl RateMeter {
+    fn new(start: u64) -> Self {
+        Self { last_emit: Instant::now(), last_bytes: start, rate: 0.0 }
+    }
+
+    fn tick(&mut self, id: &str, done: u64, total: u64, force: bool) -> Option<TransferProgress> {
+        let elapsed = self.last_emit.elapsed();
+        if !force && elapsed < PROGRESS_INTERVAL {
+            return None;
+        }
+        let secs = elapsed.as_secs_f64();
+        if secs > 0.0 {
+            let current = (done - self.last_bytes) as f64 / secs;
+            self.rate = if self.rate == 0.0 { current } else { self.rate * 0.7 + current * 0.3 };
+        }
+        self.last_emit = Instant::now();
+        self.last_bytes = done;
+
+        let eta_secs = (self.rate > 0.0).then(|| ((total - done) as f64 / self.rate).ceil() as u64);
+        Some(TransferProgress { id: id.to_string(), phase: "transfer", transferred: done, total, rate: self.rate, eta_secs })
+    }
+}
@@ -32,15 +32,19 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, String> {
         .execute(&pool)
         .await
         .map_err(|e| e.to_string())?;
-    // --- 执行建表迁移 ---
-    
+    migrate(&pool).await?;
+    Ok(pool)
+}
+
+// --- 执行建表迁移 (测试中也用于初始化内存数据库) ---
+pub(crate) async fn migrate(pool: &Pool<Sqlite>) -> Result<(), String> {
     // 1. Vault 表 (Config & Keys)
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS vault_config (
             key TEXT PRIMARY KEY,
             value TEXT NOT NULL
         );"
-    ).execute(&pool).await.map_err(|e| e.to_string())?;
+    ).execute(pool).await.map_err(|e| e.to_string())?;
 
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS vault_keys (
@@ -54,7 +58,7 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, String> {
             created_at INTEGER NOT NULL,
             updated_at INTEGER NOT NULL
         );"
-    ).execute(&pool).await.map_err(|e| e.to_string())?;
+    ).execute(pool).await.map_err(|e| e.to_string())?;
 
     // 2. Server 表
     // 注意：tags 我们存为 TEXT (JSON 字符串)
@@ -90,9 +94,15 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, String> {
             connect_timeout INTEGER DEFAULT 10,
             keep_alive_interval INTEGER DEFAULT 60,
             auto_reconnect BOOLEAN DEFAULT 0,
-            max_reconnects INTEGER DEFAULT 3        
+            max_reconnects INTEGER DEFAULT 3,
+            agent_forwarding BOOLEAN DEFAULT 0,
+            x11_forwarding BOOLEAN DEFAULT 0
         );"
-    ).execute(&pool).await.map_err(|e| e.to_string())?;
+    ).execute(pool).await.map_err(|e| e.to_string())?;
+
+    // 🟢 [新增] 旧库补列 (CREATE TABLE IF NOT EXISTS 不会更新已有表结构)
+    ensure_column(pool, "servers", "agent_forwarding", "BOOLEAN DEFAULT 0").await?;
+    ensure_column(pool, "servers", "x11_forwarding", "BOOLEAN DEFAULT 0").await?;
 
     // --- [新增] 3. Snippets 表 ---
 sqlx::query(
@@ -105,7 +115,7 @@ sqlx::query(
             created_at INTEGER NOT NULL,
             updated_at INTEGER NOT NULL
         );"
-    ).execute(&pool).await.map_err(|e| e.to_string())?;
+    ).execute(pool).await.map_err(|e| e.to_string())?;
 
     // --- [New] 4. Proxies Table ---
     sqlx::query(
@@ -120,7 +130,7 @@ sqlx::query(
             created_at INTEGER NOT NULL,
             updated_at INTEGER NOT NULL
         );"
-    ).execute(&pool).await.map_err(|e| e.to_string())?;
+    ).execute(pool).await.map_err(|e| e.to_string())?;
 // 🟢 [新增] 5. Key Usages Table (密钥使用记录)
     // 采用联合主键 (key_id, server_id)，保证同一个密钥在同一个服务器只有一条最新记录
     // 使用 ON DELETE CASCADE 确保删除密钥或服务器时自动清理记录
@@ -133,7 +143,7 @@ sqlx::query(
             FOREIGN KEY(key_id) REFERENCES vault_keys(id) ON DELETE CASCADE,
             FOREIGN KEY(server_id) REFERENCES servers(id) ON DELETE CASCADE
         );"
-    ).execute(&pool).await.map_err(|e| e.to_string())?;
+    ).execute(pool).await.map_err(|e| e.to_string())?;
 
     // 🟢 [新增] 6. Command History Tables (终端命令历史三层架构)
 
@@ -147,13 +157,13 @@ sqlx::query(
             last_used_at INTEGER NOT NULL,
             global_exec_count INTEGER DEFAULT 1
         );"
-    ).execute(&pool).await.map_err(|e| e.to_string())?;
+    ).execute(pool).await.map_err(|e| e.to_string())?;
 
     // 索引：补全搜索 & 热度排序
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_history_command ON command_history(normalized_command);")
-        .execute(&pool).await.map_err(|e| e.to_string())?;
+        .execute(pool).await.map_err(|e| e.to_string())?;
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_history_count ON command_history(global_exec_count DESC);")
-        .execute(&pool).await.map_err(|e| e.to_string())?;
+        .execute(pool).await.map_err(|e| e.to_string())?;
 
     // 6.2 服务器统计表
     sqlx::query(
@@ -167,11 +177,11 @@ sqlx::query(
             FOREIGN KEY(command_id) REFERENCES command_history(id) ON DELETE CASCADE,
             UNIQUE(command_id, server_id)
         );"
-    ).execute(&pool).await.map_err(|e| e.to_string())?;
+    ).execute(pool).await.map_err(|e| e.to_string())?;
 
     // 索引：查询某服务器的高频命令
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_usage_server_rank ON command_usage(server_id, exec_count DESC);")
-        .execute(&pool).await.map_err(|e| e.to_string())?;
+        .execute(pool).await.map_err(|e| e.to_string())?;
 
     // 6.3 历史流水表
     sqlx::query(
@@ -183,11 +193,232 @@ sqlx::query(
             executed_at INTEGER NOT NULL,
             FOREIGN KEY(command_id) REFERENCES command_history(id) ON DELETE CASCADE
         );"
-    ).execute(&pool).await.map_err(|e| e.to_string())?;
+    ).execute(pool).await.map_err(|e| e.to_string())?;
+
+    // 执行结果 (片段执行等非交互来源记录；终端输入无法得知结果，为 NULL)
+    ensure_column(pool, "command_events", "status", "TEXT").await?;
+    ensure_column(pool, "command_events", "exit_code", "INTEGER").await?;
 
     // 索引：查询流水线
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_events_timeline ON command_events(server_id, executed_at DESC);")
-        .execute(&pool).await.map_err(|e| e.to_string())?;
+        .execute(pool).await.map_err(|e| e.to_string())?;
 
-    Ok(pool)
-}
\ No newline at end of file
+    // 🟢 [新增] 7. Port Forwards 表 (-L / -R / -D 规则)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS port_forwards (
+            id TEXT PRIMARY KEY,
+            server_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            bind_host TEXT NOT NULL DEFAULT '127.0.0.1',
+            bind_port INTEGER NOT NULL,
+            target_host TEXT,
+            target_port INTEGER,
+            auto_start BOOLEAN DEFAULT 0,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );"
+    ).execute(pool).await.map_err(|e| e.to_string())?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_forwards_server ON port_forwards(server_id);")
+        .execute(pool).await.map_err(|e| e.to_string())?;
+
+    // 🟢 [新增] 8. 片段检索：标签拆表 + FTS5 全文索引，均由触发器随 snippets 表同步
+    init_snippet_search(pool).await?;
+
+    // 🟢 [新增] 9. 片段修订历史：内容变化时由触发器追加一版，删除片段时一并清理
+    init_snippet_revisions(pool).await?;
+
+    // 🟢 [新增] 10. 片段 Git 同步配置 (单行)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS snippet_sync (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            repo_path TEXT NOT NULL,
+            remote_url TEXT,
+            branch TEXT NOT NULL DEFAULT 'main',
+            last_commit TEXT,
+            last_synced_at INTEGER
+        );"
+    ).execute(pool).await.map_err(|e| e.to_string())?;
+
+    // 🟢 [新增] 文件传输队列 + 并发设置
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS transfers (
+            id TEXT PRIMARY KEY,
+            session_id TEXT NOT NULL,
+            server_id TEXT,
+            kind TEXT NOT NULL,
+            name TEXT NOT NULL,
+            local_path TEXT NOT NULL,
+            remote_path TEXT NOT NULL,
+            total_bytes INTEGER NOT NULL DEFAULT 0,
+            transferred INTEGER NOT NULL DEFAULT 0,
+            status TEXT NOT NULL,
+            error TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );"
+    ).execute(pool).await.map_err(|e| e.to_string())?;
+    ensure_column(pool, "transfers", "verify", "INTEGER NOT NULL DEFAULT 1").await?;
+    ensure_column(pool, "transfers", "checksum", "TEXT").await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS transfer_settings (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            max_workers INTEGER NOT NULL DEFAULT 3
+        );"
+    ).execute(pool).await.map_err(|e| e.to_string())?;
+
+    // 🟢 [新增] 文件夹同步配置 + 每个配置上次同步完成时的文件状态 (用于区分删除与新增)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS sync_profiles (
+            id TEXT PRIMARY KEY,
+            server_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            local_path TEXT NOT NULL,
+            remote_path TEXT NOT NULL,
+            direction TEXT NOT NULL DEFAULT 'both',
+            compare TEXT NOT NULL DEFAULT 'mtime',
+            include_globs TEXT NOT NULL DEFAULT '',
+            exclude_globs TEXT NOT NULL DEFAULT '',
+            propagate_deletes BOOLEAN DEFAULT 0,
+            last_synced_at INTEGER,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );"
+    ).execute(pool).await.map_err(|e| e.to_string())?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_sync_profiles_server ON sync_profiles(server_id);")
+        .execute(pool).await.map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS sync_state (
+            profile_id TEXT NOT NULL,
+            path TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            local_mtime INTEGER NOT NULL,
+            remote_mtime INTEGER NOT NULL,
+            PRIMARY KEY (profile_id, path)
+        );"
+    ).execute(pool).await.map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+async fn init_snippet_revisions(pool: &Pool<Sqlite>) -> Result<(), String> {
+    let statements = [
+        "CREATE TABLE IF NOT EXISTS snippet_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            snippet_id TEXT NOT NULL,
+            revision INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            code TEXT NOT NULL,
+            language TEXT NOT NULL,
+            tags TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            UNIQUE (snippet_id, revision)
+        );",
+        "CREATE TRIGGER IF NOT EXISTS snippets_revision_ai AFTER INSERT ON snippets BEGIN
+            INSERT INTO snippet_revisions(snippet_id, revision, title, code, language, tags, created_at)
+            VALUES (new.id, 1, new.title, new.code, new.language, new.tags, new.updated_at);
+        END;",
+        // 只改 updated_at 不算新版本；升级前创建的片段没有历史，先补记修改前的内容
+        "CREATE TRIGGER IF NOT EXISTS snippets_revision_au AFTER UPDATE ON snippets
+         WHEN old.title IS NOT new.title OR old.code IS NOT new.code
+           OR old.language IS NOT new.language OR old.tags IS NOT new.tags
+         BEGIN
+            INSERT INTO snippet_revisions(snippet_id, revision, title, code, language, tags, created_at)
+            SELECT old.id, 1, old.title, old.code, old.language, old.tags, old.updated_at
+            WHERE NOT EXISTS (SELECT 1 FROM snippet_revisions WHERE snippet_id = old.id);
+            INSERT INTO snippet_revisions(snippet_id, revision, title, code, language, tags, created_at)
+            SELECT new.id, COALESCE(MAX(revision), 0) + 1, new.title, new.code, new.language, new.tags, new.updated_at
+            FROM snippet_revisions WHERE snippet_id = new.id;
+        END;",
+        "CREATE TRIGGER IF NOT EXISTS snippets_revision_ad AFTER DELETE ON snippets BEGIN
+            DELETE FROM snippet_revisions WHERE snippet_id = old.id;
+        END;",
+    ];
+    for sql in statements {
+        sqlx::query(sql).execute(pool).await.map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+async fn init_snippet_search(pool: &Pool<Sqlite>) -> Result<(), String> {
+    let fts_exists: Option<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'snippets_fts'"
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let statements = [
+        // 标签忽略大小写去重，保留首次写入的写法用于展示
+        "CREATE TABLE IF NOT EXISTS snippet_tags (
+            snippet_id TEXT NOT NULL,
+            tag TEXT NOT NULL COLLATE NOCASE,
+            PRIMARY KEY (snippet_id, tag)
+        );",
+        "CREATE INDEX IF NOT EXISTS idx_snippet_tags_tag ON snippet_tags(tag);",
+        "CREATE INDEX IF NOT EXISTS idx_snippets_language ON snippets(language);",
+        // 以 snippet_id 关联而非 rowid：snippets 为 TEXT 主键，VACUUM 可能重排隐式 rowid
+        "CREATE VIRTUAL TABLE IF NOT EXISTS snippets_fts USING fts5(
+            snippet_id UNINDEXED, title, code, tags,
+            tokenize = 'unicode61 remove_diacritics 2'
+        );",
+        "CREATE TRIGGER IF NOT EXISTS snippets_search_ai AFTER INSERT ON snippets BEGIN
+            INSERT INTO snippets_fts(snippet_id, title, code, tags) VALUES (new.id, new.title, new.code, new.tags);
+            INSERT OR IGNORE INTO snippet_tags(snippet_id, tag)
+                SELECT new.id, trim(value) FROM json_each(CASE WHEN json_valid(new.tags) THEN new.tags ELSE '[]' END)
+                WHERE trim(value) <> '';
+        END;",
+        "CREATE TRIGGER IF NOT EXISTS snippets_search_ad AFTER DELETE ON snippets BEGIN
+            DELETE FROM snippets_fts WHERE snippet_id = old.id;
+            DELETE FROM snippet_tags WHERE snippet_id = old.id;
+        END;",
+        "CREATE TRIGGER IF NOT EXISTS snippets_search_au AFTER UPDATE ON snippets BEGIN
+            DELETE FROM snippets_fts WHERE snippet_id = old.id;
+            INSERT INTO snippets_fts(snippet_id, title, code, tags) VALUES (new.id, new.title, new.code, new.tags);
+            DELETE FROM snippet_tags WHERE snippet_id = old.id;
+            INSERT OR IGNORE INTO snippet_tags(snippet_id, tag)
+                SELECT new.id, trim(value) FROM json_each(CASE WHEN json_valid(new.tags) THEN new.tags ELSE '[]' END)
+                WHERE trim(value) <> '';
+        END;",
+    ];
+    for sql in statements {
+        sqlx::query(sql).execute(pool).await.map_err(|e| e.to_string())?;
+    }
+
+    // 首次建立索引时回填已有片段
+    if fts_exists.is_none() {
+        sqlx::query("INSERT INTO snippets_fts(snippet_id, title, code, tags) SELECT id, title, code, tags FROM snippets;")
+            .execute(pool).await.map_err(|e| e.to_string())?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO snippet_tags(snippet_id, tag)
+             SELECT s.id, trim(j.value) FROM snippets s, json_each(s.tags) j
+             WHERE json_valid(s.tags) AND trim(j.value) <> '';"
+        )
+        .execute(pool).await.map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// 列不存在时执行 ALTER TABLE ADD COLUMN
+async fn ensure_column(pool: &Pool<Sqlite>, table: &str, column: &str, decl: &str) -> Result<(), String> {
+    let exists: Option<String> = sqlx::query_scalar(&format!(
+        "SELECT name FROM pragma_table_info('{}') WHERE name = ?",
+        table
+    ))
+    .bind(column)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if exists.is_none() {
+        sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl))
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
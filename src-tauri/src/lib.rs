@@ -25,6 +25,8 @@ use tauri_plugin_autostart::MacosLauncher;
 use commands::fs::{
     list_ssh_files, sftp_copy, sftp_create_file, sftp_delete, sftp_download_file, sftp_mkdir,
     sftp_rename, sftp_upload_file, sftp_chmod, sftp_write_file, sftp_read_file,
+    sftp_scan_tree, sftp_upload_tree, sftp_download_tree, sftp_delete_tree, sftp_move,
+    list_sync_profiles, save_sync_profile, delete_sync_profile, sftp_sync_plan, sftp_sync_apply,
 };
 use commands::server::*;
 use commands::backup::*;
@@ -34,6 +36,7 @@ use commands::vault::{
     add_key, delete_key, get_all_keys, get_decrypted_content, init_vault, lock_vault, unlock_vault, get_vault_status, check_key_associations
 };
 use commands::snippet::*;
+use commands::transfer::*;
 use commands::monitor::{
     get_ssh_cpu_info, get_ssh_mem_info, get_ssh_disk_info, get_ssh_os_info, get_ssh_network_info,
 };
@@ -87,6 +90,10 @@ pub fn run() {
             let pool = tauri::async_runtime::block_on(async move {
                 db::init_db(&handle).await.expect("数据库初始化失败")
             });
+            // 🟢 [新增] 传输队列 (载入上次未完成的任务)
+            let transfers = tauri::async_runtime::block_on(TransferManager::load(pool.clone()))
+                .expect("传输队列初始化失败");
+            app.manage(transfers);
             app.manage(AppState { db: pool });
 
             // 🟢 [修改] 托盘逻辑已经被 cfg(desktop) 包裹，在移动端会自动跳过
@@ -139,13 +146,27 @@ pub fn run() {
             // ... (此处保持你原来的命令注册列表不变)
             list_servers, save_server, delete_server, update_last_connected,
             connect_ssh, write_ssh, resize_ssh, disconnect_ssh, test_connection,
-            check_host_key, trust_host_key, quick_connect,
+            check_host_key, trust_host_key, quick_connect, parse_destination, respond_auth_prompt,
+            list_port_forwards, save_port_forward, delete_port_forward,
+            start_port_forward, stop_port_forward, get_port_forward_status,
+            exec_remote, exec_remote_stream,
+            create_broadcast_group, list_broadcast_groups, delete_broadcast_group, add_broadcast_members,
+            remove_broadcast_members, set_broadcast_member_paused, write_broadcast,
             get_ssh_cpu_info, get_ssh_mem_info, get_ssh_disk_info, get_ssh_os_info, get_ssh_network_info,
             list_ssh_files, sftp_mkdir, sftp_create_file, sftp_rename, sftp_delete, sftp_copy,
             sftp_download_file, sftp_upload_file, sftp_chmod, sftp_read_file, sftp_write_file,
+            sftp_scan_tree, sftp_upload_tree, sftp_download_tree, sftp_delete_tree, sftp_move,
+            list_sync_profiles, save_sync_profile, delete_sync_profile, sftp_sync_plan, sftp_sync_apply,
+            enqueue_transfer, list_transfers, pause_transfer, resume_transfer, cancel_transfer,
+            remove_transfer, clear_finished_transfers, get_transfer_settings, set_transfer_concurrency,
             init_vault, unlock_vault, lock_vault, add_key, delete_key, 
             get_decrypted_content, get_all_keys, get_vault_status, check_key_associations,
             get_all_snippets, add_snippet, update_snippet, delete_snippet,
+            execute_snippet, parse_snippet_template, render_snippet_template,
+            export_snippets, import_snippets, search_snippets,
+            list_snippet_revisions, diff_snippet_revisions, restore_snippet_revision,
+            get_snippet_sync_config, save_snippet_sync_config, sync_snippets_git,
+            resolve_snippet_sync_conflict, abort_snippet_sync,
             add_proxy, get_all_proxies, update_proxy, delete_proxy,
             get_system_fonts, save_webdav_password, check_webdav, create_cloud_backup,
             get_backup_list, delete_cloud_backup, restore_cloud_backup,
@@ -107,6 +107,12 @@ pub struct ServerConfig {
     pub keep_alive_interval: Option<u32>,
     pub auto_reconnect: Option<bool>,
     pub max_reconnects: Option<u32>,
+
+    // 🟢 [新增] 将本机 ssh-agent 转发给远端 Shell
+    pub agent_forwarding: Option<bool>,
+
+    // 🟢 [新增] 远端 GUI 程序显示到本机 DISPLAY (ssh -X)
+    pub x11_forwarding: Option<bool>,
 }
 
 // 默认值函数
@@ -137,6 +143,23 @@ pub struct SshConfig {
     pub password_id: Option<String>,
     pub password_source: Option<String>,
 
+    // 🟢 [新增] 跳板机：connection_type = proxy 时 proxy_id 指向另一台已保存的服务器
+    #[serde(default)]
+    pub connection_type: Option<ConnectionType>,
+    pub proxy_id: Option<String>,
+    // 解析后的跳板链 (最外层在前)，每一跳使用各自的凭证与 known_hosts 校验
+    #[serde(default)]
+    pub jump_hosts: Vec<SshConfig>,
+    // 🟢 [新增] connection_type = http / socks5 时解析出的代理 (仅作用于最外层 TCP 连接)
+    #[serde(default)]
+    pub proxy: Option<Proxy>,
+
+    // 🟢 [新增] auth_type = agent 时经 SSH_AUTH_SOCK 认证；agent_forwarding 为按服务器开启的 -A
+    #[serde(default)]
+    pub auth_type: Option<AuthType>,
+    pub agent_forwarding: Option<bool>,
+    pub x11_forwarding: Option<bool>,
+
     // 🟢 [关键修复 2] 这里就是报错的根源！必须手动加上这 4 个字段
     pub connect_timeout: Option<u32>,
     pub keep_alive_interval: Option<u32>,
@@ -171,6 +194,87 @@ pub struct SnippetDto {
     pub updated_at: i64,
 }
 
+// 片段 Git 同步配置 (snippet_sync 表，仅一行)
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetSyncConfig {
+    /// 本地工作副本目录
+    pub repo_path: String,
+    /// 为空时只在本地仓库提交，不拉取 / 推送
+    pub remote_url: Option<String>,
+    pub branch: String,
+    #[serde(default)]
+    pub last_commit: Option<String>,
+    #[serde(default)]
+    pub last_synced_at: Option<i64>,
+}
+
+// 文件传输任务 (transfers 表，重启后恢复队列)
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferJob {
+    pub id: String,
+    /// 执行传输的 SSH 会话；重启后失效，恢复时可换绑到新会话
+    pub session_id: String,
+    pub server_id: Option<String>,
+    pub kind: String, // upload | download
+    pub name: String,
+    pub local_path: String,
+    pub remote_path: String,
+    pub total_bytes: i64,
+    pub transferred: i64,
+    pub status: String, // queued | running | paused | completed | failed | cancelled
+    pub error: Option<String>,
+    /// 完成后校验 SHA-256
+    pub verify: bool,
+    /// 校验通过后的 SHA-256 (十六进制)
+    pub checksum: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+// 文件夹同步配置 (sync_profiles 表，按服务器保存)
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncProfile {
+    #[serde(default)]
+    pub id: String,
+    pub server_id: String,
+    pub name: String,
+    pub local_path: String,
+    pub remote_path: String,
+    pub direction: String, // both | upload (本地 -> 远程镜像) | download (远程 -> 本地镜像)
+    pub compare: String,   // size | mtime | hash
+    /// 每行一个 glob，为空时同步全部文件
+    #[serde(default)]
+    pub include_globs: String,
+    #[serde(default)]
+    pub exclude_globs: String,
+    /// 双向时一侧删除的文件在另一侧也删除；镜像时删除目标端多余的文件
+    #[serde(default)]
+    pub propagate_deletes: bool,
+    #[serde(default)]
+    pub last_synced_at: Option<i64>,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl From<Snippet> for SnippetDto {
+    fn from(row: Snippet) -> Self {
+        Self {
+            id: row.id,
+            title: row.title,
+            code: row.code,
+            language: row.language,
+            tags: serde_json::from_str(&row.tags).unwrap_or_default(), // JSON String -> Vec
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct Proxy {
@@ -215,9 +319,37 @@ pub struct TestConnectionPayload {
 
     // 高级设置
     pub connect_timeout: Option<u32>,
+    #[serde(default)]
+    pub connection_type: Option<ConnectionType>,
     pub proxy_id: Option<String>,
 }
 
+// =========================================================
+// Port Forwarding (端口转发规则，按服务器保存)
+// =========================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct PortForwardRule {
+    pub id: String,
+    pub server_id: String,
+    pub name: String,
+    pub kind: String, // "local" (-L) | "remote" (-R) | "dynamic" (-D)
+
+    // local / dynamic：本机监听地址；remote：远端监听地址
+    pub bind_host: String,
+    pub bind_port: u16,
+
+    // local / remote 的转发目标 (dynamic 由 SOCKS 请求决定)
+    pub target_host: Option<String>,
+    pub target_port: Option<u16>,
+
+    // 随会话连接自动启动
+    pub auto_start: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
 // =========================================================
 // Command History (命令历史记录)
 // =========================================================
@@ -7,6 +7,7 @@ import { SnippetPage } from '@/features/snippet/SnippetPage';
 import { DashboardPage } from '@/features/dashboard/DashboardPage';
 import './locales/i18n';
 import { GlobalVaultModal } from '@/features/keys/components/GlobalVaultModal';
+import { AuthPromptModal } from '@/features/server/components/AuthPromptModal';
 import { useSettingsEffects } from '@/features/settings/hooks/useSettingsEffects';
 import { SettingsPage } from "@/features/settings/presentation/SettingsPage";
 import { FileEditorPage } from './windows/FileEditorPage';
@@ -121,6 +122,8 @@ function App() {
       )}
 
       <GlobalVaultModal />
+      {/* SSH 二次验证 (keyboard-interactive) */}
+      <AuthPromptModal />
       <Toaster richColors closeButton position="top-center" style={{ zIndex: 999999 }} />
       
       <Routes>
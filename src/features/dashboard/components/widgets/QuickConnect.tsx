@@ -3,12 +3,22 @@ import { useTranslation } from "react-i18next";
 import { useNavigate } from "react-router-dom";
 import { Zap, ArrowRight, Monitor, Command } from "lucide-react"; 
 import { toast } from "sonner";
+import { invoke } from "@tauri-apps/api/core";
+import { ask } from "@tauri-apps/plugin-dialog";
 import { useTerminalStore } from "@/store/useTerminalStore";
 import { useServerStore } from "@/features/server/application/useServerStore";
 import { Server } from "@/features/server/domain/types";
 import { v4 as uuidv4 } from 'uuid';
 import { cn } from "@/lib/utils"; // 🟢 引入 cn 工具函数以匹配 DashboardHeader 风格
 
+// 🟢 [新增] 后端 parse_destination 的解析结果
+interface QuickDestination {
+  username: string;
+  host: string;
+  port: number;
+  keyId?: string | null;
+}
+
 export const QuickConnect = () => {
   const { t } = useTranslation();
   const navigate = useNavigate();
@@ -20,31 +30,55 @@ export const QuickConnect = () => {
   const handleConnect = async () => {
     if (!input.trim()) return;
 
-    // 1. 解析格式
-    const regex = /^(([^@]+)@)?([^:]+)(:(\d+))?$/;
-    const match = input.match(regex);
-
-    if (!match) {
-      toast.error(t('dashboard.quickConnect.invalidFormat', 'Invalid format. Use: user@host:port'));
+    // 1. 解析格式 (user@host:port / ssh://user@host:port / -i keyid，由后端统一解析)
+    let dest: QuickDestination;
+    try {
+      dest = await invoke<QuickDestination>('parse_destination', { input: input.trim() });
+    } catch (e) {
+      toast.error(t('dashboard.quickConnect.invalidFormat', 'Invalid format. Use: user@host:port'), { description: String(e) });
       return;
     }
 
-    const username = match[2] || "root";
-    const host = match[3];
-    const port = parseInt(match[5] || "22", 10);
+    const { username, host, port } = dest;
+    const keyId = dest.keyId || undefined;
+    const quickId = `quick-${uuidv4()}`;
 
-    toast.info(t('dashboard.quickConnect.connecting', 'Connecting to {{host}}...', { host }));
+    // 2. 主机密钥预检查 (首次连接需用户确认指纹)
+    try {
+      const check = await invoke<{ status: string, data?: { keyType: string, fingerprint: string } }>('check_host_key', {
+        id: quickId, host, port
+      });
+      if (check.status !== 'verified' && check.data) {
+        const trusted = await ask(
+          t('dashboard.quickConnect.verifyBody',
+            `The authenticity of host "${host}" can't be established.\n${check.data.keyType} key fingerprint is ${check.data.fingerprint}.\n\nTrust this host and continue?`,
+            { host, keyType: check.data.keyType, fingerprint: check.data.fingerprint }),
+          {
+            title: t('server.verify.title', 'New Host Verification'),
+            kind: 'warning',
+            okLabel: t('common.confirm', 'Trust'),
+            cancelLabel: t('common.cancel', 'Cancel')
+          }
+        );
+        if (!trusted) return;
+        await invoke('trust_host_key', { id: quickId, fingerprint: check.data.fingerprint, keyType: check.data.keyType });
+      }
+    } catch (e) {
+      toast.error(t('dashboard.quickConnect.error', 'Failed to initialize connection'), { description: String(e) });
+      return;
+    }
 
-    const quickId = `quick-${uuidv4()}`;
+    toast.info(t('dashboard.quickConnect.connecting', 'Connecting to {{host}}...', { host }));
 
-    // 2. 构建 Server 对象
+    // 3. 构建 Server 对象
     const quickServer: Server = {
         id: quickId,
         name: `${username}@${host}:${port}`,
         ip: host,
         port: port,
         username: username,
-        authType: 'password', 
+        authType: keyId ? 'key' : 'password',
+        keyId,
         connectionType: 'direct',
         os: 'linux', 
         icon: 'zap', 
@@ -56,12 +90,12 @@ export const QuickConnect = () => {
         createdAt: Date.now(),
         updatedAt: Date.now(),
         passwordSource: 'manual',
-        keySource: 'manual',
+        keySource: keyId ? 'store' : 'manual',
         password: "" 
     };
 
     try {
-        // 3. 存入 Store 并跳转
+        // 4. 存入 Store 并跳转
         addTemporaryServer(quickServer);
         createTab(quickServer);
         navigate('/terminal');
@@ -138,7 +172,7 @@ export const QuickConnect = () => {
           </div>
           
           <p className="mt-3 text-xs text-slate-400 dark:text-slate-500 px-1 italic">
-            {t('dashboard.quickConnect.hint', 'Format: user@host or user@host:port')}
+            {t('dashboard.quickConnect.hint', 'Format: user@host:port, ssh://user@host:port or -i keyid user@host')}
           </p>
         </div>
       </div>
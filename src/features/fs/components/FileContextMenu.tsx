@@ -6,7 +6,7 @@ import {
     FilePlus, FolderPlus, RefreshCw, Terminal, 
     Code, ExternalLink, Link, Edit3, Download, 
     Copy, Shield, Trash2, Scissors, ClipboardPaste,
-    Plus, ChevronRight, Upload
+    Plus, ChevronRight, Upload, FolderUp
 } from 'lucide-react';
 import { FileIcon, FolderIcon } from './FileIcons'; 
 
@@ -16,7 +16,7 @@ export type FileActionType =
     // [修复 1] 将 'permissions' 改为 'chmod' 以匹配逻辑层
     | 'download' | 'copy' | 'move' | 'chmod' | 'delete'
     | 'cut' | 'paste'
-    | 'upload';
+    | 'upload' | 'uploadFolder';
 
 interface Props {
     x: number;
@@ -173,7 +173,10 @@ const FileContextMenuComponent = ({ x, y, file, hasClipboard, onClose, onAction
             </div>
             
             {!file && (
-                <MenuItem icon={Upload} label={t('fs.context.upload', 'Upload File')} action="upload" {...commonProps} />
+                <>
+                    <MenuItem icon={Upload} label={t('fs.context.upload', 'Upload File')} action="upload" {...commonProps} />
+                    <MenuItem icon={FolderUp} label={t('fs.context.uploadFolder', 'Upload Folder')} action="uploadFolder" {...commonProps} />
+                </>
             )}
             
             <Divider />
@@ -1,8 +1,8 @@
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { useTransferStore, TransferTask } from '@/store/useTransferStore';
 import { 
   X, Upload, Download, CheckCircle2, AlertCircle, 
-  Loader2, Trash2, FolderOpen, AlertTriangle, Ban 
+  Loader2, Trash2, FolderOpen, AlertTriangle, Ban, Pause, Play, Clock 
 } from 'lucide-react'; // [新增] 引入 Ban 图标用于取消
 import { formatBytes } from '@/utils/format';
 import { clsx } from 'clsx';
@@ -11,12 +11,17 @@ import { useTranslation } from 'react-i18next';
 export const TransferManager = () => {
     const { t } = useTranslation();
     // [新增] 从 store 中解构 cancelTask
-    const { isOpen, tasks, toggleOpen, clearCompleted, removeTask, cancelTask } = useTransferStore();
+    const { isOpen, tasks, toggleOpen, clearCompleted, removeTask, cancelTask, pauseTask, resumeTask, init } = useTransferStore();
 
     // 删除确认弹窗的状态
     const [deleteConfirm, setDeleteConfirm] = useState<{ id: string; name: string } | null>(null);
     const [deleteFileChecked, setDeleteFileChecked] = useState(false);
 
+    // 载入后端持久化的传输队列 (含上次未完成、已转为暂停的任务)
+    useEffect(() => {
+        init().catch(e => console.error('Failed to load transfers:', e));
+    }, [init]);
+
     if (!isOpen) return null;
 
     const safeTasks = tasks || [];
@@ -25,8 +30,7 @@ export const TransferManager = () => {
     // 处理删除逻辑
     const handleConfirmDelete = () => {
         if (deleteConfirm) {
-            // @ts-ignore 
-            removeTask(deleteConfirm.id, deleteFileChecked);
+            removeTask(deleteConfirm.id, deleteFileChecked).catch(e => console.error('Remove failed:', e));
             setDeleteConfirm(null);
             setDeleteFileChecked(false);
         }
@@ -35,9 +39,7 @@ export const TransferManager = () => {
     // 处理取消逻辑
     const handleCancel = (id: string) => {
         // 调用 store 的取消方法
-        if (cancelTask) {
-            cancelTask(id);
-        }
+        cancelTask(id).catch(e => console.error('Cancel failed:', e));
     };
 
     // 模拟打开文件位置
@@ -96,6 +98,8 @@ export const TransferManager = () => {
                                 t={t}
                                 onDeleteRequest={() => setDeleteConfirm({ id: task.id, name: task.name })}
                                 onCancelRequest={() => handleCancel(task.id)}
+                                onPauseRequest={() => pauseTask(task.id).catch(e => console.error('Pause failed:', e))}
+                                onResumeRequest={() => resumeTask(task.id).catch(e => console.error('Resume failed:', e))}
                                 onOpenLocation={() => handleOpenLocation(task)}
                             />
                         ))
@@ -161,16 +165,22 @@ const TransferItem = ({
     t, 
     onDeleteRequest, 
     onCancelRequest,
+    onPauseRequest,
+    onResumeRequest,
     onOpenLocation 
 }: { 
     task: TransferTask; 
     t: any;
     onDeleteRequest: () => void;
     onCancelRequest: () => void;
+    onPauseRequest: () => void;
+    onResumeRequest: () => void;
     onOpenLocation: () => void;
 }) => {
     const isUpload = task.type === 'upload';
     const isRunning = task.status === 'running';
+    const isActive = isRunning || task.status === 'queued';
+    const canResume = task.status === 'paused' || task.status === 'failed';
     
     // @ts-ignore
     const dateStr = new Date(task.startTime).toLocaleString();
@@ -181,8 +191,8 @@ const TransferItem = ({
             {/* Hover Actions Overlay */}
             <div className="absolute inset-0 bg-slate-100/90 dark:bg-slate-800/95 backdrop-blur-[1px] opacity-0 group-hover:opacity-100 transition-opacity flex items-center justify-center gap-3 z-10">
                 
-                {/* 1. 打开文件位置按钮：非运行中 且 非上传 时显示 */}
-                {!isUpload && !isRunning && (
+                {/* 1. 打开文件位置按钮：下载完成后显示 */}
+                {!isUpload && task.status === 'completed' && (
                     <button 
                         onClick={onOpenLocation}
                         title={t('fs.transfer.open_folder', 'Open Folder')}
@@ -192,8 +202,28 @@ const TransferItem = ({
                     </button>
                 )}
 
-                {/* 2. 取消/删除按钮：运行中显示取消，否则显示删除 */}
-                {isRunning ? (
+                {/* 2. 暂停 / 继续 */}
+                {isActive && (
+                    <button 
+                        onClick={onPauseRequest}
+                        title={t('fs.transfer.pause', 'Pause')}
+                        className="p-2 bg-white dark:bg-slate-700 border border-slate-200 dark:border-slate-600 rounded-full shadow-sm text-slate-600 dark:text-slate-300 hover:text-blue-500 hover:border-blue-200 transition-all scale-90 hover:scale-100"
+                    >
+                        <Pause className="w-4 h-4" />
+                    </button>
+                )}
+                {canResume && (
+                    <button 
+                        onClick={onResumeRequest}
+                        title={t('fs.transfer.resume', 'Resume')}
+                        className="p-2 bg-white dark:bg-slate-700 border border-slate-200 dark:border-slate-600 rounded-full shadow-sm text-slate-600 dark:text-slate-300 hover:text-blue-500 hover:border-blue-200 transition-all scale-90 hover:scale-100"
+                    >
+                        <Play className="w-4 h-4" />
+                    </button>
+                )}
+
+                {/* 3. 取消/删除按钮：进行中或暂停时显示取消，否则显示删除 */}
+                {isActive || task.status === 'paused' ? (
                     <button 
                         onClick={onCancelRequest}
                         title={t('fs.transfer.cancel', 'Cancel Transfer')}
@@ -246,15 +276,29 @@ const TransferItem = ({
                 </div>
 
                 {/* Progress Bar */}
-                {task.status === 'running' && (
-                    <div className="h-1 w-full bg-slate-200 dark:bg-slate-700 rounded-full mt-1.5 overflow-hidden">
-                        <div 
-                            className="h-full bg-blue-500 rounded-full transition-all duration-300" 
-                            style={{ width: `${task.progress || 0}%` }}
-                        />
-                    </div>
+                {(isRunning || task.status === 'paused') && (
+                    <>
+                        <div className="h-1 w-full bg-slate-200 dark:bg-slate-700 rounded-full mt-1.5 overflow-hidden">
+                            <div 
+                                className={clsx("h-full rounded-full transition-all duration-300", isRunning ? "bg-blue-500" : "bg-slate-400")}
+                                style={{ width: `${task.progress || 0}%` }}
+                            />
+                        </div>
+                        <div className="flex items-center justify-between text-[9px] text-slate-400 mt-0.5">
+                            <span>{formatBytes(task.transferred)} / {formatBytes(task.size)}</span>
+                            {isRunning && task.phase === 'verify' && (
+                                <span>{t('fs.transfer.verifying', 'Verifying...')}</span>
+                            )}
+                            {isRunning && task.phase !== 'verify' && task.rate > 0 && (
+                                <span>
+                                    {formatBytes(task.rate)}/s
+                                    {task.etaSecs !== undefined && ` · ${formatEta(task.etaSecs)}`}
+                                </span>
+                            )}
+                        </div>
+                    </>
                 )}
-                {task.status === 'error' && (
+                {task.status === 'failed' && (
                     <div className="text-[10px] text-red-500 mt-1 truncate" title={task.error}>
                         {task.error}
                     </div>
@@ -267,5 +311,14 @@ const TransferItem = ({
 const StatusIcon = ({ status }: { status: TransferTask['status'] }) => {
     if (status === 'running') return <Loader2 className="w-3 h-3 text-blue-500 animate-spin" />;
     if (status === 'completed') return <CheckCircle2 className="w-3 h-3 text-green-500" />;
+    if (status === 'queued') return <Clock className="w-3 h-3 text-slate-400" />;
+    if (status === 'paused') return <Pause className="w-3 h-3 text-slate-400" />;
+    if (status === 'cancelled') return <Ban className="w-3 h-3 text-slate-400" />;
     return <AlertCircle className="w-3 h-3 text-red-500" />;
+};
+
+const formatEta = (secs: number) => {
+    if (secs < 60) return `${secs}s`;
+    if (secs < 3600) return `${Math.floor(secs / 60)}m ${secs % 60}s`;
+    return `${Math.floor(secs / 3600)}h ${Math.floor((secs % 3600) / 60)}m`;
 };
\ No newline at end of file
@@ -1,12 +1,14 @@
-import { useCallback, useState } from 'react';
+import { useCallback, useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { useTranslation } from 'react-i18next';
 import { useFileStore } from '@/store/useFileStore';
 import { useTransferStore } from '@/store/useTransferStore'; 
-import { FileEntry, SortField } from '@/features/fs/types';
+import { BatchReport, CopyReport, FileEntry, SortField } from '@/features/fs/types';
 import { FileActionType } from '../components/FileContextMenu';
 import { ModalType } from '../components/FsActionModals';
 import { open, save } from '@tauri-apps/plugin-dialog';
+import { join } from '@tauri-apps/api/path';
 // [新增] 引入编辑器配置检查
 import { isEditable } from '../editor/config';
 // [新增] 引入 Tauri 窗口 API
@@ -27,8 +29,8 @@ const pathUtils = {
 
 export const useFileActions = (sessionId: string) => {
     const { t } = useTranslation();
-    const { setPath, setSort, getSession, triggerReload, setClipboard } = useFileStore();
-    const { addTask, updateStatus } = useTransferStore(); 
+    const { setPath, setSort, getSession, triggerReload, setClipboard, clipboard } = useFileStore();
+    const enqueue = useTransferStore((s) => s.enqueue);
     
     const connectionId = sessionId;
 
@@ -45,8 +47,6 @@ export const useFileActions = (sessionId: string) => {
     const [toastMessage, setToastMessage] = useState<{msg: string, type: 'success' | 'error'} | null>(null);
     const [isSubmitting, setIsSubmitting] = useState(false);
 
-    const sessionState = getSession(sessionId);
-    const clipboard = sessionState.clipboard;
     const hasClipboard = !!clipboard && clipboard.files.length > 0;
 
     const showToast = (msg: string, type: 'success' | 'error' = 'success') => {
@@ -56,6 +56,31 @@ export const useFileActions = (sessionId: string) => {
 
     const refresh = useCallback(() => triggerReload(sessionId), [sessionId, triggerReload]);
 
+    // 目录级操作的结果提示：有失败路径时列出第一条
+    const showBatchResult = (report: BatchReport, okMsg: string) => {
+        if (report.failures.length === 0) {
+            showToast(okMsg);
+            return;
+        }
+        const first = report.failures[0];
+        console.warn('Batch failures:', report.failures);
+        showToast(
+            t('fs.msg.batchPartial', '{{count}} item(s) failed: {{path}} ({{error}})', {
+                count: report.failures.length, path: first.path, error: first.error
+            }),
+            'error'
+        );
+    };
+
+    // 上传完成后刷新当前会话的文件列表
+    useEffect(() => {
+        const unlisten = listen<{ sessionId: string; kind: string; status: string }>('transfer-status', (e) => {
+            const job = e.payload;
+            if (job.kind === 'upload' && job.status === 'completed' && job.sessionId === connectionId) refresh();
+        });
+        return () => { unlisten.then(f => f()); };
+    }, [connectionId, refresh]);
+
     const handleSort = useCallback((field: SortField) => {
         setSort(sessionId, field);
     }, [sessionId, setSort]);
@@ -141,47 +166,42 @@ export const useFileActions = (sessionId: string) => {
             if (!fileName) return;
 
             const remotePath = pathUtils.join(currentPath, fileName);
-            const transferId = Date.now().toString(); 
-            addTask({
-                id: transferId,
-                type: 'upload',
-                name: fileName,
-                localPath: localPath,
-                remotePath: remotePath,
-                size: 0,
-                status: 'running',
-                progress: 0,
-                startTime: Date.now()
-            });
 
-            setIsSubmitting(true);
-            showToast(t('fs.msg.uploading', 'Uploading...'));
-
-            await invoke('sftp_upload_file', {
-                id: connectionId,
+            // 交给后端传输队列，进度与结果通过 transfer-status 事件回到传输面板
+            await enqueue({
+                sessionId: connectionId,
+                kind: 'upload',
+                name: fileName,
                 localPath,
                 remotePath
             });
-
-            updateStatus(transferId, 'completed');
-            showToast(t('fs.msg.uploadSuccess', 'Upload successful'));
-            refresh(); 
+            showToast(t('fs.msg.uploadQueued', 'Upload queued'));
         } catch (error: any) {
             console.error("Upload failed:", error);
             showToast(t('fs.msg.uploadFailed', 'Upload failed'), 'error');
-        } finally {
-            setIsSubmitting(false);
         }
-    }, [sessionId, connectionId, getSession, refresh, t, addTask, updateStatus]);
+    }, [sessionId, connectionId, getSession, t, enqueue]);
 
     const handleDownload = useCallback(async (file: FileEntry) => {
         if (!connectionId) return;
 
-        const transferId = Date.now().toString();
-
         try {
             if (file.isDir) {
-                showToast(t('fs.error.folderDownload', 'Folder download not supported yet'), 'error');
+                const parent = await open({
+                    directory: true,
+                    title: t('fs.context.downloadFolder', 'Download folder to')
+                });
+                if (!parent || Array.isArray(parent)) return;
+
+                setIsSubmitting(true);
+                showToast(t('fs.msg.downloading', 'Downloading...'));
+                const report = await invoke<BatchReport>('sftp_download_tree', {
+                    id: connectionId,
+                    remotePath: file.path,
+                    localPath: await join(parent, file.name),
+                    symlinks: 'skip'
+                });
+                showBatchResult(report, t('fs.msg.downloadSuccess', 'Download successful'));
                 return;
             }
 
@@ -192,43 +212,59 @@ export const useFileActions = (sessionId: string) => {
 
             if (!localPath) return; 
 
-            addTask({
-                id: transferId,
-                type: 'download',
+            await enqueue({
+                sessionId: connectionId,
+                kind: 'download',
                 name: file.name,
-                localPath: localPath,
-                remotePath: file.path,
-                size: file.size,
-                status: 'running',
-                progress: 0,
-                startTime: Date.now()
+                localPath,
+                remotePath: file.path
             });
+            showToast(t('fs.msg.downloadQueued', 'Download queued'));
+        } catch (error: any) {
+            console.error("Download failed:", error);
+            showToast(t('fs.msg.downloadFailed', 'Download failed'), 'error');
+        } finally {
+            setIsSubmitting(false);
+        }
+    }, [sessionId, connectionId, t, enqueue]);
 
-            setIsSubmitting(true);
-            showToast(t('fs.msg.downloading', 'Downloading...'));
+    const handleUploadFolder = useCallback(async () => {
+        if (!connectionId) return;
+        const currentPath = getSession(sessionId).currentPath;
 
-            await invoke('sftp_download_file', {
-                id: connectionId,
-                remotePath: file.path,
-                localPath
+        try {
+            const localPath = await open({
+                directory: true,
+                title: t('fs.context.uploadFolder', 'Upload Folder')
             });
+            if (!localPath || Array.isArray(localPath)) return;
+
+            const folderName = localPath.split(/[\\/]/).filter(Boolean).pop();
+            if (!folderName) return;
 
-            updateStatus(transferId, 'completed');
-            showToast(t('fs.msg.downloadSuccess', 'Download successful'));
+            setIsSubmitting(true);
+            showToast(t('fs.msg.uploading', 'Uploading...'));
+            const report = await invoke<BatchReport>('sftp_upload_tree', {
+                id: connectionId,
+                localPath,
+                remotePath: pathUtils.join(currentPath, folderName),
+                symlinks: 'skip'
+            });
+            showBatchResult(report, t('fs.msg.uploadSuccess', 'Upload successful'));
+            refresh();
         } catch (error: any) {
-            console.error("Download failed:", error);
-            updateStatus(transferId, 'error', error.toString());
-            showToast(t('fs.msg.downloadFailed', 'Download failed'), 'error');
+            console.error("Upload failed:", error);
+            showToast(t('fs.msg.uploadFailed', 'Upload failed'), 'error');
         } finally {
             setIsSubmitting(false);
         }
-    }, [sessionId, connectionId, t, addTask, updateStatus]);
+    }, [sessionId, connectionId, getSession, refresh, t]);
 
     const handlePaste = useCallback(async () => {
         if (!connectionId) return;
 
         const state = getSession(sessionId);
-        const clipboard = state.clipboard;
+        const clipboard = useFileStore.getState().clipboard;
         if (!clipboard || !clipboard.files.length) return;
 
         setIsSubmitting(true);
@@ -236,11 +272,13 @@ export const useFileActions = (sessionId: string) => {
         const targetFiles = state.files;
 
         let successCount = 0;
+        // 源在另一会话时由后端经本机中转；同一会话优先在服务器上执行 cp / mv
+        const isSameSession = clipboard.sessionId === connectionId;
 
         try {
             for (const file of clipboard.files) {
                 let targetName = file.name;
-                const isSameDir = clipboard.sourcePath === currentPath;
+                const isSameDir = isSameSession && clipboard.sourcePath === currentPath;
                 let collision = targetFiles.find(f => f.name === targetName);
 
                 if (collision) {
@@ -256,14 +294,15 @@ export const useFileActions = (sessionId: string) => {
                 const fromPath = file.path;
                 const toPath = pathUtils.join(currentPath, targetName);
 
-                if (clipboard.type === 'copy') {
-                    if (file.isDir) {
-                        showToast(t('fs.error.folderCopy', 'Folder copy not supported yet'), 'error');
-                        continue;
-                    }
-                    await invoke('sftp_copy', { id: connectionId, fromPath, toPath });
-                } else {
-                    await invoke('sftp_rename', { id: connectionId, oldPath: fromPath, newPath: toPath });
+                const report = await invoke<CopyReport>(clipboard.type === 'copy' ? 'sftp_copy' : 'sftp_move', {
+                    id: clipboard.sessionId,
+                    fromPath,
+                    toPath,
+                    targetId: connectionId
+                });
+                if (report.failures.length > 0) {
+                    showBatchResult(report, '');
+                    continue;
                 }
                 successCount++;
             }
@@ -273,6 +312,8 @@ export const useFileActions = (sessionId: string) => {
                 refresh();
                 if (clipboard.type === 'move') {
                     setClipboard(sessionId, null);
+                    // 源会话的列表也已变化
+                    if (!isSameSession) triggerReload(clipboard.sessionId);
                 }
             }
         } catch (err: any) {
@@ -281,7 +322,7 @@ export const useFileActions = (sessionId: string) => {
         } finally {
             setIsSubmitting(false);
         }
-    }, [sessionId, connectionId, getSession, refresh, t, setClipboard]);
+    }, [sessionId, connectionId, getSession, refresh, t, setClipboard, triggerReload]);
 
     // 菜单动作分发
     const executeAction = useCallback(async (action: FileActionType, file?: FileEntry) => {
@@ -360,13 +401,16 @@ export const useFileActions = (sessionId: string) => {
             case 'upload':
                 handleUpload();
                 break;
+            case 'uploadFolder':
+                handleUploadFolder();
+                break;
             case 'download':
                 if (file) handleDownload(file);
                 break;
                 
             default: break;
         }
-    }, [sessionId, connectionId, getSession, refresh, t, setClipboard, handlePaste, handleUpload, handleDownload, openEditorWindow]);
+    }, [sessionId, connectionId, getSession, refresh, t, setClipboard, handlePaste, handleUpload, handleUploadFolder, handleDownload, openEditorWindow]);
 
     // 弹窗确认逻辑
     const handleModalConfirm = useCallback(async (rawInput?: string, options?: { recursive: boolean }) => {
@@ -411,8 +455,14 @@ export const useFileActions = (sessionId: string) => {
                 showToast(t('fs.msg.chmodSuccess', 'Permissions updated'));
             }
             else if (type === 'delete' && file) {
-                await invoke('sftp_delete', { id: connectionId, path: file.path, isDir: file.isDir });
-                showToast(t('fs.msg.deleteSuccess', 'Deleted successfully'));
+                if (file.isDir) {
+                    // 目录递归删除，部分失败时提示第一条
+                    const report = await invoke<BatchReport>('sftp_delete_tree', { id: connectionId, path: file.path });
+                    showBatchResult(report, t('fs.msg.deleteSuccess', 'Deleted successfully'));
+                } else {
+                    await invoke('sftp_delete', { id: connectionId, path: file.path, isDir: false });
+                    showToast(t('fs.msg.deleteSuccess', 'Deleted successfully'));
+                }
             }
 
             refresh();
@@ -438,6 +488,7 @@ export const useFileActions = (sessionId: string) => {
         hasClipboard, 
         clipboardType: clipboard?.type,
         handleUpload,
+        handleUploadFolder,
         handleDownload,
         
         // [修改] 不再导出 editorState 和 closeEditor，因为现在是独立窗口模式
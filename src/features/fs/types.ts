@@ -32,4 +32,95 @@ export interface TransferItem {
   status: TransferStatus;
   startTime: number;
   error?: string;
-}
\ No newline at end of file
+}
+// 目录级操作 (递归上传 / 下载 / 删除) 的结果
+export type SymlinkMode = 'skip' | 'follow' | 'copy';
+
+export interface PathError {
+  path: string;
+  error: string;
+}
+
+export interface TreeScan {
+  files: number;
+  dirs: number;
+  symlinks: number;
+  totalBytes: number;
+  skipped: string[];
+  failures: PathError[];
+}
+
+export interface BatchReport {
+  files: number;
+  dirs: number;
+  symlinks: number;
+  bytes: number;
+  skipped: string[];
+  failures: PathError[];
+}
+
+// 远程复制 / 移动结果：cp / rsync / rename / mv 在服务器上执行，stream 经本机中转
+export type CopyMethod = 'cp' | 'rsync' | 'rename' | 'mv' | 'stream';
+
+export interface CopyReport extends BatchReport {
+  method: CopyMethod;
+}
+
+export interface BatchProgress {
+  doneEntries: number;
+  totalEntries: number;
+  doneBytes: number;
+  totalBytes: number;
+  currentPath: string;
+}
+
+// 文件夹同步 (本地 <-> 远程)
+export type SyncDirection = 'both' | 'upload' | 'download';
+export type SyncCompare = 'size' | 'mtime' | 'hash';
+export type SyncAction = 'upload' | 'download' | 'deleteLocal' | 'deleteRemote' | 'conflict' | 'skip';
+
+export interface SyncProfile {
+  id: string; // 为空时新建
+  serverId: string;
+  name: string;
+  localPath: string;
+  remotePath: string;
+  direction: SyncDirection;
+  compare: SyncCompare;
+  includeGlobs: string; // 每行一个 glob
+  excludeGlobs: string;
+  propagateDeletes: boolean;
+  lastSyncedAt?: number;
+  createdAt?: number;
+  updatedAt?: number;
+}
+
+export interface SyncFileState {
+  size: number;
+  mtime: number; // 秒
+}
+
+export interface SyncItem {
+  path: string;
+  action: SyncAction;
+  reason: 'new' | 'missing' | 'modified' | 'differs' | 'deleted' | 'extraneous' | 'bothModified' | 'modifiedAndDeleted';
+  local?: SyncFileState;
+  remote?: SyncFileState;
+}
+
+export interface SyncPlan {
+  items: SyncItem[];
+  unchanged: number;
+  uploadBytes: number;
+  downloadBytes: number;
+  failures: PathError[];
+}
+
+export interface SyncReport {
+  uploaded: number;
+  downloaded: number;
+  deleted: number;
+  skipped: number;
+  bytes: number;
+  failures: PathError[];
+}
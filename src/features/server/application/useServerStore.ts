@@ -82,6 +82,8 @@ export const useServerStore = create<ServerState>()(
             keepAliveInterval: s.keepAliveInterval ?? s.keep_alive_interval,
             autoReconnect: s.autoReconnect ?? s.auto_reconnect,
             maxReconnects: s.maxReconnects ?? s.max_reconnects,
+            agentForwarding: !!(s.agentForwarding ?? s.agent_forwarding),
+            x11Forwarding: !!(s.x11Forwarding ?? s.x11_forwarding),
           }));
 
           // 🟢 [核心修复] 获取当前 Store 中已存在的“快速连接”临时数据
@@ -163,6 +165,8 @@ export const useServerStore = create<ServerState>()(
             keepAliveInterval: serverData.keepAliveInterval ?? existingServer?.keepAliveInterval,
             autoReconnect: serverData.autoReconnect ?? existingServer?.autoReconnect,
             maxReconnects: serverData.maxReconnects ?? existingServer?.maxReconnects,
+            agentForwarding: serverData.agentForwarding ?? existingServer?.agentForwarding ?? false,
+            x11Forwarding: serverData.x11Forwarding ?? existingServer?.x11Forwarding ?? false,
         };
 
         if (!existingServer && (!newServer.name || !newServer.ip)) {
@@ -0,0 +1,143 @@
+import { useEffect, useRef, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { ShieldCheck, Loader2, X } from 'lucide-react';
+import { clsx } from 'clsx';
+
+// 后端 ssh-auth-prompt 事件 (keyboard-interactive 认证中的服务端提示)
+export interface AuthPromptRequest {
+  requestId: string;
+  sessionId: string;
+  host: string;
+  username: string;
+  name: string;
+  instructions: string;
+  prompts: { prompt: string; echo: boolean }[];
+}
+
+/**
+ * 全局二次验证弹窗：OTP / Duo / Verification code
+ * 多个连接同时要求验证时按到达顺序依次显示
+ */
+export const AuthPromptModal = () => {
+  const { t } = useTranslation();
+  const [queue, setQueue] = useState<AuthPromptRequest[]>([]);
+  const [answers, setAnswers] = useState<string[]>([]);
+  const [submitting, setSubmitting] = useState(false);
+  const firstInputRef = useRef<HTMLInputElement>(null);
+
+  const current = queue[0];
+
+  useEffect(() => {
+    const unlistenPrompt = listen<AuthPromptRequest>('ssh-auth-prompt', (e) => {
+      setQueue(q => [...q, e.payload]);
+    });
+    // 后端超时后撤回提示
+    const unlistenClosed = listen<string>('ssh-auth-prompt-closed', (e) => {
+      setQueue(q => q.filter(r => r.requestId !== e.payload));
+    });
+    return () => {
+      unlistenPrompt.then(f => f());
+      unlistenClosed.then(f => f());
+    };
+  }, []);
+
+  // 切换到下一条提示时重置输入并聚焦
+  useEffect(() => {
+    if (!current) return;
+    setAnswers(current.prompts.map(() => ''));
+    setSubmitting(false);
+    const timer = setTimeout(() => firstInputRef.current?.focus(), 50);
+    return () => clearTimeout(timer);
+  }, [current?.requestId]);
+
+  if (!current) return null;
+
+  const finish = async (responses: string[] | null) => {
+    setSubmitting(true);
+    try {
+      await invoke('respond_auth_prompt', { requestId: current.requestId, responses });
+    } catch (e) {
+      console.error('[auth-prompt]', e);
+    } finally {
+      setQueue(q => q.filter(r => r.requestId !== current.requestId));
+    }
+  };
+
+  const handleSubmit = (e: React.FormEvent) => {
+    e.preventDefault();
+    finish(answers);
+  };
+
+  return (
+    <div className="fixed inset-0 z-[9999] flex items-center justify-center bg-black/50 backdrop-blur-sm animate-in fade-in duration-200">
+      <div className="w-full max-w-md bg-white dark:bg-slate-900 rounded-xl shadow-2xl border border-slate-200 dark:border-slate-800 p-6 relative">
+        <button
+          onClick={() => finish(null)}
+          className="absolute right-4 top-4 text-slate-400 hover:text-slate-600 dark:hover:text-slate-200 transition-colors"
+        >
+          <X className="w-5 h-5" />
+        </button>
+
+        <div className="flex flex-col items-center text-center mb-6">
+          <div className="w-12 h-12 bg-blue-100 dark:bg-blue-900/30 rounded-full flex items-center justify-center mb-4 text-blue-600 dark:text-blue-400">
+            <ShieldCheck className="w-6 h-6" />
+          </div>
+          <h3 className="text-lg font-semibold text-slate-900 dark:text-slate-100">
+            {current.name || t('server.authPrompt.title', 'Verification Required')}
+          </h3>
+          <p className="text-sm text-slate-500 dark:text-slate-400 mt-1">
+            {t('server.authPrompt.desc', '{{user}}@{{host}} requires additional verification', {
+              user: current.username,
+              host: current.host,
+            })}
+          </p>
+          {current.instructions && (
+            <p className="text-xs text-slate-500 dark:text-slate-400 mt-2 whitespace-pre-wrap">
+              {current.instructions}
+            </p>
+          )}
+        </div>
+
+        <form onSubmit={handleSubmit} className="space-y-4">
+          {current.prompts.map((p, i) => (
+            <div key={i} className="space-y-1.5">
+              <label className="text-xs font-medium text-slate-600 dark:text-slate-300">{p.prompt.trim()}</label>
+              <input
+                ref={i === 0 ? firstInputRef : undefined}
+                type={p.echo ? 'text' : 'password'}
+                autoComplete="one-time-code"
+                value={answers[i] ?? ''}
+                onChange={(e) => setAnswers(a => a.map((v, j) => (j === i ? e.target.value : v)))}
+                className={clsx(
+                  "w-full bg-slate-50 dark:bg-slate-950 border rounded-lg px-4 py-3 text-sm transition-all outline-none",
+                  "border-slate-200 dark:border-slate-800",
+                  "focus:ring-2 focus:ring-blue-500 focus:border-transparent",
+                  "text-slate-900 dark:text-slate-100 placeholder:text-slate-400"
+                )}
+              />
+            </div>
+          ))}
+
+          <div className="flex gap-3">
+            <button
+              type="button"
+              onClick={() => finish(null)}
+              className="flex-1 px-4 py-2.5 rounded-lg text-sm font-medium border border-slate-200 dark:border-slate-700 hover:bg-slate-50 dark:hover:bg-slate-800 text-slate-700 dark:text-slate-300 transition-colors"
+            >
+              {t('common.cancel', 'Cancel')}
+            </button>
+            <button
+              type="submit"
+              disabled={submitting}
+              className="flex-1 flex items-center justify-center gap-2 bg-blue-600 hover:bg-blue-700 text-white rounded-lg py-2.5 text-sm font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              {submitting ? <Loader2 className="w-4 h-4 animate-spin" /> : t('common.confirm', 'Confirm')}
+            </button>
+          </div>
+        </form>
+      </div>
+    </div>
+  );
+};
@@ -1,4 +1,4 @@
-export type AuthType = 'password' | 'key';
+export type AuthType = 'password' | 'key' | 'agent';
 export type ConnectionType = 'direct' | 'http' | 'socks5';
 export type ServerStatus = 'connected' | 'disconnected' | 'connecting';
 
@@ -45,6 +45,10 @@ export interface Server {
   keepAliveInterval?: number;
   autoReconnect?: boolean;
   maxReconnects?: number;
+  // 🟢 [新增] 将本机 ssh-agent 转发给远端
+  agentForwarding?: boolean;
+  // 🟢 [新增] 远端 GUI 程序显示到本机 (ssh -X)
+  x11Forwarding?: boolean;
 }
 
 export interface ProxyItem {
@@ -5,12 +5,29 @@ import { invoke } from "@tauri-apps/api/core";
 import { toast } from "sonner";
 import { useTranslation } from "react-i18next"; // 🟢 [新增] 引入翻译 Hook
 
+// 🟢 [新增] 后端 test_connection 返回的分阶段诊断报告
+export interface DiagnosticStage {
+  name: 'jump' | 'dns' | 'tcp' | 'banner' | 'kex' | 'hostKey' | 'auth';
+  status: 'ok' | 'warning' | 'failed';
+  latencyMs?: number | null;
+  detail?: string | null;
+}
+
+export interface ConnectionTestReport {
+  success: boolean;
+  failedStage?: string | null;
+  error?: string | null;
+  totalMs: number;
+  stages: DiagnosticStage[];
+}
+
 export const useConnectionTest = (
   trigger: UseFormTrigger<ServerFormValues>,
   getValues: UseFormGetValues<ServerFormValues>
 ) => {
   const { t } = useTranslation(); // 🟢 [新增] 获取 t 函数
   const [status, setStatus] = useState<'idle' | 'loading' | 'success' | 'error'>('idle');
+  const [report, setReport] = useState<ConnectionTestReport | null>(null);
 
   const testConnection = async () => {
     // 1. 核心需求：在测试前检测必填区域
@@ -22,6 +39,7 @@ export const useConnectionTest = (
     }
 
     setStatus('loading');
+    setReport(null);
     const data = getValues();
 
     try {
@@ -50,11 +68,24 @@ export const useConnectionTest = (
 
       console.log("🔌 Testing Connection with:", payload);
 
-      await invoke("test_connection", { payload });
-      
+      const result = await invoke<ConnectionTestReport>("test_connection", { payload });
+      setReport(result);
+
+      if (!result.success) {
+        setStatus('error');
+        // 🟢 [新增] 指明失败阶段 (DNS / TCP / 认证...)，区分密码错误与网络不通
+        toast.error(t('server.form.testFailedAt', "Failed at {{stage}}: {{error}}", {
+          stage: result.failedStage,
+          error: result.error,
+        }));
+        return;
+      }
+
       setStatus('success');
       // 🟢 [修改] 本地化成功提示
-      toast.success(t('server.form.testSuccess', "Connection successful!"));
+      toast.success(t('server.form.testSuccess', "Connection successful!"), {
+        description: `${result.totalMs}ms`,
+      });
       
       setTimeout(() => setStatus('idle'), 3000);
 
@@ -73,6 +104,7 @@ export const useConnectionTest = (
 
   return { 
     status, 
+    report,
     testConnection 
   };
 };
\ No newline at end of file
@@ -60,6 +60,8 @@ export const useFormSubmit = (onSuccess?: () => void) => {
         keepAliveInterval: data.keepAliveInterval,
         autoReconnect: data.autoReconnect,
         maxReconnects: data.maxReconnects,
+        agentForwarding: data.agentForwarding,
+        x11Forwarding: data.x11Forwarding,
       };
 
 
@@ -81,14 +81,6 @@ export const useServerConnect = () => {
     setIsConnecting(true);
     setPendingServer(server);
 
-    // 如果是密钥认证，直接执行连接
-    if (server.authType === 'key') {
-        // 🟢 本地化日志
-        setLogs(prev => [...prev, t('server.logs.authKey', 'Auth Method: Public Key. Initializing tunnel...')]);
-        await executeConnection(server);
-        return;
-    }
-
     try {
         // 🟢 本地化日志
         setLogs(prev => [...prev, t('server.logs.preflight', 'Initiating pre-flight host verification...')]);
@@ -17,4 +17,6 @@ export const DEFAULT_SERVER_FORM_VALUES: Partial<ServerFormValues> = {
   keepAliveInterval: 60,
   autoReconnect: false,
   maxReconnects: 3,
+  agentForwarding: false,
+  x11Forwarding: false,
 };
\ No newline at end of file
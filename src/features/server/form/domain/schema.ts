@@ -1,7 +1,7 @@
 import { z } from "zod";
 
 // 枚举定义
-export const AuthTypeEnum = z.enum(["password", "key"]);
+export const AuthTypeEnum = z.enum(["password", "key", "agent"]);
 export const ConnectionTypeEnum = z.enum(["direct", "http", "socks5"]);
 export const KeySourceEnum = z.enum(["manual", "store"]);
 
@@ -52,6 +52,8 @@ export const serverFormSchema = z.object({
   keepAliveInterval: z.number().min(0).max(3600).default(60),   // 默认 60秒 (0表示关闭)
   autoReconnect: z.boolean().default(false),                    // 默认 关闭
   maxReconnects: z.number().min(0).max(20).default(3),          // 默认 3次
+  agentForwarding: z.boolean().default(false),                  // 默认 关闭 (ssh -A)
+  x11Forwarding: z.boolean().default(false),                    // 默认 关闭 (ssh -X)
 });
 
 export type ServerFormValues = z.infer<typeof serverFormSchema>;
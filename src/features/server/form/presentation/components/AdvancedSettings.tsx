@@ -4,7 +4,7 @@ import { Input } from "@/components/ui/input";
 import { Switch } from "@/components/ui/switch";
 // ⚠️ 请根据你的实际目录结构确认引用路径
 import { ServerFormValues } from "../../domain/schema";
-import { Zap, Activity, Timer, RefreshCw } from "lucide-react";
+import { Zap, Activity, Timer, RefreshCw, Forward, MonitorUp } from "lucide-react";
 
 interface AdvancedSettingsProps {
   t: any; // 这里的类型取决于你使用的 i18n 库，通常是 TFunction
@@ -17,6 +17,8 @@ interface AdvancedSettingsProps {
 export const AdvancedSettings = ({ t, register, errors, watch, setValue }: AdvancedSettingsProps) => {
   // 监听自动重连开关
   const autoReconnect = watch("autoReconnect");
+  const agentForwarding = watch("agentForwarding");
+  const x11Forwarding = watch("x11Forwarding");
 
   // 安全翻译辅助函数
   const translate = (key: string, fallback: string) => t ? t(key, fallback) : fallback;
@@ -154,6 +156,46 @@ export const AdvancedSettings = ({ t, register, errors, watch, setValue }: Advan
           </div>
         </div>
       )}
+
+      {/* 5. Agent 转发 (ssh -A)，仅对信任的服务器开启 */}
+      <div className="flex items-center gap-4 p-3 rounded-lg border border-slate-200 dark:border-slate-800 bg-slate-50/50 dark:bg-slate-900/50">
+        <div className="flex items-center justify-center w-8 h-8 rounded-full bg-blue-100 dark:bg-blue-900/30">
+            <Forward className="w-4 h-4 text-blue-600 dark:text-blue-400" />
+        </div>
+        <div className="flex-1">
+          <Label className="text-xs font-semibold text-slate-700 dark:text-slate-300">
+            {translate('server.form.agentForwarding', 'Agent Forwarding')}
+          </Label>
+          <p className="text-[10px] text-slate-500">
+            {translate('server.form.agentForwardingDesc', 'Let the remote shell use your local ssh-agent. Only enable for trusted hosts.')}
+          </p>
+        </div>
+        <Switch 
+          checked={!!agentForwarding}
+          onCheckedChange={(val) => setValue("agentForwarding", val, { shouldDirty: true })}
+          className="scale-90"
+        />
+      </div>
+
+      {/* 6. X11 转发 (ssh -X)，需要本机运行 X server */}
+      <div className="flex items-center gap-4 p-3 rounded-lg border border-slate-200 dark:border-slate-800 bg-slate-50/50 dark:bg-slate-900/50">
+        <div className="flex items-center justify-center w-8 h-8 rounded-full bg-blue-100 dark:bg-blue-900/30">
+            <MonitorUp className="w-4 h-4 text-blue-600 dark:text-blue-400" />
+        </div>
+        <div className="flex-1">
+          <Label className="text-xs font-semibold text-slate-700 dark:text-slate-300">
+            {translate('server.form.x11Forwarding', 'X11 Forwarding')}
+          </Label>
+          <p className="text-[10px] text-slate-500">
+            {translate('server.form.x11ForwardingDesc', 'Show remote GUI programs on your local display. Requires a running X server.')}
+          </p>
+        </div>
+        <Switch 
+          checked={!!x11Forwarding}
+          onCheckedChange={(val) => setValue("x11Forwarding", val, { shouldDirty: true })}
+          className="scale-90"
+        />
+      </div>
     </div>
   );
-};
\ No newline at end of file
+};
@@ -1,6 +1,6 @@
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
-import { User, Shield, KeyRound, Terminal } from "lucide-react";
+import { User, Shield, KeyRound, Terminal, Usb } from "lucide-react";
 import { AnimatePresence } from "framer-motion";
 import { cn } from "@/lib/utils";
 import { PasswordSection, KeySection } from "./ServerAuthComponents"; 
@@ -19,6 +19,11 @@ const AuthTypeSwitcher = ({ value, onChange, t }: { value: string, onChange: (v:
     >
       <Terminal className="w-3.5 h-3.5" /> {t('server.form.vault.key', 'Private Key')}
     </button>
+    <button type="button" onClick={() => onChange('agent')}
+      className={cn("flex-1 sm:flex-none px-4 py-1.5 text-xs font-medium rounded-md transition-all duration-200 flex items-center justify-center gap-2", value === 'agent' ? "bg-white dark:bg-slate-700 text-blue-600 shadow-sm" : "text-slate-500 hover:text-slate-700 dark:text-slate-400")}
+    >
+      <Usb className="w-3.5 h-3.5" /> {t('server.form.vault.agent', 'SSH Agent')}
+    </button>
   </div>
 );
 
@@ -99,6 +104,11 @@ export const AuthCredentials = ({
                        showPass={showPassword}
                        onToggleShow={onToggleShowPassword}
                     />
+                 ) : authType === 'agent' ? (
+                    // 🟢 [新增] Agent 认证无需凭证，身份来自 SSH_AUTH_SOCK
+                    <p key="agent" className="text-xs text-slate-500 leading-relaxed px-1">
+                      {t('server.form.agentDesc', 'Identities are read from the local ssh-agent (SSH_AUTH_SOCK). Each key is offered to the server in turn, including hardware-token keys that never leave the agent.')}
+                    </p>
                  ) : (
                     <KeySection 
                        key="key"
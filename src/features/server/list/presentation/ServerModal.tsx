@@ -73,6 +73,8 @@ export const ServerModal = ({ open, onOpenChange, initialData, onClose }: Server
       keepAliveInterval: initialData.keepAliveInterval ?? 60,
       autoReconnect: initialData.autoReconnect ?? false,
       maxReconnects: initialData.maxReconnects ?? 3,
+      agentForwarding: initialData.agentForwarding ?? false,
+      x11Forwarding: initialData.x11Forwarding ?? false,
     };
   }, [initialData]);
 
@@ -62,7 +62,8 @@ export const useBackupLogic = () => {
       const list = await invoke<CloudBackupFile[]>('get_backup_list', { 
         url, 
         username, 
-        password: null 
+        password: null,
+        proxyId: settings['backup.proxyId'] || null
       });
       const sortedList = list.sort((a, b) => b.name.localeCompare(a.name));
       updateState({ backupList: sortedList });
@@ -84,7 +85,8 @@ export const useBackupLogic = () => {
         url,
         username,
         password: null,
-        filename
+        filename,
+        proxyId: settings['backup.proxyId'] || null
       });
       
       toast.success(t('common.deletedSuccess', "Deleted successfully"));
@@ -115,7 +117,8 @@ export const useBackupLogic = () => {
       await invoke('check_webdav', { 
         url: webdavUrl, 
         username, 
-        password: password || null 
+        password: password || null,
+        proxyId: settings['backup.proxyId'] || null
       });
 
       updateSettings({
@@ -147,7 +150,8 @@ export const useBackupLogic = () => {
         username, 
         password: null,
         deviceName, 
-        deviceId
+        deviceId,
+        proxyId: settings['backup.proxyId'] || null
       });
       
       toast.success(t('settings.backup.backupSuccess', "Backup uploaded successfully"));
@@ -199,7 +203,8 @@ export const useBackupLogic = () => {
               url,
               username,
               password: null,
-              filename: state.selectedBackup.name
+              filename: state.selectedBackup.name,
+              proxyId: settings['backup.proxyId'] || null
           });
           toast.success(t('settings.backup.restoreSuccess', "Restore successful. Please restart app."));
       } catch(e) {
@@ -300,6 +305,8 @@ export const useBackupLogic = () => {
       performRestore: onConfirmRestore, 
 
       setInterval: (val: string) => updateSettings({ 'backup.interval': val }),
+      // 🟢 [新增] WebDAV 经代理访问 ('none' 表示直连)
+      setProxy: (val: string) => updateSettings({ 'backup.proxyId': val === 'none' ? '' : val }),
       toggleAutoBackup: (v: boolean) => updateSettings({ 'backup.autoBackup': v }),
     }
   };
@@ -24,6 +24,7 @@ export const BackupManager = () => {
           onSave={actions.handleSaveAndTest}
           onToggleAuto={actions.toggleAutoBackup}
           onIntervalChange={actions.setInterval}
+          onProxyChange={actions.setProxy}
         />
       </div>
 
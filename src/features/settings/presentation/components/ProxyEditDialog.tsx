@@ -164,7 +164,8 @@ export const ProxyEditDialog = ({ isOpen, onClose, onSave, initialData }: Props)
                 <SelectContent className="z-[250]">
                   {/* 🟢 Protocols usually remain in English, but you can translate if needed */}
                   <SelectItem value="http">HTTP</SelectItem>
-                  <SelectItem value="https">HTTPS</SelectItem>
+                  {/* 到代理本身的 TLS 未实现，仅保留给已保存的配置显示 */}
+                  <SelectItem value="https" disabled>HTTPS (unsupported)</SelectItem>
                   <SelectItem value="socks4">SOCKS4</SelectItem>
                   <SelectItem value="socks5">SOCKS5</SelectItem>
                 </SelectContent>
@@ -1,4 +1,5 @@
-import { Cloud, CheckCircle2, Lock, ShieldCheck, CalendarClock } from "lucide-react";
+import { useEffect } from "react";
+import { Cloud, CheckCircle2, Lock, ShieldCheck, CalendarClock, Network } from "lucide-react";
 import { UseFormReturn } from "react-hook-form";
 import { Switch } from "@/components/ui/switch";
 import { Label } from "@/components/ui/label";
@@ -6,6 +7,7 @@ import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@
 import { CustomInput } from "@/components/common/CustomInput";
 import { CustomButton } from "@/components/common/CustomButton";
 import { WebDavFormValues } from "../../../domain/backup";
+import { useSettingsStore } from "../../../application/useSettingsStore";
 import { TFunction } from "i18next"; // 🟢 1. 引入类型
 
 interface Props {
@@ -17,14 +19,21 @@ interface Props {
   onSave: () => void;
   onToggleAuto: (checked: boolean) => void;
   onIntervalChange: (val: string) => void;
+  onProxyChange: (val: string) => void;
 }
 
 export const WebDavConfigCard = ({
-  t, form, settings, isConfigured, isTesting, onSave, onToggleAuto, onIntervalChange
+  t, form, settings, isConfigured, isTesting, onSave, onToggleAuto, onIntervalChange, onProxyChange
 }: Props) => {
   // ... 代码保持不变
   const { register, formState: { errors } } = form;
 
+  // 🟢 [新增] 代理列表 (WebDAV 可经 HTTP / SOCKS 代理访问)
+  const { proxies, loadProxies } = useSettingsStore();
+  useEffect(() => {
+    loadProxies();
+  }, [loadProxies]);
+
   return (
     <div className="border border-slate-200 dark:border-slate-800 rounded-xl bg-white/50 dark:bg-slate-900/50 overflow-hidden shadow-sm">
       {/* Header */}
@@ -94,6 +103,24 @@ export const WebDavConfigCard = ({
           />
         </div>
 
+        <div className="space-y-1.5">
+          <Label className="text-xs text-slate-500 font-normal flex items-center gap-1.5">
+            <Network className="w-3.5 h-3.5" />
+            {t('settings.backup.proxy', 'Proxy')}
+          </Label>
+          <Select value={settings['backup.proxyId'] || 'none'} onValueChange={onProxyChange}>
+            <SelectTrigger className="h-9 text-xs bg-white dark:bg-slate-900"><SelectValue /></SelectTrigger>
+            <SelectContent>
+              <SelectItem value="none">{t('settings.backup.proxyNone', 'Direct (no proxy)')}</SelectItem>
+              {proxies.map(p => (
+                <SelectItem key={p.id} value={p.id}>
+                  {p.name} ({p.type.toUpperCase()} {p.host}:{p.port})
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+
         <div className="pt-2 flex justify-between items-center">
            <p className="text-[10px] text-slate-400">
               {isConfigured 
@@ -1,5 +1,11 @@
 import { invoke } from "@tauri-apps/api/core";
-import { Snippet } from "../domain/types";
+import { listen, UnlistenFn } from "@tauri-apps/api/event";
+import {
+  Snippet, SnippetPlaceholder, SnippetRunRequest, SnippetRunSummary, HostRunResult, HostOutputChunk,
+  SnippetExportFormat, SnippetImportFormat, ImportConflictStrategy, SnippetImportReport,
+  SnippetSearchQuery, SnippetSearchResult, SnippetRevision, RevisionDiff,
+  SnippetSyncConfig, SnippetSyncReport,
+} from "../domain/types";
 
 // 这里的接口对应 Rust 里的 SnippetDto
 // 确保 domain/types.ts 里的 Snippet 接口和 Rust 的 SnippetDto 字段名一致 (createdAt vs created_at)
@@ -11,6 +17,11 @@ export const SnippetService = {
     return await invoke<Snippet[]>("get_all_snippets");
   },
 
+  // 全文检索 + 标签 / 语言筛选 (分页，附分面计数)
+  async search(query: SnippetSearchQuery): Promise<SnippetSearchResult> {
+    return await invoke<SnippetSearchResult>("search_snippets", { query });
+  },
+
   async add(snippet: Snippet): Promise<void> {
     await invoke("add_snippet", { snippet });
   },
@@ -23,6 +34,84 @@ export const SnippetService = {
     await invoke("delete_snippet", { id });
   },
 
+  // 修订历史：新版本在前
+  async listRevisions(snippetId: string): Promise<SnippetRevision[]> {
+    return await invoke<SnippetRevision[]>("list_snippet_revisions", { snippetId });
+  },
+
+  // 逐行对比两个版本；不传 toRevision 时与最新版本对比
+  async diffRevisions(snippetId: string, fromRevision: number, toRevision?: number): Promise<RevisionDiff> {
+    return await invoke<RevisionDiff>("diff_snippet_revisions", { snippetId, fromRevision, toRevision });
+  },
+
+  // 恢复旧版本 (作为新版本追加)，返回更新后的片段
+  async restoreRevision(snippetId: string, revision: number): Promise<Snippet> {
+    return await invoke<Snippet>("restore_snippet_revision", { snippetId, revision });
+  },
+
+  // Git 同步
+  async getSyncConfig(): Promise<SnippetSyncConfig> {
+    return await invoke<SnippetSyncConfig>("get_snippet_sync_config");
+  },
+
+  async saveSyncConfig(config: SnippetSyncConfig): Promise<SnippetSyncConfig> {
+    return await invoke<SnippetSyncConfig>("save_snippet_sync_config", { config });
+  },
+
+  // status 为 conflicts 时需逐个 resolveSyncConflict，全部处理后自动完成同步
+  async syncGit(): Promise<SnippetSyncReport> {
+    return await invoke<SnippetSyncReport>("sync_snippets_git");
+  },
+
+  async resolveSyncConflict(snippetId: string, choice: 'local' | 'remote'): Promise<SnippetSyncReport> {
+    return await invoke<SnippetSyncReport>("resolve_snippet_sync_conflict", { snippetId, choice });
+  },
+
+  async abortSync(): Promise<void> {
+    await invoke("abort_snippet_sync");
+  },
+
+  // 导出到 JSON 文件或 .sh 目录，返回导出数量；不传 ids 时导出全部
+  async export(path: string, format: SnippetExportFormat, ids?: string[]): Promise<number> {
+    return await invoke<number>("export_snippets", { path, format, ids });
+  },
+
+  // 导入：不传 format 时按路径自动识别 (目录 -> sh, .json, .ini -> securecrt)
+  async import(
+    path: string,
+    format?: SnippetImportFormat,
+    onConflict: ImportConflictStrategy = 'skip',
+  ): Promise<SnippetImportReport> {
+    return await invoke<SnippetImportReport>("import_snippets", { path, format, onConflict });
+  },
+
+  // 解析模板占位符
+  async parseTemplate(code: string): Promise<SnippetPlaceholder[]> {
+    return await invoke<SnippetPlaceholder[]>("parse_snippet_template", { code });
+  },
+
+  // 渲染预览 (secret 显示为掩码)；不传 serverId 时服务器字段原样保留
+  async renderTemplate(code: string, values: Record<string, string>, serverId?: string): Promise<string> {
+    return await invoke<string>("render_snippet_template", { code, values, serverId });
+  },
+
+  // 在多台服务器上执行片段：执行期间通过回调推送每台主机的输出与状态
+  async execute(
+    request: SnippetRunRequest,
+    onOutput?: (chunk: HostOutputChunk) => void,
+    onHost?: (result: HostRunResult) => void,
+  ): Promise<SnippetRunSummary> {
+    const unlisteners: UnlistenFn[] = await Promise.all([
+      listen<HostOutputChunk>(`snippet-output-${request.runId}`, (e) => onOutput?.(e.payload)),
+      listen<HostRunResult>(`snippet-host-${request.runId}`, (e) => onHost?.(e.payload)),
+    ]);
+    try {
+      return await invoke<SnippetRunSummary>("execute_snippet", { request });
+    } finally {
+      unlisteners.forEach(f => f());
+    }
+  },
+
   // 如果你需要 init，现在可以在 Rust setup 阶段自动完成，前端通常不需要手动 initTable
   // 但为了保持 Store 代码兼容，可以留个空函数
   async initTable(): Promise<void> {
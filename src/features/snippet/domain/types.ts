@@ -9,4 +9,160 @@ export interface Snippet {
   tags: string[];
   createdAt: number;
   updatedAt: number;
-}
\ No newline at end of file
+}
+
+// 模板占位符 {{name:type:quote|raw=default}} (对应 Rust 的 Placeholder；secret 默认转义)
+export type PlaceholderKind = 'string' | 'number' | 'enum' | 'secret' | 'server';
+
+export interface SnippetPlaceholder {
+  name: string;
+  kind: PlaceholderKind;
+  options: string[];
+  defaultValue: string | null;
+  required: boolean;
+}
+
+// 批量执行 (对应 Rust 的 SnippetRunRequest / SnippetRunSummary)
+export interface SnippetRunRequest {
+  runId: string;
+  snippetId: string;
+  values?: Record<string, string>; // 模板占位符取值，secret 填 Vault 条目 ID
+  serverIds?: string[];
+  tags?: string[];
+  matchAllTags?: boolean;
+  concurrency?: number;
+  timeoutSecs?: number;
+  failFast?: boolean;
+}
+
+export type HostRunStatus = 'running' | 'success' | 'failed' | 'skipped';
+
+export interface HostRunResult {
+  serverId: string;
+  serverName: string;
+  status: HostRunStatus;
+  exitCode: number | null;
+  signal: string | null;
+  timedOut: boolean;
+  error: string | null;
+  durationMs: number;
+}
+
+// snippet-output-{runId} 事件
+export interface HostOutputChunk {
+  serverId: string;
+  stream: 'stdout' | 'stderr';
+  data: string;
+}
+
+export interface SnippetRunSummary {
+  runId: string;
+  snippetId: string;
+  total: number;
+  succeeded: string[];
+  failed: string[];
+  skipped: string[];
+  results: HostRunResult[];
+  durationMs: number;
+}
+
+// 导入导出 (对应 Rust 的 ImportReport)
+export type SnippetExportFormat = 'json' | 'sh';
+export type SnippetImportFormat = 'json' | 'sh' | 'termius' | 'securecrt';
+export type ImportConflictStrategy = 'skip' | 'overwrite' | 'duplicate';
+
+export interface ImportConflict {
+  title: string;
+  existingId: string;
+  matchedBy: 'id' | 'title';
+  resolution: 'skipped' | 'overwritten' | 'duplicated';
+}
+
+export interface SnippetImportReport {
+  format: SnippetImportFormat;
+  imported: number;
+  updated: number;
+  unchanged: number;
+  conflicts: ImportConflict[];
+  errors: string[];
+}
+
+// 全文检索 (对应 Rust 的 SnippetSearchQuery / SnippetSearchResult)
+export interface SnippetSearchQuery {
+  text?: string;
+  tags?: string[];
+  language?: SnippetLanguage;
+  offset?: number;
+  limit?: number;
+}
+
+export interface SnippetSearchHit extends Snippet {
+  score: number | null;
+}
+
+export interface FacetCount {
+  value: string;
+  count: number;
+}
+
+export interface SnippetSearchResult {
+  items: SnippetSearchHit[];
+  total: number;
+  offset: number;
+  limit: number;
+  tagFacets: FacetCount[];
+  languageFacets: FacetCount[];
+}
+
+// 修订历史 (对应 Rust 的 SnippetRevision / RevisionDiff)
+export interface SnippetRevision {
+  snippetId: string;
+  revision: number;
+  title: string;
+  code: string;
+  language: SnippetLanguage;
+  tags: string[];
+  createdAt: number;
+}
+
+export interface DiffLine {
+  kind: 'equal' | 'added' | 'removed';
+  text: string;
+  oldLine: number | null;
+  newLine: number | null;
+}
+
+export interface RevisionDiff {
+  fromRevision: number;
+  toRevision: number;
+  changedFields: ('title' | 'language' | 'tags')[];
+  lines: DiffLine[];
+  added: number;
+  removed: number;
+}
+
+// Git 同步 (对应 Rust 的 SnippetSyncConfig / SnippetSyncReport)
+export interface SnippetSyncConfig {
+  repoPath: string;
+  remoteUrl: string | null;
+  branch: string;
+  lastCommit?: string | null;
+  lastSyncedAt?: number | null;
+}
+
+export interface SyncConflict {
+  snippetId: string;
+  path: string;
+  local: Snippet | null; // null 表示该侧已删除
+  remote: Snippet | null;
+}
+
+export interface SnippetSyncReport {
+  status: 'synced' | 'conflicts';
+  added: number;
+  updated: number;
+  deleted: number;
+  pushed: boolean;
+  commit: string | null;
+  conflicts: SyncConflict[];
+}
@@ -3,6 +3,8 @@ import { Terminal } from "@xterm/xterm";
 import { FitAddon } from "@xterm/addon-fit";
 import { WebglAddon } from "@xterm/addon-webgl";
 import { invoke } from '@tauri-apps/api/core';
+import { toast } from "sonner";
+import { useTranslation } from "react-i18next";
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { useTerminalStore } from "@/store/useTerminalStore";
 import { useServerStore } from "@/features/server/application/useServerStore";
@@ -20,6 +22,7 @@ function debounce(func: Function, wait: number) {
 }
 
 export const useTerminalSession = (sessionId: string, isActive: boolean) => {
+  const { t } = useTranslation();
   const mountRef = useRef<HTMLDivElement>(null);
   const termRef = useRef<Terminal | null>(null);
   const fitAddonRef = useRef<FitAddon | null>(null);
@@ -41,6 +44,7 @@ export const useTerminalSession = (sessionId: string, isActive: boolean) => {
   );
 
   const consumeCredential = useSessionCredentialStore(s => s.consumeCredential);
+  const saveServer = useServerStore(s => s.saveServer);
   const settings = useSettingsStore(s => s.settings);
   const customThemes = useSettingsStore(s => s.customThemes);
   
@@ -91,7 +95,8 @@ export const useTerminalSession = (sessionId: string, isActive: boolean) => {
 
     try {
         let finalPassword = manualPassword;
-        if (!finalPassword && serverConfig.provider === 'QuickConnect') {
+        // -i 指定了 Vault 密钥时无需密码
+        if (!finalPassword && serverConfig.provider === 'QuickConnect' && !serverConfig.keyId) {
             const tempPwd = consumeCredential(serverConfig.id);
             if (tempPwd) finalPassword = tempPwd;
             else {
@@ -110,7 +115,34 @@ export const useTerminalSession = (sessionId: string, isActive: boolean) => {
                 username: serverConfig.username,
                 password: finalPassword || null,
                 privateKey: serverConfig.privateKey || null,
-                passphrase: serverConfig.passphrase || null
+                passphrase: serverConfig.passphrase || null,
+                keyId: serverConfig.keyId || null
+            });
+
+            // 🟢 [新增] 连接成功后询问是否保存到服务器列表 (经 save_server，密码转存 Vault)
+            toast.success(`${serverConfig.username}@${serverConfig.ip}:${serverConfig.port}`, {
+                action: {
+                    label: t('dashboard.quickConnect.saveTarget', 'Save to server list'),
+                    onClick: () => {
+                        saveServer({
+                            name: serverConfig.name,
+                            ip: serverConfig.ip,
+                            port: serverConfig.port,
+                            username: serverConfig.username,
+                            authType: serverConfig.authType,
+                            connectionType: 'direct',
+                            password: finalPassword || undefined,
+                            passwordSource: 'manual',
+                            keyId: serverConfig.keyId,
+                            keySource: serverConfig.keySource,
+                            provider: 'Custom',
+                            icon: 'server',
+                            tags: [],
+                        })
+                            .then(() => toast.success(t('dashboard.quickConnect.saved', 'Saved to server list')))
+                            .catch((e) => toast.error(String(e)));
+                    }
+                }
             });
         } else {
             await invoke('connect_ssh', { serverId: serverConfig.id, sessionId: sessionId });
@@ -133,7 +165,7 @@ export const useTerminalSession = (sessionId: string, isActive: boolean) => {
              setIsPasswordRequired(true);
         }
     }
-  }, [serverConfig, sessionId, consumeCredential, updateSessionStatus, performSafeResize]);
+  }, [serverConfig, sessionId, consumeCredential, updateSessionStatus, performSafeResize, saveServer, t]);
 
   // 3. 初始化终端与数据监听
   useEffect(() => {
@@ -165,6 +197,7 @@ export const useTerminalSession = (sessionId: string, isActive: boolean) => {
 
     let isMounted = true; 
     let unlistenFn: UnlistenFn | null = null;
+    let unlistenStateFn: UnlistenFn | null = null;
     
     const setup = async () => {
         const unlisten = await listen<string>(`term-data-${sessionId}`, (event) => {
@@ -173,11 +206,24 @@ export const useTerminalSession = (sessionId: string, isActive: boolean) => {
             }
         });
 
+        // 后端断线守护推送的状态 (重连中 / 重连成功 / 失败)
+        const unlistenState = await listen<{ state: string }>(`ssh-state-${sessionId}`, (event) => {
+            if (!isMounted) return;
+            switch (event.payload.state) {
+                case 'reconnecting': updateSessionStatus(sessionId, 'connecting'); break;
+                case 'connected': updateSessionStatus(sessionId, 'connected'); break;
+                case 'failed': updateSessionStatus(sessionId, 'error'); break;
+                case 'disconnected': updateSessionStatus(sessionId, 'disconnected'); break;
+            }
+        });
+
         if (!isMounted) {
             unlisten(); 
+            unlistenState();
             return;
         }
         unlistenFn = unlisten;
+        unlistenStateFn = unlistenState;
         await connectInternal();
     };
 
@@ -218,6 +264,7 @@ export const useTerminalSession = (sessionId: string, isActive: boolean) => {
         isMounted = false;
         isConnectionReadyRef.current = false;
         if (unlistenFn) unlistenFn();
+        if (unlistenStateFn) unlistenStateFn();
         dataDisposable.dispose();
         resizeObserver.disconnect();
         term.dispose();
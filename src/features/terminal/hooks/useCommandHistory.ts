@@ -7,6 +7,9 @@ export interface HistoryItem {
   id: number;
   command: string;
   createdAt: number;
+  /** 执行结果 (仅片段执行等非交互来源有值) */
+  status?: 'success' | 'failed' | 'timeout' | null;
+  exitCode?: number | null;
 }
 
 export const useCommandHistory = (isOpen: boolean, isActive: boolean, sessionId: string | null) => {
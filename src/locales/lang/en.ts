@@ -105,13 +105,15 @@ export const en = {
         becomeSponsor: "Become a sponsor"
       },
       quickConnect: {
-        hint: "Format: user@host or user@host:port",
+        hint: "Format: user@host:port, ssh://user@host:port or -i keyid user@host",
         title: "Start Connection",
         error: "Failed to initialize connection",
         connecting: "Connecting to {{host}}...",
         invalidFormat:
           "Invalid format. Please use: user@host:port",
-        disclaimerTitle: "Disclaimer"
+        disclaimerTitle: "Disclaimer",
+        saveTarget: "Save to server list",
+        saved: "Saved to server list"
       }
     },
 
@@ -119,6 +121,10 @@ export const en = {
       errorTrust: "Unable to trust host key",
       locked_connect: "Please unlock the vault to connect.",
       errorTerminal: "Failed to open terminal: {{message}}",
+      authPrompt: {
+        title: "Verification Required",
+        desc: "{{user}}@{{host}} requires additional verification"
+      },
       verify: {
         title: "New Host Verification",
         desc:
@@ -411,6 +417,8 @@ export const en = {
         paste: "Paste",
         pasteInto: "Paste here",
         upload: "Upload File",
+        uploadFolder: "Upload Folder",
+        downloadFolder: "Download folder to",
         permission: "Change Permissions"
       },
       placeholder: {
@@ -433,7 +441,8 @@ export const en = {
         downloading: "Downloading...",
         downloadSuccess: "Download successful",
         downloadFailed: "Download failed",
-        chmodSuccess: "Permissions updated successfully"
+        chmodSuccess: "Permissions updated successfully",
+        batchPartial: "{{count}} item(s) failed: {{path}} ({{error}})"
       },
       perm: {
         prop_file: "Target",
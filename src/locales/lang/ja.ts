@@ -399,6 +399,8 @@ export const ja = {
         paste: "貼り付け",
         pasteInto: "ここに貼り付け",
         upload: "ファイルをアップロード",
+        uploadFolder: "フォルダーをアップロード",
+        downloadFolder: "フォルダーのダウンロード先",
         permission: "権限を変更"
       },
       placeholder: {
@@ -421,7 +423,8 @@ export const ja = {
         downloading: "ダウンロード中...",
         downloadSuccess: "ダウンロード成功",
         downloadFailed: "ダウンロード失敗",
-        chmodSuccess: "権限を変更しました"
+        chmodSuccess: "権限を変更しました",
+        batchPartial: "{{count}} 件失敗しました: {{path}} ({{error}})"
       },
       perm: {
         prop_file: "対象",
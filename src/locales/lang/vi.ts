@@ -399,6 +399,8 @@ export const vi = {
         paste: "Dán",
         pasteInto: "Dán vào đây",
         upload: "Tải tệp lên",
+        uploadFolder: "Tải thư mục lên",
+        downloadFolder: "Tải thư mục xuống vào",
         permission: "Thay đổi quyền"
       },
       placeholder: {
@@ -421,7 +423,8 @@ export const vi = {
         downloading: "Đang tải xuống...",
         downloadSuccess: "Tải xuống thành công",
         downloadFailed: "Tải xuống thất bại",
-        chmodSuccess: "Thay đổi quyền thành công"
+        chmodSuccess: "Thay đổi quyền thành công",
+        batchPartial: "{{count}} mục thất bại: {{path}} ({{error}})"
       },
       perm: {
         prop_file: "Đối tượng",
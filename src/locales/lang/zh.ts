@@ -103,12 +103,14 @@ export const zh = {
         becomeSponsor: "成为支持者",
       },
       quickConnect:{
-        hint: "格式: user@host or user@host:port",
+        hint: "格式: user@host:port、ssh://user@host:port 或 -i keyid user@host",
         title: "开始连接",
         error: "连接初始化失败",
         connecting: "正在连接 {{host}}...",
         invalidFormat: "格式无效。请使用：user@host:port",
         disclaimerTitle: "担保免责声明",
+        saveTarget: "保存到服务器列表",
+        saved: "已保存到服务器列表",
       }
 
 
@@ -119,6 +121,10 @@ export const zh = {
       errorTrust: "无法信任主机密钥",
       locked_connect: "请解锁密钥库以连接。",
       errorTerminal: "打开终端失败：{{message}}",
+      authPrompt: {
+        title: "需要二次验证",
+        desc: "{{user}}@{{host}} 需要额外的身份验证",
+      },
       verify: {
         title: "新主机确认",
         desc: "无法确认主机的真实性。正在首次连接到此服务器。",
@@ -398,6 +404,8 @@ keys: {
         paste: "粘贴",
         pasteInto: "粘贴到此处",
         upload: "上传文件",
+        uploadFolder: "上传文件夹",
+        downloadFolder: "下载文件夹到",
         permission: "修改权限",
       },
       placeholder: {
@@ -421,6 +429,7 @@ keys: {
         downloadSuccess: "下载成功",
         downloadFailed: "下载失败",
         chmodSuccess: "权限修改成功",
+        batchPartial: "{{count}} 项失败：{{path}} ({{error}})",
       },
       perm:{
         prop_file: "目标",
@@ -393,6 +393,8 @@ export const zhTW = {
         paste: "貼上",
         pasteInto: "貼上至此處",
         upload: "上傳檔案",
+        uploadFolder: "上傳資料夾",
+        downloadFolder: "下載資料夾至",
         permission: "修改權限"
       },
       placeholder: {
@@ -415,7 +417,8 @@ export const zhTW = {
         downloading: "正在下載...",
         downloadSuccess: "下載成功",
         downloadFailed: "下載失敗",
-        chmodSuccess: "權限修改成功"
+        chmodSuccess: "權限修改成功",
+        batchPartial: "{{count}} 項失敗：{{path}} ({{error}})"
       },
       perm: {
         prop_file: "目標",
@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import { Server } from "@/features/server/domain/types";
+import { SyncAction, SyncPlan, SyncProfile, SyncReport } from "@/features/fs/types";
 
 export const ServerAPI = {
   // 获取所有服务器
@@ -27,4 +28,28 @@ export const ServerAPI = {
   delete: async (id: string): Promise<void> => {
     return await invoke("delete_server", { id });
   }
-};
\ No newline at end of file
+};
+// 文件夹同步：配置按服务器保存，sessionId 为执行同步的 SSH 会话
+export const SyncAPI = {
+  list: (serverId: string): Promise<SyncProfile[]> =>
+    invoke("list_sync_profiles", { serverId }),
+
+  save: (profile: SyncProfile): Promise<SyncProfile> =>
+    invoke("save_sync_profile", { profile }),
+
+  delete: (id: string): Promise<void> =>
+    invoke("delete_sync_profile", { id }),
+
+  // 预演，不修改任何文件
+  plan: (sessionId: string, profileId: string): Promise<SyncPlan> =>
+    invoke("sftp_sync_plan", { id: sessionId, profileId }),
+
+  // resolutions: 按相对路径覆盖计划中的动作 (处理冲突或跳过)
+  apply: (
+    sessionId: string,
+    profileId: string,
+    resolutions?: Record<string, SyncAction>,
+    opId?: string
+  ): Promise<SyncReport> =>
+    invoke("sftp_sync_apply", { id: sessionId, profileId, resolutions, opId }),
+};
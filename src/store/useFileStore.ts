@@ -5,6 +5,8 @@ export interface ClipboardState {
   type: 'copy' | 'move';
   files: FileEntry[];
   sourcePath: string;
+  // 复制源所在的会话；剪贴板在会话间共享，可粘贴到另一台服务器
+  sessionId: string;
 }
 
 // [新增] 定义缓存结构
@@ -28,7 +30,6 @@ interface SessionFileState {
   sortOrder: SortOrder;
   showHidden: boolean;
   reloadTrigger: number;
-  clipboard: ClipboardState | null;
   // [新增] 缓存字段
   cache: FileCache; 
 }
@@ -43,12 +44,12 @@ const defaultSessionState: SessionFileState = {
   sortOrder: 'asc',
   showHidden: false,
   reloadTrigger: 0,
-  clipboard: null,
   cache: {}, // [新增] 默认空缓存
 };
 
 interface FileStore {
   sessions: Record<string, SessionFileState>;
+  clipboard: ClipboardState | null;
   
   triggerReload: (sessionId: string) => void; 
   initSession: (sessionId: string) => void;
@@ -64,7 +65,7 @@ interface FileStore {
   toggleHidden: (sessionId: string) => void;
   setSort: (sessionId: string, field: SortField) => void;
   getSession: (sessionId: string) => SessionFileState;
-  setClipboard: (sessionId: string, clipboard: ClipboardState | null) => void;
+  setClipboard: (sessionId: string, clipboard: Omit<ClipboardState, 'sessionId'> | null) => void;
   
   // [新增] 专门用于清理缓存（例如刷新时强制清理）
   clearCache: (sessionId: string, path: string) => void;
@@ -72,6 +73,7 @@ interface FileStore {
 
 export const useFileStore = create<FileStore>((set, get) => ({
   sessions: {},
+  clipboard: null,
 
   getSession: (sessionId) => {
     return get().sessions[sessionId] || defaultSessionState;
@@ -139,15 +141,9 @@ export const useFileStore = create<FileStore>((set, get) => ({
       };
   }),
 
-  setClipboard: (sessionId, clipboard) => set((state) => ({
-    sessions: {
-      ...state.sessions,
-      [sessionId]: {
-        ...(state.sessions[sessionId] || defaultSessionState),
-        clipboard
-      }
-    }
-  })),
+  setClipboard: (sessionId, clipboard) => set({
+    clipboard: clipboard ? { ...clipboard, sessionId } : null
+  }),
   
   // [核心修改] setPath 逻辑优化
   setPath: (sessionId, path) => set((state) => {
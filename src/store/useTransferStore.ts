@@ -1,111 +1,197 @@
 import { create } from 'zustand';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+
+export type TransferStatus = 'queued' | 'running' | 'paused' | 'completed' | 'failed' | 'cancelled';
 
 export interface TransferTask {
     id: string;
     type: 'upload' | 'download';
     name: string;
+    sessionId: string;
     localPath: string;
     remotePath: string;
     size: number;
-    status: 'running' | 'completed' | 'error';
+    transferred: number;
+    status: TransferStatus;
     progress: number;
-    startTime: number; 
+    // 字节/秒，仅运行中有效
+    rate: number;
+    etaSecs?: number;
+    // verify: 传输完成，正在校验 SHA-256
+    phase?: 'transfer' | 'verify';
+    checksum?: string;
+    startTime: number;
     error?: string;
-    // 如果您使用 AbortController 来控制取消，可以考虑不放在 Store 里，
-    // 而是维护一个外部的 Map<id, AbortController>，或者在这里暂存（但不推荐存不可序列化对象）
 }
 
+export interface TransferRequest {
+    sessionId: string;
+    serverId?: string;
+    kind: 'upload' | 'download';
+    localPath: string;
+    remotePath: string;
+    name?: string;
+    // 完成后校验 SHA-256，缺省开启
+    verify?: boolean;
+}
+
+// 后端 transfers 表记录
+interface TransferJob {
+    id: string;
+    sessionId: string;
+    serverId?: string;
+    kind: 'upload' | 'download';
+    name: string;
+    localPath: string;
+    remotePath: string;
+    totalBytes: number;
+    transferred: number;
+    status: TransferStatus;
+    error?: string;
+    verify: boolean;
+    checksum?: string;
+    createdAt: number;
+    updatedAt: number;
+}
+
+interface TransferProgress {
+    id: string;
+    phase: 'transfer' | 'verify';
+    transferred: number;
+    total: number;
+    rate: number;
+    etaSecs?: number;
+}
+
+const percent = (done: number, total: number) => total > 0 ? Math.min(100, (done / total) * 100) : 0;
+
+const fromJob = (job: TransferJob, prev?: TransferTask): TransferTask => ({
+    id: job.id,
+    type: job.kind,
+    name: job.name,
+    sessionId: job.sessionId,
+    localPath: job.localPath,
+    remotePath: job.remotePath,
+    size: job.totalBytes,
+    transferred: job.transferred,
+    status: job.status,
+    progress: job.status === 'completed' ? 100 : percent(job.transferred, job.totalBytes),
+    rate: job.status === 'running' ? prev?.rate ?? 0 : 0,
+    etaSecs: job.status === 'running' ? prev?.etaSecs : undefined,
+    phase: job.status === 'running' ? prev?.phase : undefined,
+    checksum: job.checksum ?? undefined,
+    startTime: job.createdAt,
+    error: job.error ?? undefined,
+});
+
 interface TransferState {
     isOpen: boolean;
     tasks: TransferTask[];
-    
+    maxWorkers: number;
+
+    // 载入持久化队列并订阅后端事件 (只执行一次)
+    init: () => Promise<void>;
     toggleOpen: () => void;
-    addTask: (task: TransferTask) => void;
-    updateStatus: (id: string, status: TransferTask['status'], error?: string) => void;
-    updateProgress: (id: string, progress: number) => void;
-    clearCompleted: () => void;
-    
-    // [新增] 删除单个任务，支持传入是否物理删除文件的标志
-    removeTask: (id: string, shouldDeleteFile?: boolean) => Promise<void> | void;
-    
-    // [新增] 取消正在运行的任务
-    cancelTask: (id: string) => void;
+    enqueue: (request: TransferRequest) => Promise<TransferTask>;
+    pauseTask: (id: string) => Promise<void>;
+    // sessionId: 原会话已断开时换绑到新会话
+    resumeTask: (id: string, sessionId?: string) => Promise<void>;
+    cancelTask: (id: string) => Promise<void>;
+    // shouldDeleteFile 仅对下载生效 (删除已下载的本地文件)
+    removeTask: (id: string, shouldDeleteFile?: boolean) => Promise<void>;
+    clearCompleted: () => Promise<void>;
+    setMaxWorkers: (maxWorkers: number) => Promise<void>;
 }
 
-export const useTransferStore = create<TransferState>((set, get) => ({
-    isOpen: false,
-    tasks: [],
-
-    toggleOpen: () => set((state) => ({ isOpen: !state.isOpen })),
-
-    addTask: (task) => set((state) => ({ 
-        tasks: [task, ...state.tasks], 
-        isOpen: true 
-    })),
-
-    updateStatus: (id, status, error) => set((state) => ({
-        tasks: state.tasks.map(t => 
-            t.id === id ? { ...t, status, error } : t
-        )
-    })),
-
-    updateProgress: (id, progress) => set((state) => ({
-        tasks: state.tasks.map(t => 
-            t.id === id ? { ...t, progress } : t
-        )
-    })),
-
-    clearCompleted: () => set((state) => ({
-        tasks: state.tasks.filter(t => t.status === 'running')
-    })),
-
-    // [实现] 取消任务逻辑
-    cancelTask: (id) => {
-        const task = get().tasks.find(t => t.id === id);
-        
-        // 只有正在运行的任务才需要取消
-        if (task && task.status === 'running') {
-            console.log(`正在请求取消任务: ${id}`);
-            
-            // TODO: 这里是关键！需要对接实际的网络请求取消逻辑
-            // 1. 如果是 fetch/axios，调用 AbortController.abort()
-            // 2. 如果是 Node.js Stream / SSH，调用 stream.destroy() 或 ssh.end()
-            // 示例：window.transferManager.abort(id);
-
-            // 更新 UI 状态为 Error (并标记为用户取消)
+let initPromise: Promise<void> | null = null;
+
+export const useTransferStore = create<TransferState>((set, get) => {
+    const upsert = (job: TransferJob) => set((state) => {
+        const prev = state.tasks.find(t => t.id === job.id);
+        const task = fromJob(job, prev);
+        return {
+            tasks: prev
+                ? state.tasks.map(t => t.id === job.id ? task : t)
+                : [task, ...state.tasks]
+        };
+    });
+
+    return {
+        isOpen: false,
+        tasks: [],
+        maxWorkers: 3,
+
+        init: () => {
+            if (!initPromise) {
+                initPromise = (async () => {
+                    await listen<TransferJob>('transfer-status', (e) => upsert(e.payload));
+                    await listen<TransferProgress>('transfer-progress', (e) => {
+                        const p = e.payload;
+                        set((state) => ({
+                            tasks: state.tasks.map(t => t.id === p.id ? {
+                                ...t,
+                                size: p.total,
+                                transferred: p.transferred,
+                                progress: percent(p.transferred, p.total),
+                                rate: p.rate,
+                                etaSecs: p.etaSecs ?? undefined,
+                                phase: p.phase,
+                            } : t)
+                        }));
+                    });
+                    const [jobs, settings] = await Promise.all([
+                        invoke<TransferJob[]>('list_transfers'),
+                        invoke<{ maxWorkers: number }>('get_transfer_settings'),
+                    ]);
+                    set({ tasks: jobs.map(j => fromJob(j)), maxWorkers: settings.maxWorkers });
+                })().catch((e) => {
+                    initPromise = null;
+                    throw e;
+                });
+            }
+            return initPromise;
+        },
+
+        toggleOpen: () => set((state) => ({ isOpen: !state.isOpen })),
+
+        enqueue: async (request) => {
+            await get().init();
+            const job = await invoke<TransferJob>('enqueue_transfer', { request });
+            upsert(job);
+            set({ isOpen: true });
+            return get().tasks.find(t => t.id === job.id) ?? fromJob(job);
+        },
+
+        pauseTask: async (id) => {
+            upsert(await invoke<TransferJob>('pause_transfer', { id }));
+        },
+
+        resumeTask: async (id, sessionId) => {
+            upsert(await invoke<TransferJob>('resume_transfer', { id, sessionId }));
+        },
+
+        cancelTask: async (id) => {
+            upsert(await invoke<TransferJob>('cancel_transfer', { id }));
+        },
+
+        removeTask: async (id, shouldDeleteFile = false) => {
+            await invoke('remove_transfer', { id, deleteFile: shouldDeleteFile });
             set((state) => ({
-                tasks: state.tasks.map(t => 
-                    t.id === id ? { ...t, status: 'error', error: '用户取消' } : t
-                )
+                tasks: state.tasks.filter((t) => t.id !== id),
             }));
-        }
-    },
-
-    // [保留] 删除任务逻辑
-    removeTask: async (id, shouldDeleteFile = false) => {
-        // 1. 如果需要删除物理文件，先获取任务信息
-        if (shouldDeleteFile) {
-            const task = get().tasks.find(t => t.id === id);
-            if (task) {
-                // 安全检查：只允许删除本地文件
-                // 如果是 Upload 任务，localPath 是源文件，通常不建议默认删除，但这里遵循用户 Checkbox 的选择
-                // 如果是 Download 任务，localPath 是下载下来的文件
-                try {
-                    console.log(`正在请求删除物理文件: ${task.localPath}`);
-                    
-                    // TODO: 在这里调用后端 API 或 Electron/Tauri 文件系统接口
-                    // await window.fs.unlink(task.localPath);
-                    
-                } catch (error) {
-                    console.error('删除物理文件失败:', error);
-                    // 即使物理删除失败，通常也应该继续移除 UI 上的记录，或者给用户一个 Toast 提示
-                }
-            }
-        }
-
-        // 2. 从 Store 列表中移除记录
-        set((state) => ({
-            tasks: state.tasks.filter((t) => t.id !== id),
-        }));
-    }
-}));
\ No newline at end of file
+        },
+
+        clearCompleted: async () => {
+            await invoke('clear_finished_transfers');
+            set((state) => ({
+                tasks: state.tasks.filter(t => !['completed', 'failed', 'cancelled'].includes(t.status))
+            }));
+        },
+
+        setMaxWorkers: async (maxWorkers) => {
+            const settings = await invoke<{ maxWorkers: number }>('set_transfer_concurrency', { maxWorkers });
+            set({ maxWorkers: settings.maxWorkers });
+        },
+    };
+});